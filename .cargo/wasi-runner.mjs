@@ -0,0 +1,29 @@
+// Runs a wasm32-wasip1 test or benchmark binary under Node's WASI.
+//
+// Usage: node .cargo/wasi-runner.mjs <binary.wasm> [args...]
+//
+// The current directory and the filesystem root are preopened, so that
+// relative and absolute paths resolve as they do natively. Criterion reports
+// go to target/wasm32-wasip1/criterion unless CRITERION_HOME is set, so that
+// they are not compared against native baselines.
+
+import { readFile } from "node:fs/promises";
+import { dirname, resolve } from "node:path";
+import { argv, env, exit } from "node:process";
+import { WASI } from "node:wasi";
+
+const [binary, ...args] = argv.slice(2);
+const wasi = new WASI({
+  version: "preview1",
+  args: [binary, ...args],
+  env: {
+    // the binary is in <target>/wasm32-wasip1/<profile>/deps
+    CRITERION_HOME: resolve(dirname(binary), "../../criterion"),
+    ...env,
+  },
+  preopens: { ".": ".", "/": "/" },
+  returnOnExit: true,
+});
+const module = await WebAssembly.compile(await readFile(binary));
+const instance = await WebAssembly.instantiate(module, wasi.getImportObject());
+exit(wasi.start(instance));
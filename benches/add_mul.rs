@@ -1,6 +1,99 @@
+#![cfg_attr(feature = "portable-simd", feature(portable_simd))]
+
+use criterion::measurement::WallTime;
 use criterion::*;
 use itertools::Itertools;
-use prime_field;
+use prime_field::{
+    babybear, binary, bls12_381_fr, bn254, curve25519, koalabear, mersenne31, mersenne61, p128,
+    secp256k1, solinas_c, stark252, Barrett, Canonical, Crandall, FieldElement, Fp2, Fp3,
+    GenericPrimeField, Limbs32, Modulo, Montgomery, PackedField, Plantard, PrimeField64,
+    PseudoMersenne, Reduce159, Reducer, P64,
+};
+
+mod candidates;
+
+use candidates::*;
+
+fn bench_add<F: PrimeField64>(group: &mut BenchmarkGroup<WallTime>, n_operations: usize) {
+    let operands = F::random(n_operations + 1);
+
+    let id = BenchmarkId::new(F::NAME, n_operations);
+    group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for (&x, &y) in operands.iter().tuple_windows() {
+                F::add(x, y);
+            }
+        });
+    });
+}
+
+fn bench_sub<F: PrimeField64>(group: &mut BenchmarkGroup<WallTime>, n_operations: usize) {
+    let operands = F::random(n_operations + 1);
+
+    let id = BenchmarkId::new(F::NAME, n_operations);
+    group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for (&x, &y) in operands.iter().tuple_windows() {
+                F::sub(x, y);
+            }
+        });
+    });
+}
+
+fn bench_mul<F: PrimeField64>(group: &mut BenchmarkGroup<WallTime>, n_operations: usize) {
+    let operands = F::random(n_operations + 1);
+
+    let id = BenchmarkId::new(F::NAME, n_operations);
+    group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for (&x, &y) in operands.iter().tuple_windows() {
+                F::mul(x, y);
+            }
+        });
+    });
+}
+
+fn bench_mul_add<F: PrimeField64>(group: &mut BenchmarkGroup<WallTime>, n_operations: usize) {
+    use std::hint::black_box;
+
+    let operands = F::random(n_operations + 2);
+
+    let id = BenchmarkId::new(format!("{}/mul+add", F::NAME), n_operations);
+    group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for (&a, &b, &c) in operands.iter().tuple_windows() {
+                black_box(F::add(F::mul(a, b), c));
+            }
+        });
+    });
+
+    let id = BenchmarkId::new(format!("{}/mul_add", F::NAME), n_operations);
+    group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for (&a, &b, &c) in operands.iter().tuple_windows() {
+                black_box(F::mul_add(a, b, c));
+            }
+        });
+    });
+}
+
+fn bench_element<R: Reducer>(group: &mut BenchmarkGroup<WallTime>, n_operations: usize) {
+    let operands = R::random(n_operations + 1)
+        .into_iter()
+        .map(FieldElement::<R>::new)
+        .collect::<Vec<_>>();
+
+    let id = BenchmarkId::new(R::NAME, n_operations);
+    group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            let mut acc = FieldElement::<R>::new(1);
+            for (&x, &y) in operands.iter().tuple_windows() {
+                acc *= x + y;
+            }
+            acc
+        });
+    });
+}
 
 fn add_benchmark(c: &mut Criterion) {
     let n_samples = 1_000;
@@ -20,29 +113,89 @@ fn add_benchmark(c: &mut Criterion) {
         });
     });
 
-    let id = BenchmarkId::new("mod", n_operations);
-    add_group.bench_function(id, |bencher| {
+    bench_add::<Modulo>(&mut add_group, n_operations);
+    bench_add::<Reduce159>(&mut add_group, n_operations);
+    bench_add::<Montgomery>(&mut add_group, n_operations);
+    bench_add::<Limbs32>(&mut add_group, n_operations);
+    bench_add::<GenericPrimeField<P64>>(&mut add_group, n_operations);
+    candidate_fields!(bench_add)
+        .iter()
+        .for_each(|bench| bench(&mut add_group, n_operations));
+    bench_add::<babybear::Modulo>(&mut add_group, n_operations);
+    bench_add::<babybear::Barrett>(&mut add_group, n_operations);
+    bench_add::<babybear::Montgomery>(&mut add_group, n_operations);
+    bench_add::<koalabear::Modulo>(&mut add_group, n_operations);
+    bench_add::<koalabear::Barrett>(&mut add_group, n_operations);
+    bench_add::<koalabear::Montgomery>(&mut add_group, n_operations);
+    bench_add::<koalabear::Fold>(&mut add_group, n_operations);
+    bench_add::<mersenne31::Modulo>(&mut add_group, n_operations);
+    bench_add::<mersenne31::Fold>(&mut add_group, n_operations);
+    bench_add::<mersenne61::Modulo>(&mut add_group, n_operations);
+    bench_add::<mersenne61::Fold>(&mut add_group, n_operations);
+}
+
+fn sub_benchmark(c: &mut Criterion) {
+    let n_samples = 1_000;
+    let n_operations = 1_000;
+
+    let mut sub_group = c.benchmark_group("sub");
+    sub_group.sample_size(n_samples);
+
+    let operands = prime_field::random_elements(n_operations + 1);
+
+    let id = BenchmarkId::new("baseline", n_operations);
+    sub_group.bench_function(id, |bencher| {
         bencher.iter(|| {
             for (&x, &y) in operands.iter().tuple_windows() {
-                prime_field::add_modulo(x, y);
+                let _diff = x.wrapping_sub(y);
             }
         });
     });
 
-    let id = BenchmarkId::new("fast", n_operations);
-    add_group.bench_function(id, |bencher| {
+    bench_sub::<Modulo>(&mut sub_group, n_operations);
+    bench_sub::<Reduce159>(&mut sub_group, n_operations);
+    bench_sub::<Montgomery>(&mut sub_group, n_operations);
+    bench_sub::<Limbs32>(&mut sub_group, n_operations);
+    bench_sub::<GenericPrimeField<P64>>(&mut sub_group, n_operations);
+    candidate_fields!(bench_sub)
+        .iter()
+        .for_each(|bench| bench(&mut sub_group, n_operations));
+    bench_sub::<babybear::Modulo>(&mut sub_group, n_operations);
+    bench_sub::<babybear::Barrett>(&mut sub_group, n_operations);
+    bench_sub::<babybear::Montgomery>(&mut sub_group, n_operations);
+    bench_sub::<koalabear::Modulo>(&mut sub_group, n_operations);
+    bench_sub::<koalabear::Barrett>(&mut sub_group, n_operations);
+    bench_sub::<koalabear::Montgomery>(&mut sub_group, n_operations);
+    bench_sub::<koalabear::Fold>(&mut sub_group, n_operations);
+    bench_sub::<mersenne31::Modulo>(&mut sub_group, n_operations);
+    bench_sub::<mersenne31::Fold>(&mut sub_group, n_operations);
+    bench_sub::<mersenne61::Modulo>(&mut sub_group, n_operations);
+    bench_sub::<mersenne61::Fold>(&mut sub_group, n_operations);
+}
+
+fn neg_benchmark(c: &mut Criterion) {
+    let n_samples = 1_000;
+    let n_operations = 1_000;
+
+    let mut neg_group = c.benchmark_group("neg");
+    neg_group.sample_size(n_samples);
+
+    let operands = prime_field::random_elements(n_operations);
+
+    let id = BenchmarkId::new("neg", n_operations);
+    neg_group.bench_function(id, |bencher| {
         bencher.iter(|| {
-            for (&x, &y) in operands.iter().tuple_windows() {
-                prime_field::add_with_sub_u128(x, y);
+            for &x in operands.iter() {
+                prime_field::neg(x);
             }
         });
     });
 
-    let id = BenchmarkId::new("winterfell", n_operations);
-    add_group.bench_function(id, |bencher| {
+    let id = BenchmarkId::new("neg_branchless", n_operations);
+    neg_group.bench_function(id, |bencher| {
         bencher.iter(|| {
-            for (&x, &y) in operands.iter().tuple_windows() {
-                prime_field::add_winterfell(x, y);
+            for &x in operands.iter() {
+                prime_field::neg_branchless(x);
             }
         });
     });
@@ -66,33 +219,2098 @@ fn mul_benchmark(c: &mut Criterion) {
         });
     });
 
-    let id = BenchmarkId::new("mod", n_operations);
-    mul_group.bench_function(id, |bencher| {
+    bench_mul::<Modulo>(&mut mul_group, n_operations);
+    bench_mul::<Reduce159>(&mut mul_group, n_operations);
+    #[cfg(all(
+        feature = "asm",
+        target_arch = "x86_64",
+        target_feature = "bmi2",
+        target_feature = "adx"
+    ))]
+    bench_mul::<prime_field::asm::MulxAdx>(&mut mul_group, n_operations);
+    #[cfg(all(feature = "asm", target_arch = "aarch64"))]
+    bench_mul::<prime_field::asm::Umulh>(&mut mul_group, n_operations);
+    #[cfg(all(feature = "asm", target_arch = "riscv64"))]
+    bench_mul::<prime_field::asm::Mulhu>(&mut mul_group, n_operations);
+    bench_mul::<Barrett>(&mut mul_group, n_operations);
+    bench_mul::<Crandall>(&mut mul_group, n_operations);
+    bench_mul::<Montgomery>(&mut mul_group, n_operations);
+    bench_mul::<Plantard>(&mut mul_group, n_operations);
+    bench_mul::<Limbs32>(&mut mul_group, n_operations);
+    bench_mul::<GenericPrimeField<P64>>(&mut mul_group, n_operations);
+    candidate_fields!(bench_mul)
+        .iter()
+        .for_each(|bench| bench(&mut mul_group, n_operations));
+    bench_mul::<babybear::Modulo>(&mut mul_group, n_operations);
+    bench_mul::<babybear::Barrett>(&mut mul_group, n_operations);
+    bench_mul::<babybear::Montgomery>(&mut mul_group, n_operations);
+    bench_mul::<koalabear::Modulo>(&mut mul_group, n_operations);
+    bench_mul::<koalabear::Barrett>(&mut mul_group, n_operations);
+    bench_mul::<koalabear::Montgomery>(&mut mul_group, n_operations);
+    bench_mul::<koalabear::Fold>(&mut mul_group, n_operations);
+    bench_mul::<mersenne31::Modulo>(&mut mul_group, n_operations);
+    bench_mul::<mersenne31::Fold>(&mut mul_group, n_operations);
+    bench_mul::<mersenne61::Modulo>(&mut mul_group, n_operations);
+    bench_mul::<mersenne61::Fold>(&mut mul_group, n_operations);
+}
+
+fn reduce_benchmark(c: &mut Criterion) {
+    use std::hint::black_box;
+
+    let n_samples = 1_000;
+    let n_operations = 1_000;
+
+    let mut reduce_group = c.benchmark_group("reduce");
+    reduce_group.sample_size(n_samples);
+
+    let operands = (0..n_operations)
+        .map(|_| rand::random::<u128>())
+        .collect::<Vec<_>>();
+
+    fn bench_reduce(
+        group: &mut BenchmarkGroup<WallTime>,
+        name: &str,
+        operands: &[u128],
+        f: impl Fn(u128) -> u64,
+    ) {
+        let id = BenchmarkId::new(name, operands.len());
+        group.bench_function(id, |bencher| {
+            bencher.iter(|| {
+                for &x in operands.iter() {
+                    black_box(f(black_box(x)));
+                }
+            });
+        });
+    }
+
+    bench_reduce(&mut reduce_group, "reduce159", &operands, Reduce159::reduce);
+    bench_reduce(
+        &mut reduce_group,
+        "crandall",
+        &operands,
+        prime_field::reduce_crandall,
+    );
+    bench_reduce(
+        &mut reduce_group,
+        "barrett",
+        &operands,
+        prime_field::reduce_barrett,
+    );
+    #[cfg(all(
+        feature = "asm",
+        target_arch = "x86_64",
+        target_feature = "bmi2",
+        target_feature = "adx"
+    ))]
+    bench_reduce(
+        &mut reduce_group,
+        "mulx_adx",
+        &operands,
+        prime_field::asm::MulxAdx::reduce,
+    );
+    #[cfg(all(feature = "asm", target_arch = "aarch64"))]
+    bench_reduce(
+        &mut reduce_group,
+        "umulh",
+        &operands,
+        prime_field::asm::Umulh::reduce,
+    );
+    #[cfg(all(feature = "asm", target_arch = "riscv64"))]
+    bench_reduce(
+        &mut reduce_group,
+        "mulhu",
+        &operands,
+        prime_field::asm::Mulhu::reduce,
+    );
+    let strategies = operands
+        .iter()
+        .map(|_| prime_field::ReduceStrategy::ALL[rand::random::<usize>() % 5])
+        .collect::<Vec<_>>();
+    let id = BenchmarkId::new("reduce128/random_strategy", n_operations);
+    reduce_group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for (&x, &strategy) in operands.iter().zip(strategies.iter()) {
+                black_box(prime_field::reduce128(black_box(x), strategy));
+            }
+        });
+    });
+
+    let small_operands = operands.iter().map(|&x| x >> 32).collect::<Vec<_>>();
+    bench_reduce(
+        &mut reduce_group,
+        "reduce159/96_bits",
+        &small_operands,
+        Reduce159::reduce,
+    );
+    bench_reduce(
+        &mut reduce_group,
+        "reduce96/96_bits",
+        &small_operands,
+        prime_field::reduce96,
+    );
+    bench_reduce(&mut reduce_group, "limbs32", &operands, Limbs32::reduce);
+    bench_reduce(&mut reduce_group, "limbs32/four_limbs", &operands, |x| {
+        prime_field::reduce_limbs32([
+            x as u32,
+            (x >> 32) as u32,
+            (x >> 64) as u32,
+            (x >> 96) as u32,
+        ])
+    });
+}
+
+fn unstructured_benchmark(c: &mut Criterion) {
+    use std::hint::black_box;
+
+    // the RC5 magic constant from the digits of e, which happens to be prime
+    const UNSTRUCTURED: u64 = 0xb7e1_5162_8aed_2a6b;
+
+    let n_samples = 1_000;
+    let n_operations = 1_000;
+
+    let mut structure_group = c.benchmark_group("modulus_structure");
+    structure_group.sample_size(n_samples);
+
+    fn bench_generic<const P: u64>(group: &mut BenchmarkGroup<WallTime>, label: &str) {
+        type F<const P: u64> = GenericPrimeField<P>;
+
+        let operands = F::<P>::random(1_001);
+        let montgomery = operands
+            .iter()
+            .map(|&x| F::<P>::to_montgomery(x))
+            .collect::<Vec<_>>();
+
+        let id = BenchmarkId::new(format!("{label}/barrett"), 1_000);
+        group.bench_function(id, |bencher| {
+            bencher.iter(|| {
+                for (&x, &y) in operands.iter().tuple_windows() {
+                    black_box(F::<P>::mul(black_box(x), y));
+                }
+            });
+        });
+
+        let id = BenchmarkId::new(format!("{label}/montgomery"), 1_000);
+        group.bench_function(id, |bencher| {
+            bencher.iter(|| {
+                for (&x, &y) in montgomery.iter().tuple_windows() {
+                    black_box(F::<P>::mul_montgomery(black_box(x), y));
+                }
+            });
+        });
+    }
+
+    bench_generic::<P64>(&mut structure_group, "goldilocks/generic");
+    bench_generic::<UNSTRUCTURED>(&mut structure_group, "unstructured/generic");
+
+    // the fold-based reduction generated from p = 2^N - C, against Barrett on the same p
+    fn bench_pseudo_mersenne<const N: u32, const C: u64>(
+        group: &mut BenchmarkGroup<WallTime>,
+        label: &str,
+    ) {
+        type F<const N: u32, const C: u64> = PseudoMersenne<N, C>;
+
+        let operands = F::<N, C>::random(1_001);
+
+        let id = BenchmarkId::new(format!("{label}/fold"), 1_000);
+        group.bench_function(id, |bencher| {
+            bencher.iter(|| {
+                for (&x, &y) in operands.iter().tuple_windows() {
+                    black_box(F::<N, C>::mul(black_box(x), y));
+                }
+            });
+        });
+    }
+
+    const GOLDILOCKS_C: u64 = solinas_c(&[(1, 32), (-1, 0)]);
+    const CANDIDATE_C: u64 = solinas_c(&[(1, 8), (1, 0)]);
+    bench_pseudo_mersenne::<64, GOLDILOCKS_C>(&mut structure_group, "goldilocks/solinas");
+    bench_pseudo_mersenne::<64, CANDIDATE_C>(&mut structure_group, "2^64-2^8-1/solinas");
+    bench_generic::<{ 0u64.wrapping_sub(CANDIDATE_C) }>(&mut structure_group, "2^64-2^8-1/generic");
+
+    let operands = prime_field::random_elements(n_operations);
+
+    let id = BenchmarkId::new("goldilocks/reduce159", n_operations);
+    structure_group.bench_function(id, |bencher| {
         bencher.iter(|| {
             for (&x, &y) in operands.iter().tuple_windows() {
-                prime_field::mul_modulo(x, y);
+                black_box(Reduce159::mul(black_box(x), y));
             }
         });
     });
 
-    let id = BenchmarkId::new("reduce159", n_operations);
-    mul_group.bench_function(id, |bencher| {
+    let id = BenchmarkId::new("goldilocks/montgomery", n_operations);
+    structure_group.bench_function(id, |bencher| {
         bencher.iter(|| {
             for (&x, &y) in operands.iter().tuple_windows() {
-                prime_field::mul_reduce159(x, y);
+                black_box(prime_field::mul_reduce_montgomery(black_box(x), y));
             }
         });
     });
+}
 
-    let id = BenchmarkId::new("reduce_montgomery", n_operations);
-    mul_group.bench_function(id, |bencher| {
+fn multi_limb_benchmark(c: &mut Criterion) {
+    use std::hint::black_box;
+
+    use prime_field::MultiLimbModulus;
+
+    let n_samples = 100;
+    let n_operations = 1_000;
+
+    let mut multi_limb_group = c.benchmark_group("multi_limb");
+    multi_limb_group.sample_size(n_samples);
+
+    fn bench_montgomery<const N: usize>(
+        group: &mut BenchmarkGroup<WallTime>,
+        label: &str,
+        field: MultiLimbModulus<N>,
+        n_operations: usize,
+    ) {
+        // a top limb below that of p keeps the values below p
+        let operands = (0..=n_operations)
+            .map(|_| {
+                let mut x = std::array::from_fn(|_| rand::random::<u64>());
+                x[N - 1] %= field.modulus[N - 1];
+                field.to_montgomery(&x)
+            })
+            .collect::<Vec<_>>();
+
+        let id = BenchmarkId::new(format!("{label}/sos"), n_operations);
+        group.bench_function(id, |bencher| {
+            bencher.iter(|| {
+                for (x, y) in operands.iter().tuple_windows() {
+                    black_box(field.mul_sos(black_box(x), y));
+                }
+            });
+        });
+
+        let id = BenchmarkId::new(format!("{label}/cios"), n_operations);
+        group.bench_function(id, |bencher| {
+            bencher.iter(|| {
+                for (x, y) in operands.iter().tuple_windows() {
+                    black_box(field.mul_cios(black_box(x), y));
+                }
+            });
+        });
+    }
+
+    bench_montgomery(&mut multi_limb_group, "p128", p128::FIELD, n_operations);
+
+    let operands = (0..=n_operations)
+        .map(|_| {
+            let x = [rand::random(), rand::random::<u64>() % p128::MODULUS[1]];
+            p128::FIELD.to_montgomery(&x)
+        })
+        .collect::<Vec<_>>();
+
+    let id = BenchmarkId::new("p128/specialized", n_operations);
+    multi_limb_group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for (x, y) in operands.iter().tuple_windows() {
+                black_box(p128::mul(black_box(x), y));
+            }
+        });
+    });
+
+    // 2^255 - 19
+    let p25519 = MultiLimbModulus::new([u64::MAX - 18, u64::MAX, u64::MAX, u64::MAX >> 1]);
+    bench_montgomery(&mut multi_limb_group, "p25519", p25519, n_operations);
+
+    // acc = acc * y + z in both layouts; radix-2^51 additions skip carry propagation
+    let operands = (0..=n_operations)
+        .map(|_| {
+            let mut x = std::array::from_fn(|_| rand::random::<u64>());
+            x[3] >>= 2;
+            x
+        })
+        .collect::<Vec<[u64; 4]>>();
+    let operands_51 = operands
+        .iter()
+        .map(curve25519::to_radix51)
+        .collect::<Vec<_>>();
+
+    let id = BenchmarkId::new("p25519/saturated", n_operations);
+    multi_limb_group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for (x, y, z) in operands.iter().tuple_windows() {
+                black_box(curve25519::add(&curve25519::mul(black_box(x), y), z));
+            }
+        });
+    });
+
+    let id = BenchmarkId::new("p25519/radix51", n_operations);
+    multi_limb_group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for (x, y, z) in operands_51.iter().tuple_windows() {
+                black_box(curve25519::add51(&curve25519::mul51(black_box(x), y), z));
+            }
+        });
+    });
+
+    bench_montgomery(&mut multi_limb_group, "bn254", bn254::FIELD, n_operations);
+    bench_montgomery(
+        &mut multi_limb_group,
+        "bls12_381_fr",
+        bls12_381_fr::FIELD,
+        n_operations,
+    );
+    bench_montgomery(
+        &mut multi_limb_group,
+        "stark252",
+        stark252::FIELD,
+        n_operations,
+    );
+
+    let operands = (0..=n_operations)
+        .map(|_| {
+            let mut x = std::array::from_fn(|_| rand::random::<u64>());
+            x[3] %= stark252::MODULUS[3];
+            stark252::FIELD.to_montgomery(&x)
+        })
+        .collect::<Vec<_>>();
+
+    let id = BenchmarkId::new("stark252/specialized", n_operations);
+    multi_limb_group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for (x, y) in operands.iter().tuple_windows() {
+                black_box(stark252::mul(black_box(x), y));
+            }
+        });
+    });
+
+    bench_montgomery(
+        &mut multi_limb_group,
+        "secp256k1",
+        secp256k1::FIELD,
+        n_operations,
+    );
+
+    // random limbs are below p but for a 2^-224 chance, and need no Montgomery form
+    let operands = (0..=n_operations)
+        .map(|_| std::array::from_fn(|_| rand::random::<u64>()))
+        .collect::<Vec<[u64; 4]>>();
+
+    let id = BenchmarkId::new("secp256k1/pseudo_mersenne", n_operations);
+    multi_limb_group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for (x, y) in operands.iter().tuple_windows() {
+                black_box(secp256k1::mul(black_box(x), y));
+            }
+        });
+    });
+
+    // the same workload on single-limb Goldilocks, for scale
+    let operands = prime_field::random_elements(n_operations);
+
+    let id = BenchmarkId::new("goldilocks/montgomery", n_operations);
+    multi_limb_group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for (&x, &y) in operands.iter().tuple_windows() {
+                black_box(prime_field::mul_reduce_montgomery(black_box(x), y));
+            }
+        });
+    });
+
+    let bls12_381 = MultiLimbModulus::new([
+        0xb9fe_ffff_ffff_aaab,
+        0x1eab_fffe_b153_ffff,
+        0x6730_d2a0_f6b0_f624,
+        0x6477_4b84_f385_12bf,
+        0x4b1b_a7b6_434b_acd7,
+        0x1a01_11ea_397f_e69a,
+    ]);
+    bench_montgomery(&mut multi_limb_group, "bls12_381", bls12_381, n_operations);
+
+    // acc = acc * y + z, reduced to [0, p) only at the end of the relaxed chain
+    let chain_length = 16;
+    let operands = (0..n_operations)
+        .map(|_| {
+            let mut x = std::array::from_fn(|_| rand::random::<u64>());
+            x[5] %= bls12_381.modulus[5];
+            bls12_381.to_montgomery(&x)
+        })
+        .collect::<Vec<_>>();
+    let (y, z) = (operands[0], operands[1]);
+
+    let id = BenchmarkId::new(format!("bls12_381/strict/{chain_length}"), n_operations);
+    multi_limb_group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for x in operands.iter() {
+                let mut acc = *black_box(x);
+                for _ in 0..chain_length {
+                    acc = bls12_381.add(&bls12_381.mul_cios(&acc, &y), &z);
+                }
+                black_box(acc);
+            }
+        });
+    });
+
+    let id = BenchmarkId::new(format!("bls12_381/relaxed/{chain_length}"), n_operations);
+    multi_limb_group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for x in operands.iter() {
+                let mut acc = *black_box(x);
+                for _ in 0..chain_length {
+                    acc = bls12_381.add_relaxed(&bls12_381.mul_cios_relaxed(&acc, &y), &z);
+                }
+                black_box(bls12_381.reduce_relaxed(&acc));
+            }
+        });
+    });
+}
+
+fn binary_benchmark(c: &mut Criterion) {
+    use std::hint::black_box;
+
+    let n_samples = 1_000;
+    let n_operations = 1_000;
+
+    let mut binary_group = c.benchmark_group("binary");
+    binary_group.sample_size(n_samples);
+
+    // the slice kernels detect PCLMULQDQ or PMULL at runtime
+    let backend = if binary::is_available() {
+        "clmul"
+    } else {
+        "soft"
+    };
+    let operands = (0..=n_operations)
+        .map(|_| rand::random::<u64>())
+        .collect::<Vec<_>>();
+
+    let id = BenchmarkId::new("gf2_64/soft", n_operations);
+    binary_group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for (&x, &y) in operands.iter().tuple_windows() {
+                black_box(binary::mul64_soft(black_box(x), y));
+            }
+        });
+    });
+
+    let mut dst = vec![0; n_operations];
+    let id = BenchmarkId::new(format!("gf2_64/slices/{backend}"), n_operations);
+    binary_group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            binary::mul64_slices(
+                &mut dst,
+                black_box(&operands[..n_operations]),
+                &operands[1..],
+            );
+        });
+    });
+
+    let operands = (0..=n_operations)
+        .map(|_| rand::random::<u128>())
+        .collect::<Vec<_>>();
+
+    let id = BenchmarkId::new("gf2_128/soft", n_operations);
+    binary_group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for (&x, &y) in operands.iter().tuple_windows() {
+                black_box(binary::mul128_soft(black_box(x), y));
+            }
+        });
+    });
+
+    let mut dst = vec![0; n_operations];
+    let id = BenchmarkId::new(format!("gf2_128/slices/{backend}"), n_operations);
+    binary_group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            binary::mul128_slices(
+                &mut dst,
+                black_box(&operands[..n_operations]),
+                &operands[1..],
+            );
+        });
+    });
+
+    // prime fields of the same sizes on the same workload
+    let operands = prime_field::random_elements(n_operations);
+
+    let id = BenchmarkId::new("goldilocks/reduce159", n_operations);
+    binary_group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for (&x, &y) in operands.iter().tuple_windows() {
+                black_box(Reduce159::mul(black_box(x), y));
+            }
+        });
+    });
+
+    let operands = (0..=n_operations)
+        .map(|_| {
+            let x = [rand::random(), rand::random::<u64>() % p128::MODULUS[1]];
+            p128::FIELD.to_montgomery(&x)
+        })
+        .collect::<Vec<_>>();
+
+    let id = BenchmarkId::new("p128/specialized", n_operations);
+    binary_group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for (x, y) in operands.iter().tuple_windows() {
+                black_box(p128::mul(black_box(x), y));
+            }
+        });
+    });
+}
+
+fn mersenne31_benchmark(c: &mut Criterion) {
+    use std::hint::black_box;
+
+    let n_samples = 1_000;
+    let n_operations = 1_000;
+
+    let mut mersenne31_group = c.benchmark_group("mersenne31");
+    mersenne31_group.sample_size(n_samples);
+
+    let operands = mersenne31::Modulo::random(n_operations + 1)
+        .into_iter()
+        .map(|x| x as u32)
+        .collect::<Vec<_>>();
+
+    // acc = acc * y, canonicalized only at the end of the partial chain
+    let chain_length = 16;
+
+    let id = BenchmarkId::new(format!("strict/{chain_length}"), n_operations);
+    mersenne31_group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for (&x, &y) in operands.iter().tuple_windows() {
+                let mut acc = black_box(x);
+                for _ in 0..chain_length {
+                    acc = mersenne31::mul(acc, y);
+                }
+                black_box(acc);
+            }
+        });
+    });
+
+    let id = BenchmarkId::new(format!("partial/{chain_length}"), n_operations);
+    mersenne31_group.bench_function(id, |bencher| {
         bencher.iter(|| {
             for (&x, &y) in operands.iter().tuple_windows() {
-                prime_field::mul_reduce_montgomery(x, y);
+                let mut acc = black_box(x);
+                for _ in 0..chain_length {
+                    acc = mersenne31::mul_partial(acc, y);
+                }
+                black_box(mersenne31::canonicalize(acc));
+            }
+        });
+    });
+
+    let shifts = operands
+        .iter()
+        .map(|&x| (x, rand::random::<u32>() % 31))
+        .collect::<Vec<_>>();
+
+    let id = BenchmarkId::new("mul/pow2", n_operations);
+    mersenne31_group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for &(x, k) in shifts.iter() {
+                black_box(mersenne31::mul(black_box(x), 1 << k));
+            }
+        });
+    });
+
+    let id = BenchmarkId::new("mul_pow2", n_operations);
+    mersenne31_group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for &(x, k) in shifts.iter() {
+                black_box(mersenne31::mul_pow2(black_box(x), k));
+            }
+        });
+    });
+}
+
+fn bench_square<F: PrimeField64>(group: &mut BenchmarkGroup<WallTime>, n_operations: usize) {
+    use std::hint::black_box;
+
+    let operands = F::random(n_operations);
+
+    let id = BenchmarkId::new(format!("{}/mul", F::NAME), n_operations);
+    group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for &x in operands.iter() {
+                black_box(F::mul(x, x));
+            }
+        });
+    });
+
+    let id = BenchmarkId::new(format!("{}/square", F::NAME), n_operations);
+    group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for &x in operands.iter() {
+                black_box(F::square(x));
             }
         });
     });
 }
 
-criterion_group!(add_mul, add_benchmark, mul_benchmark);
+fn mul_add_benchmark(c: &mut Criterion) {
+    let n_samples = 1_000;
+    let n_operations = 1_000;
+
+    let mut mul_add_group = c.benchmark_group("mul_add");
+    mul_add_group.sample_size(n_samples);
+
+    bench_mul_add::<Modulo>(&mut mul_add_group, n_operations);
+    bench_mul_add::<Reduce159>(&mut mul_add_group, n_operations);
+    bench_mul_add::<Limbs32>(&mut mul_add_group, n_operations);
+}
+
+/// A kernel writing an elementwise operation on two slices into the first
+#[cfg(feature = "portable-simd")]
+type SliceKernel = fn(&mut [u64], &[u64], &[u64]);
+
+fn slices_benchmark(c: &mut Criterion) {
+    let n_samples = 100;
+
+    let mut slices_group = c.benchmark_group("slices");
+    slices_group.sample_size(n_samples);
+
+    for len in [256, 4_096] {
+        let a = prime_field::random_elements_exact(len);
+        let b = prime_field::random_elements_exact(len);
+        let mut dst = vec![0; len];
+
+        let id = BenchmarkId::new("add/scalar", len);
+        slices_group.bench_function(id, |bencher| {
+            bencher.iter(|| {
+                for ((d, &x), &y) in dst.iter_mut().zip(&a).zip(&b) {
+                    *d = Reduce159::add(x, y);
+                }
+            });
+        });
+
+        let id = BenchmarkId::new("add/add_slices", len);
+        slices_group.bench_function(id, |bencher| {
+            bencher.iter(|| prime_field::add_slices(&mut dst, &a, &b));
+        });
+
+        let id = BenchmarkId::new("mul/scalar", len);
+        slices_group.bench_function(id, |bencher| {
+            bencher.iter(|| {
+                for ((d, &x), &y) in dst.iter_mut().zip(&a).zip(&b) {
+                    *d = Reduce159::mul(x, y);
+                }
+            });
+        });
+
+        let id = BenchmarkId::new("mul/mul_slices", len);
+        slices_group.bench_function(id, |bencher| {
+            bencher.iter(|| prime_field::mul_slices(&mut dst, &a, &b));
+        });
+
+        let id = BenchmarkId::new("sub/scalar", len);
+        slices_group.bench_function(id, |bencher| {
+            bencher.iter(|| {
+                for ((d, &x), &y) in dst.iter_mut().zip(&a).zip(&b) {
+                    *d = Reduce159::sub(x, y);
+                }
+            });
+        });
+
+        #[cfg(target_arch = "x86_64")]
+        if prime_field::avx2::is_available() {
+            use prime_field::avx2;
+
+            let id = BenchmarkId::new("add/avx2", len);
+            slices_group.bench_function(id, |bencher| {
+                bencher.iter(|| avx2::add_slices(&mut dst, &a, &b));
+            });
+
+            let id = BenchmarkId::new("sub/avx2", len);
+            slices_group.bench_function(id, |bencher| {
+                bencher.iter(|| avx2::sub_slices(&mut dst, &a, &b));
+            });
+
+            let id = BenchmarkId::new("mul/avx2", len);
+            slices_group.bench_function(id, |bencher| {
+                bencher.iter(|| avx2::mul_slices(&mut dst, &a, &b));
+            });
+        }
+
+        #[cfg(target_arch = "x86_64")]
+        if prime_field::avx512::is_available() {
+            use prime_field::avx512;
+
+            let id = BenchmarkId::new("add/avx512", len);
+            slices_group.bench_function(id, |bencher| {
+                bencher.iter(|| avx512::add_slices(&mut dst, &a, &b));
+            });
+
+            let id = BenchmarkId::new("sub/avx512", len);
+            slices_group.bench_function(id, |bencher| {
+                bencher.iter(|| avx512::sub_slices(&mut dst, &a, &b));
+            });
+
+            let id = BenchmarkId::new("mul/avx512", len);
+            slices_group.bench_function(id, |bencher| {
+                bencher.iter(|| avx512::mul_slices(&mut dst, &a, &b));
+            });
+
+            if avx512::is_ifma_available() {
+                let id = BenchmarkId::new("mul/avx512_ifma", len);
+                slices_group.bench_function(id, |bencher| {
+                    bencher.iter(|| avx512::mul_slices_ifma(&mut dst, &a, &b));
+                });
+            }
+        }
+
+        #[cfg(target_arch = "aarch64")]
+        if prime_field::neon::is_available() {
+            use prime_field::neon;
+
+            let id = BenchmarkId::new("add/neon", len);
+            slices_group.bench_function(id, |bencher| {
+                bencher.iter(|| neon::add_slices(&mut dst, &a, &b));
+            });
+
+            let id = BenchmarkId::new("sub/neon", len);
+            slices_group.bench_function(id, |bencher| {
+                bencher.iter(|| neon::sub_slices(&mut dst, &a, &b));
+            });
+
+            let id = BenchmarkId::new("mul/neon", len);
+            slices_group.bench_function(id, |bencher| {
+                bencher.iter(|| neon::mul_slices(&mut dst, &a, &b));
+            });
+        }
+
+        #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+        {
+            use prime_field::simd128;
+
+            let id = BenchmarkId::new("add/simd128", len);
+            slices_group.bench_function(id, |bencher| {
+                bencher.iter(|| simd128::add_slices(&mut dst, &a, &b));
+            });
+
+            let id = BenchmarkId::new("sub/simd128", len);
+            slices_group.bench_function(id, |bencher| {
+                bencher.iter(|| simd128::sub_slices(&mut dst, &a, &b));
+            });
+
+            let id = BenchmarkId::new("mul/simd128", len);
+            slices_group.bench_function(id, |bencher| {
+                bencher.iter(|| simd128::mul_slices(&mut dst, &a, &b));
+            });
+        }
+
+        #[cfg(feature = "portable-simd")]
+        {
+            use prime_field::portable;
+
+            let kernels: [(&str, SliceKernel); 8] = [
+                ("add/portable_x4", portable::add_slices::<4>),
+                ("add/portable_x8", portable::add_slices::<8>),
+                ("sub/portable_x4", portable::sub_slices::<4>),
+                ("sub/portable_x8", portable::sub_slices::<8>),
+                ("mul/portable_x2", portable::mul_slices::<2>),
+                ("mul/portable_x4", portable::mul_slices::<4>),
+                ("mul/portable_x8", portable::mul_slices::<8>),
+                ("mul/portable_x16", portable::mul_slices::<16>),
+            ];
+            for (id, kernel) in kernels {
+                let id = BenchmarkId::new(id, len);
+                slices_group.bench_function(id, |bencher| {
+                    bencher.iter(|| kernel(&mut dst, &a, &b));
+                });
+            }
+        }
+    }
+}
+
+fn bench_packed<P: PackedField>(group: &mut BenchmarkGroup<WallTime>, log_n: u32) {
+    use prime_field::packed;
+    use std::hint::black_box;
+
+    let len = 1 << log_n;
+    let a = prime_field::random_elements_exact(len);
+    let b = prime_field::random_elements_exact(len);
+    let mut dst = vec![0; len];
+    let backend = format!("{}_x{}", P::NAME, P::WIDTH);
+
+    let id = BenchmarkId::new(format!("mul_slices/{backend}"), len);
+    group.bench_function(id, |bencher| {
+        bencher.iter(|| packed::mul_slices::<P>(&mut dst, &a, &b));
+    });
+
+    let id = BenchmarkId::new(format!("dot/{backend}"), len);
+    group.bench_function(id, |bencher| {
+        bencher.iter(|| black_box(packed::dot::<P>(&a, &b)));
+    });
+
+    // 7 generates the multiplicative group of order 2^32 (2^32 - 1)
+    let root = prime_field::pow(7, (P64 - 1) >> log_n);
+    let twiddles = packed::ntt_twiddles(root, len);
+    let mut values = a[..len].to_vec();
+    let id = BenchmarkId::new(format!("ntt/{backend}"), len);
+    group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            values.copy_from_slice(&a[..len]);
+            packed::ntt::<P>(&mut values, &twiddles);
+        });
+    });
+}
+
+/// Runs the generic kernels on each backend enabled at compile time
+///
+/// Only the scalar backend is enabled by default on x86_64; run with
+/// `RUSTFLAGS="-C target-cpu=native"` to include the SIMD ones.
+fn packed_benchmark(c: &mut Criterion) {
+    let n_samples = 100;
+
+    let mut packed_group = c.benchmark_group("packed");
+    packed_group.sample_size(n_samples);
+
+    let log_n = 12;
+    bench_packed::<u64>(&mut packed_group, log_n);
+    #[cfg(all(target_arch = "x86_64", target_feature = "avx2"))]
+    bench_packed::<prime_field::avx2::PackedAvx2>(&mut packed_group, log_n);
+    #[cfg(all(target_arch = "x86_64", target_feature = "avx512f"))]
+    bench_packed::<prime_field::avx512::PackedAvx512>(&mut packed_group, log_n);
+    #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+    bench_packed::<prime_field::neon::PackedNeon>(&mut packed_group, log_n);
+    #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+    bench_packed::<prime_field::simd128::PackedSimd128>(&mut packed_group, log_n);
+    #[cfg(feature = "portable-simd")]
+    {
+        bench_packed::<std::simd::Simd<u64, 4>>(&mut packed_group, log_n);
+        bench_packed::<std::simd::Simd<u64, 8>>(&mut packed_group, log_n);
+    }
+}
+
+fn scale_benchmark(c: &mut Criterion) {
+    let n_samples = 100;
+
+    let mut scale_group = c.benchmark_group("scale_in_place");
+    scale_group.sample_size(n_samples);
+
+    for len in [256, 4_096] {
+        let mut v = prime_field::random_elements_exact(len);
+        let s = prime_field::random_elements_exact(1)[0];
+
+        let id = BenchmarkId::new("scalar", len);
+        scale_group.bench_function(id, |bencher| {
+            bencher.iter(|| prime_field::scale_in_place(&mut v, s));
+        });
+
+        let id = BenchmarkId::new("unrolled", len);
+        scale_group.bench_function(id, |bencher| {
+            bencher.iter(|| prime_field::scale_in_place_unrolled(&mut v, s));
+        });
+    }
+}
+
+fn linear_combination_benchmark(c: &mut Criterion) {
+    let n_samples = 100;
+    let len = 1_024;
+
+    let mut linear_combination_group = c.benchmark_group("linear_combination");
+    linear_combination_group.sample_size(n_samples);
+
+    for k in [2, 4, 8] {
+        let coeffs = prime_field::random_elements_exact(k);
+        let vectors = (0..k)
+            .map(|_| prime_field::random_elements_exact(len))
+            .collect::<Vec<_>>();
+        let vectors = vectors.iter().map(Vec::as_slice).collect::<Vec<_>>();
+        let mut dst = vec![0; len];
+
+        let id = BenchmarkId::new("mul+add", k);
+        linear_combination_group.bench_function(id, |bencher| {
+            bencher.iter(|| {
+                for (i, d) in dst.iter_mut().enumerate() {
+                    let mut acc = 0;
+                    for (&c, v) in coeffs.iter().zip(&vectors) {
+                        acc = Reduce159::add(acc, Reduce159::mul(c, v[i]));
+                    }
+                    *d = acc;
+                }
+            });
+        });
+
+        let id = BenchmarkId::new("linear_combination", k);
+        linear_combination_group.bench_function(id, |bencher| {
+            bencher.iter(|| prime_field::linear_combination(&coeffs, &vectors, &mut dst));
+        });
+    }
+}
+
+fn polynomial_benchmark(c: &mut Criterion) {
+    use std::hint::black_box;
+
+    let n_samples = 100;
+
+    let mut polynomial_group = c.benchmark_group("polynomial");
+    polynomial_group.sample_size(n_samples);
+
+    let x = prime_field::random_elements_exact(1)[0];
+    for degree in [16, 256, 4_096] {
+        let coeffs = prime_field::random_elements(degree);
+
+        let id = BenchmarkId::new("horner", degree);
+        polynomial_group.bench_function(id, |bencher| {
+            bencher.iter(|| black_box(prime_field::horner_eval(&coeffs, x)));
+        });
+
+        let id = BenchmarkId::new("even_odd", degree);
+        polynomial_group.bench_function(id, |bencher| {
+            bencher.iter(|| black_box(prime_field::even_odd_eval(&coeffs, x)));
+        });
+    }
+}
+
+fn powers_benchmark(c: &mut Criterion) {
+    use std::hint::black_box;
+
+    let n_samples = 100;
+
+    let mut powers_group = c.benchmark_group("powers");
+    powers_group.sample_size(n_samples);
+
+    let x = prime_field::random_elements_exact(1)[0];
+    for n in [16, 256, 4_096] {
+        let id = BenchmarkId::new("mul", n);
+        powers_group.bench_function(id, |bencher| {
+            bencher.iter(|| {
+                let mut powers = Vec::with_capacity(n);
+                let mut power = 1;
+                for _ in 0..n {
+                    powers.push(power);
+                    power = Reduce159::mul(power, x);
+                }
+                black_box(powers)
+            });
+        });
+
+        let id = BenchmarkId::new("powers_collect", n);
+        powers_group.bench_function(id, |bencher| {
+            bencher.iter(|| black_box(prime_field::powers_collect(x, n)));
+        });
+    }
+}
+
+fn sum_benchmark(c: &mut Criterion) {
+    use std::hint::black_box;
+
+    let n_samples = 100;
+
+    let mut sum_group = c.benchmark_group("sum");
+    sum_group.sample_size(n_samples);
+
+    for len in [16, 256, 4_096] {
+        let values = prime_field::random_elements_exact(len);
+
+        let id = BenchmarkId::new("add_modulo", len);
+        sum_group.bench_function(id, |bencher| {
+            bencher.iter(|| {
+                let mut acc = 0;
+                for &x in values.iter() {
+                    acc = prime_field::add_modulo(acc, x);
+                }
+                black_box(acc)
+            });
+        });
+
+        let id = BenchmarkId::new("sum", len);
+        sum_group.bench_function(id, |bencher| {
+            bencher.iter(|| black_box(prime_field::sum(&values)));
+        });
+
+        let values = mersenne61::Modulo::random(len);
+
+        let id = BenchmarkId::new("mersenne61/add", len);
+        sum_group.bench_function(id, |bencher| {
+            bencher.iter(|| {
+                let mut acc = 0;
+                for &x in values.iter() {
+                    acc = mersenne61::add(acc, x);
+                }
+                black_box(acc)
+            });
+        });
+
+        let id = BenchmarkId::new("mersenne61/sum", len);
+        sum_group.bench_function(id, |bencher| {
+            bencher.iter(|| black_box(mersenne61::sum(&values)));
+        });
+    }
+}
+
+fn sum_of_products_benchmark(c: &mut Criterion) {
+    use std::hint::black_box;
+
+    let n_samples = 100;
+
+    let mut sum_of_products_group = c.benchmark_group("sum_of_products");
+    sum_of_products_group.sample_size(n_samples);
+
+    for len in [4, 16, 64, 256, 1_024] {
+        let a = prime_field::random_elements_exact(len);
+        let b = prime_field::random_elements_exact(len);
+
+        let id = BenchmarkId::new("naive", len);
+        sum_of_products_group.bench_function(id, |bencher| {
+            bencher.iter(|| {
+                let mut acc = 0;
+                for (&x, &y) in a.iter().zip(&b) {
+                    acc = Reduce159::add(acc, Reduce159::mul(x, y));
+                }
+                black_box(acc)
+            });
+        });
+
+        let id = BenchmarkId::new("wide", len);
+        sum_of_products_group.bench_function(id, |bencher| {
+            bencher.iter(|| black_box(prime_field::sum_of_products(&a, &b)));
+        });
+
+        #[cfg(all(feature = "asm", target_arch = "x86_64"))]
+        if prime_field::asm::is_available() {
+            let id = BenchmarkId::new("mulx_adx", len);
+            sum_of_products_group.bench_function(id, |bencher| {
+                bencher.iter(|| black_box(prime_field::asm::sum_of_products(&a, &b)));
+            });
+        }
+    }
+}
+
+fn dot_benchmark(c: &mut Criterion) {
+    use std::hint::black_box;
+
+    let n_samples = 100;
+
+    let mut dot_group = c.benchmark_group("dot");
+    dot_group.sample_size(n_samples);
+
+    for len in [16, 256, 4_096] {
+        let a = prime_field::random_elements_exact(len);
+        let b = prime_field::random_elements_exact(len);
+
+        let id = BenchmarkId::new("mul+add", len);
+        dot_group.bench_function(id, |bencher| {
+            bencher.iter(|| {
+                let mut acc = 0;
+                for (&x, &y) in a.iter().zip(&b) {
+                    acc = Reduce159::add(acc, Reduce159::mul(x, y));
+                }
+                black_box(acc)
+            });
+        });
+
+        let id = BenchmarkId::new("dot", len);
+        dot_group.bench_function(id, |bencher| {
+            bencher.iter(|| black_box(prime_field::dot(&a, &b)));
+        });
+    }
+}
+
+fn square_benchmark(c: &mut Criterion) {
+    let n_samples = 1_000;
+    let n_operations = 1_000;
+
+    let mut square_group = c.benchmark_group("square");
+    square_group.sample_size(n_samples);
+
+    bench_square::<Reduce159>(&mut square_group, n_operations);
+    bench_square::<Limbs32>(&mut square_group, n_operations);
+}
+
+fn exp_power_of_2_benchmark(c: &mut Criterion) {
+    use std::hint::black_box;
+
+    let n_samples = 100;
+    let n_operations = 1_000;
+
+    let mut exp_power_of_2_group = c.benchmark_group("exp_power_of_2");
+    exp_power_of_2_group.sample_size(n_samples);
+
+    let operands = prime_field::random_elements(n_operations);
+
+    for k in [8, 32] {
+        let id = BenchmarkId::new("square", k);
+        exp_power_of_2_group.bench_function(id, |bencher| {
+            bencher.iter(|| {
+                for &x in operands.iter() {
+                    let mut x = x;
+                    for _ in 0..k {
+                        x = prime_field::square(x);
+                    }
+                    black_box(x);
+                }
+            });
+        });
+
+        let id = BenchmarkId::new("exp_power_of_2", k);
+        exp_power_of_2_group.bench_function(id, |bencher| {
+            bencher.iter(|| {
+                for &x in operands.iter() {
+                    black_box(prime_field::exp_power_of_2(x, k));
+                }
+            });
+        });
+    }
+}
+
+fn mul_small_benchmark(c: &mut Criterion) {
+    use std::hint::black_box;
+
+    let n_samples = 1_000;
+    let n_operations = 1_000;
+
+    let mut mul_small_group = c.benchmark_group("mul_small");
+    mul_small_group.sample_size(n_samples);
+
+    let operands = prime_field::random_elements(n_operations)
+        .into_iter()
+        .map(|x| (x, rand::random::<u32>()))
+        .collect::<Vec<_>>();
+
+    let id = BenchmarkId::new("mul", n_operations);
+    mul_small_group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for &(x, y) in operands.iter() {
+                black_box(Reduce159::mul(black_box(x), black_box(y as u64)));
+            }
+        });
+    });
+
+    let id = BenchmarkId::new("mul_small", n_operations);
+    mul_small_group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for &(x, y) in operands.iter() {
+                black_box(prime_field::mul_small(black_box(x), black_box(y)));
+            }
+        });
+    });
+}
+
+fn pow_benchmark(c: &mut Criterion) {
+    use std::hint::black_box;
+
+    let n_samples = 100;
+    let n_operations = 1_000;
+
+    let mut pow_group = c.benchmark_group("pow");
+    pow_group.sample_size(n_samples);
+
+    let operands = prime_field::random_elements(n_operations)
+        .into_iter()
+        .map(|x| (x, rand::random::<u64>()))
+        .collect::<Vec<_>>();
+
+    let id = BenchmarkId::new("left_to_right", n_operations);
+    pow_group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for &(x, exp) in operands.iter() {
+                black_box(prime_field::pow(x, exp));
+            }
+        });
+    });
+
+    let id = BenchmarkId::new("right_to_left", n_operations);
+    pow_group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for &(x, exp) in operands.iter() {
+                black_box(prime_field::pow_right_to_left(x, exp));
+            }
+        });
+    });
+}
+
+fn pow_u32_benchmark(c: &mut Criterion) {
+    use std::hint::black_box;
+
+    let n_samples = 100;
+    let n_operations = 1_000;
+
+    let mut pow_u32_group = c.benchmark_group("pow_u32");
+    pow_u32_group.sample_size(n_samples);
+
+    let operands = prime_field::random_elements(n_operations);
+
+    let id = BenchmarkId::new("pow_exp_7", n_operations);
+    pow_u32_group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for &x in operands.iter() {
+                black_box(prime_field::pow(x, black_box(7)));
+            }
+        });
+    });
+
+    let id = BenchmarkId::new("pow_u32_exp_7", n_operations);
+    pow_u32_group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for &x in operands.iter() {
+                black_box(prime_field::pow_u32(x, black_box(7)));
+            }
+        });
+    });
+
+    let id = BenchmarkId::new("pow7", n_operations);
+    pow_u32_group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for &x in operands.iter() {
+                black_box(prime_field::pow7(black_box(x)));
+            }
+        });
+    });
+
+    let exponents = operands
+        .iter()
+        .map(|_| rand::random::<u32>())
+        .collect::<Vec<_>>();
+
+    let id = BenchmarkId::new("pow_exp_random", n_operations);
+    pow_u32_group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for (&x, &exp) in operands.iter().zip(exponents.iter()) {
+                black_box(prime_field::pow(x, exp as u64));
+            }
+        });
+    });
+
+    let id = BenchmarkId::new("pow_u32_exp_random", n_operations);
+    pow_u32_group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for (&x, &exp) in operands.iter().zip(exponents.iter()) {
+                black_box(prime_field::pow_u32(x, exp));
+            }
+        });
+    });
+}
+
+fn sbox_benchmark(c: &mut Criterion) {
+    use std::hint::black_box;
+
+    let n_samples = 100;
+    let n_operations = 1_000;
+
+    let mut sbox_group = c.benchmark_group("sbox");
+    sbox_group.sample_size(n_samples);
+
+    let operands = prime_field::random_elements(n_operations);
+
+    let id = BenchmarkId::new("cube", n_operations);
+    sbox_group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for &x in operands.iter() {
+                black_box(prime_field::cube(black_box(x)));
+            }
+        });
+    });
+
+    let id = BenchmarkId::new("pow_u32_3", n_operations);
+    sbox_group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for &x in operands.iter() {
+                black_box(prime_field::pow_u32(black_box(x), 3));
+            }
+        });
+    });
+
+    let id = BenchmarkId::new("pow7", n_operations);
+    sbox_group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for &x in operands.iter() {
+                black_box(prime_field::pow7(black_box(x)));
+            }
+        });
+    });
+
+    let id = BenchmarkId::new("pow7_reduce_each_step", n_operations);
+    sbox_group.bench_function(id, |bencher| {
+        use prime_field::mul_reduce159 as mul;
+
+        bencher.iter(|| {
+            for &x in operands.iter() {
+                let x = black_box(x);
+                let x3 = mul(mul(x, x), x);
+                black_box(mul(mul(x3, x3), x));
+            }
+        });
+    });
+}
+
+fn pow_window_benchmark(c: &mut Criterion) {
+    use std::hint::black_box;
+
+    let n_samples = 100;
+    let n_operations = 100;
+
+    let mut pow_window_group = c.benchmark_group("pow_window");
+    pow_window_group.sample_size(n_samples);
+
+    // the table costs 2^W - 1 multiplications, so short exponents favour plain pow
+    for bits in [8, 16, 32, 64] {
+        let operands = prime_field::random_elements(n_operations)
+            .into_iter()
+            .map(|x| (x, rand::random::<u64>() >> (64 - bits)))
+            .collect::<Vec<_>>();
+
+        let id = BenchmarkId::new("pow", bits);
+        pow_window_group.bench_function(id, |bencher| {
+            bencher.iter(|| {
+                for &(x, exp) in operands.iter() {
+                    black_box(prime_field::pow(x, exp));
+                }
+            });
+        });
+
+        let id = BenchmarkId::new("pow_window_4", bits);
+        pow_window_group.bench_function(id, |bencher| {
+            bencher.iter(|| {
+                for &(x, exp) in operands.iter() {
+                    black_box(prime_field::pow_window::<4>(x, exp));
+                }
+            });
+        });
+    }
+
+    fn bench_window<const W: u32>(group: &mut BenchmarkGroup<WallTime>, operands: &[(u64, u64)]) {
+        let id = BenchmarkId::new("window", W);
+        group.bench_function(id, |bencher| {
+            bencher.iter(|| {
+                for &(x, exp) in operands.iter() {
+                    black_box(prime_field::pow_window::<W>(x, exp));
+                }
+            });
+        });
+    }
+
+    let operands = prime_field::random_elements(n_operations)
+        .into_iter()
+        .map(|x| (x, rand::random::<u64>()))
+        .collect::<Vec<_>>();
+    bench_window::<1>(&mut pow_window_group, &operands);
+    bench_window::<2>(&mut pow_window_group, &operands);
+    bench_window::<3>(&mut pow_window_group, &operands);
+    bench_window::<4>(&mut pow_window_group, &operands);
+    bench_window::<5>(&mut pow_window_group, &operands);
+    bench_window::<6>(&mut pow_window_group, &operands);
+}
+
+fn inverse_benchmark(c: &mut Criterion) {
+    use std::hint::black_box;
+
+    let n_samples = 100;
+    let n_operations = 1_000;
+
+    let mut inverse_group = c.benchmark_group("inverse");
+    inverse_group.sample_size(n_samples);
+
+    let operands = prime_field::random_nonzero_elements(n_operations);
+
+    let id = BenchmarkId::new("pow", n_operations);
+    inverse_group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for &x in operands.iter() {
+                black_box(prime_field::pow(x, P64 - 2));
+            }
+        });
+    });
+
+    let id = BenchmarkId::new("pow_p_minus_2", n_operations);
+    inverse_group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for &x in operands.iter() {
+                black_box(prime_field::pow_p_minus_2(x));
+            }
+        });
+    });
+
+    let id = BenchmarkId::new("xgcd", n_operations);
+    inverse_group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for &x in operands.iter() {
+                black_box(prime_field::inverse_xgcd(x));
+            }
+        });
+    });
+
+    let id = BenchmarkId::new("safegcd", n_operations);
+    inverse_group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for &x in operands.iter() {
+                black_box(prime_field::inverse_safegcd(x));
+            }
+        });
+    });
+}
+
+fn div_benchmark(c: &mut Criterion) {
+    use std::hint::black_box;
+
+    let n_samples = 100;
+    let n_operations = 1_000;
+
+    let mut div_group = c.benchmark_group("div");
+    div_group.sample_size(n_samples);
+
+    let operands = prime_field::random_nonzero_elements(n_operations + 1);
+
+    let id = BenchmarkId::new("mul", n_operations);
+    div_group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for (&x, &y) in operands.iter().tuple_windows() {
+                black_box(Reduce159::mul(x, y));
+            }
+        });
+    });
+
+    let id = BenchmarkId::new("div", n_operations);
+    div_group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for (&x, &y) in operands.iter().tuple_windows() {
+                black_box(prime_field::div(x, y));
+            }
+        });
+    });
+}
+
+fn mul_by_const_benchmark(c: &mut Criterion) {
+    use std::hint::black_box;
+
+    let n_samples = 1_000;
+    let n_operations = 1_000;
+
+    let mut mul_by_const_group = c.benchmark_group("mul_by_const");
+    mul_by_const_group.sample_size(n_samples);
+
+    let operands = prime_field::random_elements(n_operations);
+
+    fn bench_const(
+        group: &mut BenchmarkGroup<WallTime>,
+        name: &str,
+        operands: &[u64],
+        f: impl Fn(u64) -> u64,
+    ) {
+        let id = BenchmarkId::new(name, operands.len());
+        group.bench_function(id, |bencher| {
+            bencher.iter(|| {
+                for &x in operands.iter() {
+                    black_box(f(black_box(x)));
+                }
+            });
+        });
+    }
+
+    bench_const(&mut mul_by_const_group, "mul/2", &operands, |x| {
+        Reduce159::mul(x, 2)
+    });
+    bench_const(
+        &mut mul_by_const_group,
+        "double",
+        &operands,
+        prime_field::double,
+    );
+    bench_const(&mut mul_by_const_group, "mul/3", &operands, |x| {
+        Reduce159::mul(x, 3)
+    });
+    bench_const(
+        &mut mul_by_const_group,
+        "triple",
+        &operands,
+        prime_field::triple,
+    );
+    bench_const(&mut mul_by_const_group, "mul/7", &operands, |x| {
+        Reduce159::mul(x, 7)
+    });
+    bench_const(&mut mul_by_const_group, "mul_small/7", &operands, |x| {
+        prime_field::mul_small(x, 7)
+    });
+    bench_const(
+        &mut mul_by_const_group,
+        "mul_by_const/7",
+        &operands,
+        prime_field::mul_by_const::<7>,
+    );
+    bench_const(
+        &mut mul_by_const_group,
+        "mul_by_nonresidue",
+        &operands,
+        prime_field::mul_by_nonresidue,
+    );
+
+    let inverse_of_two = prime_field::inverse(2).unwrap();
+    bench_const(&mut mul_by_const_group, "mul/inverse(2)", &operands, |x| {
+        Reduce159::mul(x, inverse_of_two)
+    });
+    bench_const(
+        &mut mul_by_const_group,
+        "halve",
+        &operands,
+        prime_field::halve,
+    );
+}
+
+fn mul_pow2_benchmark(c: &mut Criterion) {
+    use std::hint::black_box;
+
+    let n_samples = 1_000;
+    let n_operations = 1_000;
+
+    let mut mul_pow2_group = c.benchmark_group("mul_pow2");
+    mul_pow2_group.sample_size(n_samples);
+
+    // both sides need 2^k as a u64 operand, so k stays below 64
+    let operands = prime_field::random_elements(n_operations)
+        .into_iter()
+        .map(|x| (x, rand::random::<u32>() % 64))
+        .collect::<Vec<_>>();
+
+    let id = BenchmarkId::new("mul", n_operations);
+    mul_pow2_group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for &(x, k) in operands.iter() {
+                black_box(Reduce159::mul(x, 1 << k));
+            }
+        });
+    });
+
+    let id = BenchmarkId::new("mul_pow2", n_operations);
+    mul_pow2_group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for &(x, k) in operands.iter() {
+                black_box(prime_field::mul_pow2(x, k));
+            }
+        });
+    });
+}
+
+fn sqrt_benchmark(c: &mut Criterion) {
+    use std::hint::black_box;
+
+    let n_samples = 100;
+    let n_operations = 1_000;
+
+    let mut sqrt_group = c.benchmark_group("sqrt");
+    sqrt_group.sample_size(n_samples);
+
+    let squares = prime_field::random_elements(n_operations)
+        .into_iter()
+        .map(|x| Reduce159::mul(x, x))
+        .collect::<Vec<_>>();
+    let randoms = prime_field::random_elements(n_operations);
+
+    let id = BenchmarkId::new("squares", n_operations);
+    sqrt_group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for &x in squares.iter() {
+                black_box(prime_field::sqrt(x));
+            }
+        });
+    });
+
+    // half of these are non-squares, which return after the first order search
+    let id = BenchmarkId::new("random", n_operations);
+    sqrt_group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for &x in randoms.iter() {
+                black_box(prime_field::sqrt(x));
+            }
+        });
+    });
+
+    let id = BenchmarkId::new("table/squares", n_operations);
+    sqrt_group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for &x in squares.iter() {
+                black_box(prime_field::sqrt_table(x));
+            }
+        });
+    });
+
+    let id = BenchmarkId::new("table/random", n_operations);
+    sqrt_group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for &x in randoms.iter() {
+                black_box(prime_field::sqrt_table(x));
+            }
+        });
+    });
+}
+
+fn element_benchmark(c: &mut Criterion) {
+    let n_samples = 1_000;
+    let n_operations = 1_000;
+
+    let mut element_group = c.benchmark_group("element");
+    element_group.sample_size(n_samples);
+
+    bench_element::<Modulo>(&mut element_group, n_operations);
+    bench_element::<Reduce159>(&mut element_group, n_operations);
+    bench_element::<Montgomery>(&mut element_group, n_operations);
+    bench_element::<Limbs32>(&mut element_group, n_operations);
+}
+
+fn fp2_operands<R: Reducer>(n: usize) -> Vec<Fp2<R>> {
+    R::random(2 * n)
+        .into_iter()
+        .tuples()
+        .map(|(c0, c1)| Fp2::new(FieldElement::new(c0), FieldElement::new(c1)))
+        .collect()
+}
+
+fn fp3_operands<R: Reducer>(n: usize) -> Vec<Fp3<R>> {
+    R::random(3 * n)
+        .into_iter()
+        .tuples()
+        .map(|(c0, c1, c2)| {
+            Fp3::new(
+                FieldElement::new(c0),
+                FieldElement::new(c1),
+                FieldElement::new(c2),
+            )
+        })
+        .collect()
+}
+
+fn binomial_operands<const D: usize, const W: u32>(
+    n: usize,
+) -> Vec<babybear::BinomialExtension<D, W>> {
+    babybear::Modulo::random(D * n)
+        .chunks(D)
+        .map(|c| babybear::BinomialExtension::new(core::array::from_fn(|i| c[i] as u32)))
+        .collect()
+}
+
+fn bench_fp2<R: Reducer>(group: &mut BenchmarkGroup<WallTime>, n_operations: usize) {
+    use std::hint::black_box;
+
+    let operands = fp2_operands::<R>(n_operations + 1);
+
+    let id = BenchmarkId::new(format!("fp2/{}/mul", R::NAME), n_operations);
+    group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for (&x, &y) in operands.iter().tuple_windows() {
+                black_box(black_box(x) * y);
+            }
+        });
+    });
+
+    let id = BenchmarkId::new(format!("fp2/{}/square", R::NAME), n_operations);
+    group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for &x in operands.iter() {
+                black_box(black_box(x).square());
+            }
+        });
+    });
+
+    let id = BenchmarkId::new(format!("fp2/{}/inverse", R::NAME), n_operations);
+    group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for &x in operands.iter() {
+                black_box(black_box(x).inverse());
+            }
+        });
+    });
+}
+
+fn bench_fp3<R: Reducer>(group: &mut BenchmarkGroup<WallTime>, n_operations: usize) {
+    use std::hint::black_box;
+
+    let operands = fp3_operands::<R>(n_operations + 1);
+
+    let id = BenchmarkId::new(format!("fp3/{}/mul", R::NAME), n_operations);
+    group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for (&x, &y) in operands.iter().tuple_windows() {
+                black_box(black_box(x) * y);
+            }
+        });
+    });
+
+    let id = BenchmarkId::new(format!("fp3/{}/inverse", R::NAME), n_operations);
+    group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for &x in operands.iter() {
+                black_box(black_box(x).inverse());
+            }
+        });
+    });
+}
+
+fn bench_binomial<const D: usize, const W: u32>(
+    group: &mut BenchmarkGroup<WallTime>,
+    label: &str,
+    n_operations: usize,
+) {
+    use std::hint::black_box;
+
+    let operands = binomial_operands::<D, W>(n_operations + 1);
+
+    let id = BenchmarkId::new(format!("{label}/mul"), n_operations);
+    group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for (&x, &y) in operands.iter().tuple_windows() {
+                black_box(black_box(x) * y);
+            }
+        });
+    });
+}
+
+fn extension_benchmark(c: &mut Criterion) {
+    let n_samples = 100;
+    let n_operations = 1_000;
+
+    let mut extension_group = c.benchmark_group("extension");
+    extension_group.sample_size(n_samples);
+
+    bench_fp2::<Reduce159>(&mut extension_group, n_operations);
+    bench_fp2::<Montgomery>(&mut extension_group, n_operations);
+    bench_fp3::<Reduce159>(&mut extension_group, n_operations);
+    bench_fp3::<Montgomery>(&mut extension_group, n_operations);
+    bench_binomial::<4, 11>(&mut extension_group, "babybear_fp4", n_operations);
+    bench_binomial::<5, 2>(&mut extension_group, "babybear_fp5", n_operations);
+}
+
+type MulVariant<T> = (&'static str, fn(T, T) -> T);
+
+fn bench_mul_variants<T: Copy>(
+    group: &mut BenchmarkGroup<WallTime>,
+    label: &str,
+    operands: &[T],
+    variants: &[MulVariant<T>],
+) {
+    use std::hint::black_box;
+
+    for &(name, mul) in variants {
+        let id = BenchmarkId::new(format!("{label}/{name}"), operands.len() - 1);
+        group.bench_function(id, |bencher| {
+            bencher.iter(|| {
+                for (&x, &y) in operands.iter().tuple_windows() {
+                    black_box(mul(black_box(x), y));
+                }
+            });
+        });
+    }
+}
+
+fn extension_mul_benchmark(c: &mut Criterion) {
+    let n_samples = 100;
+    let n_operations = 1_000;
+
+    let mut extension_mul_group = c.benchmark_group("extension_mul");
+    extension_mul_group.sample_size(n_samples);
+
+    fn fp2<R: Reducer>(group: &mut BenchmarkGroup<WallTime>, n_operations: usize) {
+        bench_mul_variants(
+            group,
+            &format!("fp2/{}", R::NAME),
+            &fp2_operands::<R>(n_operations + 1),
+            &[
+                ("schoolbook", Fp2::mul_schoolbook),
+                ("karatsuba", Fp2::mul_karatsuba),
+            ],
+        );
+    }
+
+    fn fp3<R: Reducer>(group: &mut BenchmarkGroup<WallTime>, n_operations: usize) {
+        bench_mul_variants(
+            group,
+            &format!("fp3/{}", R::NAME),
+            &fp3_operands::<R>(n_operations + 1),
+            &[
+                ("schoolbook", Fp3::mul_schoolbook),
+                ("karatsuba", Fp3::mul_karatsuba),
+                ("toom3", Fp3::mul_toom3),
+            ],
+        );
+    }
+
+    fn binomial<const D: usize, const W: u32>(
+        group: &mut BenchmarkGroup<WallTime>,
+        label: &str,
+        n_operations: usize,
+    ) {
+        bench_mul_variants(
+            group,
+            label,
+            &binomial_operands::<D, W>(n_operations + 1),
+            &[
+                ("schoolbook", babybear::BinomialExtension::mul_schoolbook),
+                ("schoolbook_delayed", |x, y| x * y),
+                ("karatsuba", babybear::BinomialExtension::mul_karatsuba),
+            ],
+        );
+    }
+
+    fp2::<Reduce159>(&mut extension_mul_group, n_operations);
+    fp2::<Montgomery>(&mut extension_mul_group, n_operations);
+    fp3::<Reduce159>(&mut extension_mul_group, n_operations);
+    fp3::<Montgomery>(&mut extension_mul_group, n_operations);
+    binomial::<4, 11>(&mut extension_mul_group, "babybear_fp4", n_operations);
+    binomial::<5, 2>(&mut extension_mul_group, "babybear_fp5", n_operations);
+}
+
+fn branching_benchmark(c: &mut Criterion) {
+    use std::hint::black_box;
+
+    let n_samples = 1_000;
+    let n_operations = 1_000;
+
+    let mut branching_group = c.benchmark_group("branching");
+    branching_group.sample_size(n_samples);
+
+    fn bench_binary(
+        group: &mut BenchmarkGroup<WallTime>,
+        name: &str,
+        operands: &[(u64, u64)],
+        f: impl Fn(u64, u64) -> u64,
+    ) {
+        let id = BenchmarkId::new(name, operands.len());
+        group.bench_function(id, |bencher| {
+            bencher.iter(|| {
+                for &(x, y) in operands.iter() {
+                    black_box(f(black_box(x), black_box(y)));
+                }
+            });
+        });
+    }
+
+    // uniform canonical sums reach p half of the time, at random
+    let adversarial_add = prime_field::random_elements(n_operations)
+        .into_iter()
+        .tuple_windows()
+        .collect::<Vec<_>>();
+    // sums of values below p / 2 never do
+    let predictable_add = adversarial_add
+        .iter()
+        .map(|&(x, y)| (x >> 2, y >> 2))
+        .collect::<Vec<_>>();
+
+    // with a factor of one, reduce159 returns y itself, which is
+    // non-canonical half of the time
+    let adversarial_mul = (0..n_operations)
+        .map(|_| {
+            let y = rand::random::<u64>() % prime_field::EPSILON;
+            (1, if rand::random::<bool>() { y + P64 } else { y })
+        })
+        .collect::<Vec<_>>();
+    let predictable_mul = adversarial_add.clone();
+
+    let cases = [
+        ("adversarial", &adversarial_add, &adversarial_mul),
+        ("predictable", &predictable_add, &predictable_mul),
+    ];
+    for (distribution, add_operands, mul_operands) in cases {
+        let group = &mut branching_group;
+        let add_branchy = format!("add/branchy/{distribution}");
+        bench_binary(group, &add_branchy, add_operands, prime_field::add_branchy);
+        let add_branchless = format!("add/branchless/{distribution}");
+        bench_binary(
+            group,
+            &add_branchless,
+            add_operands,
+            prime_field::add_branchless,
+        );
+        let mul_branchy = format!("mul/branchy/{distribution}");
+        bench_binary(group, &mul_branchy, mul_operands, prime_field::mul_branchy);
+        let mul_branchless = format!("mul/branchless/{distribution}");
+        bench_binary(
+            group,
+            &mul_branchless,
+            mul_operands,
+            prime_field::mul_branchless,
+        );
+    }
+}
+
+fn montgomery_benchmark(c: &mut Criterion) {
+    use std::hint::black_box;
+
+    let n_samples = 1_000;
+    let n_operations = 1_000;
+
+    let mut montgomery_group = c.benchmark_group("montgomery");
+    montgomery_group.sample_size(n_samples);
+
+    let canonical = prime_field::random_elements(n_operations)
+        .into_iter()
+        .map(Canonical::new)
+        .collect::<Vec<_>>();
+    let montgomery = canonical
+        .iter()
+        .map(|x| x.to_montgomery())
+        .collect::<Vec<_>>();
+
+    let id = BenchmarkId::new("to_montgomery", n_operations);
+    montgomery_group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for &x in canonical.iter() {
+                black_box(black_box(x).to_montgomery());
+            }
+        });
+    });
+
+    let id = BenchmarkId::new("to_canonical", n_operations);
+    montgomery_group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for &x in montgomery.iter() {
+                black_box(black_box(x).to_canonical());
+            }
+        });
+    });
+}
+
+fn montgomery_pipeline_benchmark(c: &mut Criterion) {
+    use std::hint::black_box;
+
+    let n_samples = 100;
+    let n_operations = 1_000;
+
+    let mut pipeline_group = c.benchmark_group("montgomery_pipeline");
+    pipeline_group.sample_size(n_samples);
+
+    let operands = prime_field::random_elements(n_operations);
+    let y = operands[0];
+    let y_montgomery = prime_field::to_montgomery(y);
+
+    for chain_length in [1, 4, 16, 64] {
+        let id = BenchmarkId::new(format!("canonical/{chain_length}"), n_operations);
+        pipeline_group.bench_function(id, |bencher| {
+            bencher.iter(|| {
+                for &x in operands.iter() {
+                    let mut acc = black_box(x);
+                    for _ in 0..chain_length {
+                        acc = Reduce159::mul(acc, y);
+                    }
+                    black_box(acc);
+                }
+            });
+        });
+
+        // convert once, multiply chain_length times in Montgomery form, convert back
+        let id = BenchmarkId::new(format!("montgomery/{chain_length}"), n_operations);
+        pipeline_group.bench_function(id, |bencher| {
+            bencher.iter(|| {
+                for &x in operands.iter() {
+                    let mut acc = prime_field::to_montgomery(black_box(x));
+                    for _ in 0..chain_length {
+                        acc = prime_field::mul_reduce_montgomery(acc, y_montgomery);
+                    }
+                    black_box(prime_field::from_montgomery(acc));
+                }
+            });
+        });
+    }
+}
+
+#[cfg(feature = "subtle")]
+fn constant_time_benchmark(c: &mut Criterion) {
+    use std::hint::black_box;
+
+    use subtle::{Choice, ConditionallySelectable};
+
+    let n_samples = 1_000;
+    let n_operations = 1_000;
+
+    let mut ct_group = c.benchmark_group("constant_time");
+    ct_group.sample_size(n_samples);
+
+    // half of the raw values are non-canonical, so the variable-time branch is unpredictable
+    let raw = prime_field::random_elements_exact(n_operations)
+        .into_iter()
+        .map(|x| {
+            if rand::random::<bool>() {
+                x
+            } else {
+                P64 + x % prime_field::EPSILON
+            }
+        })
+        .collect::<Vec<_>>();
+    // both selects pick between two different elements on the same coin flips
+    let operands = prime_field::random_elements_exact(n_operations + 1)
+        .into_iter()
+        .map(FieldElement::<Reduce159>::new)
+        .tuple_windows()
+        .map(|(x, y)| (x, y, rand::random::<bool>()))
+        .collect::<Vec<_>>();
+
+    let id = BenchmarkId::new("reduce_vt", n_operations);
+    ct_group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for &x in raw.iter() {
+                black_box(prime_field::to_canonical(x));
+            }
+        });
+    });
+
+    let id = BenchmarkId::new("reduce_ct", n_operations);
+    ct_group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for &x in raw.iter() {
+                black_box(prime_field::conditional_reduce(x));
+            }
+        });
+    });
+
+    let id = BenchmarkId::new("select_vt", n_operations);
+    ct_group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for &(x, y, coin) in operands.iter() {
+                black_box(if coin { y } else { x });
+            }
+        });
+    });
+
+    let id = BenchmarkId::new("select_ct", n_operations);
+    ct_group.bench_function(id, |bencher| {
+        bencher.iter(|| {
+            for &(x, y, coin) in operands.iter() {
+                black_box(FieldElement::conditional_select(
+                    &x,
+                    &y,
+                    Choice::from(coin as u8),
+                ));
+            }
+        });
+    });
+}
+
+#[cfg(not(feature = "subtle"))]
+fn constant_time_benchmark(_: &mut Criterion) {}
+
+criterion_group!(
+    add_mul,
+    add_benchmark,
+    sub_benchmark,
+    neg_benchmark,
+    mul_benchmark,
+    reduce_benchmark,
+    unstructured_benchmark,
+    multi_limb_benchmark,
+    binary_benchmark,
+    mersenne31_benchmark,
+    mul_add_benchmark,
+    slices_benchmark,
+    packed_benchmark,
+    scale_benchmark,
+    linear_combination_benchmark,
+    polynomial_benchmark,
+    powers_benchmark,
+    sum_benchmark,
+    sum_of_products_benchmark,
+    dot_benchmark,
+    square_benchmark,
+    exp_power_of_2_benchmark,
+    mul_small_benchmark,
+    pow_benchmark,
+    pow_u32_benchmark,
+    sbox_benchmark,
+    pow_window_benchmark,
+    inverse_benchmark,
+    div_benchmark,
+    mul_by_const_benchmark,
+    mul_pow2_benchmark,
+    sqrt_benchmark,
+    element_benchmark,
+    extension_benchmark,
+    extension_mul_benchmark,
+    branching_benchmark,
+    montgomery_benchmark,
+    montgomery_pipeline_benchmark,
+    constant_time_benchmark
+);
 criterion_main!(add_mul);
@@ -0,0 +1,14 @@
+//! The candidate moduli declared with `define_prime_field!`, shared by the
+//! benchmarks
+//!
+//! Every group that loops over `candidate_fields!` picks up a field added here.
+
+prime_field::define_prime_field! {
+    candidate_fields;
+    // 2^64 - 59, the largest 64-bit prime
+    P64m59 = (0xffff_ffff_ffff_ffc5, 2),
+}
+
+// lets the benches import the registry along with the fields
+#[allow(clippy::single_component_path_imports)]
+pub(crate) use candidate_fields;
@@ -0,0 +1,526 @@
+//! Runs one normalized workload against every field and prints a single table
+//!
+//! Each field performs `N` additions, `N` multiplications and `N` Fermat
+//! inversions over the same number of random operands, followed by one radix-2
+//! NTT of 2^`LOG_NTT` points where the two-adicity allows it. Each row is
+//! labelled from the field's [`FieldMeta`], and every timing is the fastest
+//! of `REPEATS` runs. Run it with `cargo bench --bench compare`.
+//!
+//! The `[u64; N]` fields run on [`MultiLimbModulus`] with CIOS Montgomery
+//! multiplication, and again with their specialized `mul` where there is one.
+//! Extension fields invert with the exponent p^d - 2 and skip the NTT, whose
+//! roots of unity live in the base field. The binary fields GF(2^64) and
+//! GF(2^128) are left out, since [`FieldMeta`] describes prime fields only;
+//! the `binary` group of the `add_mul` benchmark compares them instead.
+
+use std::hint::black_box;
+use std::marker::PhantomData;
+use std::time::{Duration, Instant};
+
+use itertools::Itertools;
+use num_bigint::BigUint;
+use prime_field::{
+    babybear, bls12_381_fr, bn254, curve25519, koalabear, mersenne31, mersenne61, p128, secp256k1,
+    solinas_c, stark252, Barrett, Crandall, FieldElement, FieldMeta, Fp2, Fp3, GenericPrimeField,
+    Limbs32, Meta, Modulo, Montgomery, MultiLimbModulus, Plantard, PrimeField64, PseudoMersenne,
+    Reduce159, P64,
+};
+
+mod candidates;
+
+use candidates::*;
+
+const N: usize = 1_000;
+const LOG_NTT: u32 = 12;
+const REPEATS: usize = 25;
+
+/// The Goldilocks modulus as a pseudo-Mersenne prime 2^64 - c
+const GOLDILOCKS_C: u64 = solinas_c(&[(1, 32), (-1, 0)]);
+
+/// The Curve25519 modulus with the generic Montgomery operations
+const CURVE25519: MultiLimbModulus<4> = MultiLimbModulus::new(curve25519::MODULUS);
+
+/// The elements and arithmetic of one strategy, as measured by one row
+trait Workload {
+    type Element: Copy + PartialEq;
+
+    const META: FieldMeta;
+    const STRATEGY: &'static str;
+
+    /// Samples `n` uniformly random elements in this strategy's representation
+    fn random(n: usize) -> Vec<Self::Element>;
+
+    fn add(x: Self::Element, y: Self::Element) -> Self::Element;
+
+    fn sub(x: Self::Element, y: Self::Element) -> Self::Element;
+
+    fn mul(x: Self::Element, y: Self::Element) -> Self::Element;
+
+    fn square(x: Self::Element) -> Self::Element {
+        Self::mul(x, x)
+    }
+}
+
+/// Measures a [`PrimeField64`] strategy on `u64` residues
+struct Prime<F>(PhantomData<F>);
+
+impl<F: PrimeField64 + Meta> Workload for Prime<F> {
+    type Element = u64;
+
+    const META: FieldMeta = F::META;
+    const STRATEGY: &'static str = F::NAME;
+
+    fn random(n: usize) -> Vec<u64> {
+        F::random(n)
+    }
+
+    #[inline(always)]
+    fn add(x: u64, y: u64) -> u64 {
+        F::add(x, y)
+    }
+
+    #[inline(always)]
+    fn sub(x: u64, y: u64) -> u64 {
+        F::sub(x, y)
+    }
+
+    #[inline(always)]
+    fn mul(x: u64, y: u64) -> u64 {
+        F::mul(x, y)
+    }
+
+    #[inline(always)]
+    fn square(x: u64) -> u64 {
+        F::square(x)
+    }
+}
+
+/// Declares a [`Workload`] on the `[u64; $limbs]` residues of `$field`, adding
+/// and subtracting with `$field` and multiplying with `$mul`
+macro_rules! multilimb_workload {
+    ($workload:ident, $limbs:literal, $meta:expr, $field:expr, $strategy:literal, $mul:expr) => {
+        struct $workload;
+
+        impl Workload for $workload {
+            type Element = [u64; $limbs];
+
+            const META: FieldMeta = $meta;
+            const STRATEGY: &'static str = $strategy;
+
+            /// Any value below p is a residue in both canonical and Montgomery
+            /// form, and keeping the top limb below p's guarantees one
+            fn random(n: usize) -> Vec<Self::Element> {
+                let top = $field.modulus[$limbs - 1];
+                (0..n)
+                    .map(|_| {
+                        let mut x: [u64; $limbs] = rand::random();
+                        x[$limbs - 1] %= top;
+                        x
+                    })
+                    .collect()
+            }
+
+            #[inline(always)]
+            fn add(x: Self::Element, y: Self::Element) -> Self::Element {
+                $field.add(&x, &y)
+            }
+
+            #[inline(always)]
+            fn sub(x: Self::Element, y: Self::Element) -> Self::Element {
+                $field.sub(&x, &y)
+            }
+
+            #[inline(always)]
+            fn mul(x: Self::Element, y: Self::Element) -> Self::Element {
+                $mul(&x, &y)
+            }
+        }
+    };
+}
+
+multilimb_workload!(P128Cios, 2, p128::META, p128::FIELD, "p128/cios", |x, y| {
+    p128::FIELD.mul_cios(x, y)
+});
+multilimb_workload!(
+    P128Specialized,
+    2,
+    p128::META,
+    p128::FIELD,
+    "p128/specialized",
+    p128::mul
+);
+multilimb_workload!(
+    Stark252Cios,
+    4,
+    stark252::META,
+    stark252::FIELD,
+    "stark252/cios",
+    |x, y| stark252::FIELD.mul_cios(x, y)
+);
+multilimb_workload!(
+    Stark252Specialized,
+    4,
+    stark252::META,
+    stark252::FIELD,
+    "stark252/specialized",
+    stark252::mul
+);
+multilimb_workload!(
+    Bn254Cios,
+    4,
+    bn254::META,
+    bn254::FIELD,
+    "bn254/cios",
+    bn254::mul
+);
+multilimb_workload!(
+    Bls12381FrCios,
+    4,
+    bls12_381_fr::META,
+    bls12_381_fr::FIELD,
+    "bls12_381_fr/cios",
+    bls12_381_fr::mul
+);
+multilimb_workload!(
+    Secp256k1Cios,
+    4,
+    secp256k1::META,
+    secp256k1::FIELD,
+    "secp256k1/cios",
+    |x, y| secp256k1::FIELD.mul_cios(x, y)
+);
+multilimb_workload!(
+    Secp256k1PseudoMersenne,
+    4,
+    secp256k1::META,
+    secp256k1::FIELD,
+    "secp256k1/pseudo_mersenne",
+    secp256k1::mul
+);
+multilimb_workload!(
+    Curve25519Cios,
+    4,
+    curve25519::META,
+    CURVE25519,
+    "curve25519/cios",
+    |x, y| CURVE25519.mul_cios(x, y)
+);
+multilimb_workload!(
+    Curve25519Saturated,
+    4,
+    curve25519::META,
+    CURVE25519,
+    "curve25519/saturated",
+    curve25519::mul
+);
+
+impl Workload for Fp2 {
+    type Element = Self;
+
+    const META: FieldMeta = <Self as Meta>::META;
+    const STRATEGY: &'static str = "karatsuba";
+
+    fn random(n: usize) -> Vec<Self> {
+        prime_field::random_elements_exact(2 * n)
+            .into_iter()
+            .tuples()
+            .map(|(c0, c1)| Fp2::new(FieldElement::new(c0), FieldElement::new(c1)))
+            .collect()
+    }
+
+    #[inline(always)]
+    fn add(x: Self, y: Self) -> Self {
+        x + y
+    }
+
+    #[inline(always)]
+    fn sub(x: Self, y: Self) -> Self {
+        x - y
+    }
+
+    #[inline(always)]
+    fn mul(x: Self, y: Self) -> Self {
+        x * y
+    }
+
+    #[inline(always)]
+    fn square(x: Self) -> Self {
+        x.square()
+    }
+}
+
+impl Workload for Fp3 {
+    type Element = Self;
+
+    const META: FieldMeta = <Self as Meta>::META;
+    const STRATEGY: &'static str = "karatsuba";
+
+    fn random(n: usize) -> Vec<Self> {
+        prime_field::random_elements_exact(3 * n)
+            .into_iter()
+            .tuples()
+            .map(|(c0, c1, c2)| {
+                Fp3::new(
+                    FieldElement::new(c0),
+                    FieldElement::new(c1),
+                    FieldElement::new(c2),
+                )
+            })
+            .collect()
+    }
+
+    #[inline(always)]
+    fn add(x: Self, y: Self) -> Self {
+        x + y
+    }
+
+    #[inline(always)]
+    fn sub(x: Self, y: Self) -> Self {
+        x - y
+    }
+
+    #[inline(always)]
+    fn mul(x: Self, y: Self) -> Self {
+        x * y
+    }
+
+    #[inline(always)]
+    fn square(x: Self) -> Self {
+        x.square()
+    }
+}
+
+impl<const D: usize, const W: u32> Workload for babybear::BinomialExtension<D, W> {
+    type Element = Self;
+
+    const META: FieldMeta = <Self as Meta>::META;
+    const STRATEGY: &'static str = "montgomery";
+
+    fn random(n: usize) -> Vec<Self> {
+        babybear::Modulo::random(D * n)
+            .chunks_exact(D)
+            .map(|c| Self::new(std::array::from_fn(|i| c[i] as u32)))
+            .collect()
+    }
+
+    #[inline(always)]
+    fn add(x: Self, y: Self) -> Self {
+        x + y
+    }
+
+    #[inline(always)]
+    fn sub(x: Self, y: Self) -> Self {
+        x - y
+    }
+
+    #[inline(always)]
+    fn mul(x: Self, y: Self) -> Self {
+        x * y
+    }
+
+    #[inline(always)]
+    fn square(x: Self) -> Self {
+        x.square()
+    }
+}
+
+/// Nanoseconds per operation, and microseconds for the whole NTT
+struct Row {
+    meta: FieldMeta,
+    strategy: &'static str,
+    add: f64,
+    mul: f64,
+    inverse: f64,
+    ntt: Option<f64>,
+}
+
+fn fastest(mut run: impl FnMut()) -> Duration {
+    (0..REPEATS)
+        .map(|_| {
+            let start = Instant::now();
+            run();
+            start.elapsed()
+        })
+        .min()
+        .unwrap()
+}
+
+/// Returns the order of the multiplicative group, p^d - 1
+fn group_order(meta: &FieldMeta) -> BigUint {
+    let p = meta
+        .modulus
+        .iter()
+        .rev()
+        .fold(BigUint::ZERO, |acc, &limb| acc << 64 | BigUint::from(limb));
+    p.pow(meta.extension_degree as u32) - 1u32
+}
+
+/// Raises `x` to `exp` > 0, given as limbs least significant first, with
+/// `W::mul` alone, so that it works in any representation without knowing its
+/// encoding of one
+fn pow<W: Workload>(x: W::Element, exp: &[u64]) -> W::Element {
+    let bits = 64 * exp.len() as u32 - exp[exp.len() - 1].leading_zeros();
+    let mut acc = x;
+    for bit in (0..bits - 1).rev() {
+        acc = W::square(acc);
+        if exp[bit as usize / 64] >> (bit % 64) & 1 == 1 {
+            acc = W::mul(acc, x);
+        }
+    }
+    acc
+}
+
+/// Finds a primitive 2^`log_n`-th root of unity among random elements
+///
+/// h = g^((p - 1) / 2^log_n) has order dividing 2^log_n, and is primitive
+/// exactly when squaring it `log_n - 1` times does not yet reach one.
+fn root_of_unity<W: Workload>(log_n: u32) -> W::Element {
+    let exp = (group_order(&W::META) >> log_n).to_u64_digits();
+    loop {
+        let h = pow::<W>(W::random(1)[0], &exp);
+        let half = (1..log_n).fold(h, |x, _| W::square(x));
+        if half != W::square(half) {
+            return h;
+        }
+    }
+}
+
+/// Transforms `values` in place with iterative radix-2 Cooley-Tukey butterflies
+fn ntt<W: Workload>(values: &mut [W::Element], twiddles: &[W::Element]) {
+    let n = values.len();
+    let log_n = n.trailing_zeros();
+    for i in 0..n {
+        let j = i.reverse_bits() >> (usize::BITS - log_n);
+        if i < j {
+            values.swap(i, j);
+        }
+    }
+    let mut half = 1;
+    while half < n {
+        let stride = n / (2 * half);
+        for chunk in values.chunks_exact_mut(2 * half) {
+            let (lo, hi) = chunk.split_at_mut(half);
+            for (k, (x, y)) in lo.iter_mut().zip(hi).enumerate() {
+                let t = W::mul(*y, twiddles[k * stride]);
+                (*x, *y) = (W::add(*x, t), W::sub(*x, t));
+            }
+        }
+        half *= 2;
+    }
+}
+
+fn per_operation(elapsed: Duration) -> f64 {
+    elapsed.as_secs_f64() * 1e9 / N as f64
+}
+
+fn measure<W: Workload>() -> Row {
+    let operands = W::random(N + 1);
+    let inverse_exp = (group_order(&W::META) - 1u32).to_u64_digits();
+
+    let add = fastest(|| {
+        for (&x, &y) in operands.iter().tuple_windows() {
+            black_box(W::add(black_box(x), y));
+        }
+    });
+    let mul = fastest(|| {
+        for (&x, &y) in operands.iter().tuple_windows() {
+            black_box(W::mul(black_box(x), y));
+        }
+    });
+    let inverse = fastest(|| {
+        for &x in &operands[..N] {
+            black_box(pow::<W>(black_box(x), &inverse_exp));
+        }
+    });
+
+    let meta = W::META;
+    let ntt = (meta.extension_degree == 1 && meta.two_adicity >= LOG_NTT).then(|| {
+        let root = root_of_unity::<W>(LOG_NTT);
+        let one = (0..LOG_NTT).fold(root, |x, _| W::square(x));
+        let twiddles = std::iter::successors(Some(one), |&x| Some(W::mul(x, root)))
+            .take(1 << (LOG_NTT - 1))
+            .collect::<Vec<_>>();
+        let input = W::random(1 << LOG_NTT);
+        fastest(|| {
+            let mut values = input.clone();
+            ntt::<W>(black_box(&mut values), &twiddles);
+            black_box(values);
+        })
+        .as_secs_f64()
+            * 1e6
+    });
+
+    Row {
+        meta,
+        strategy: W::STRATEGY,
+        add: per_operation(add),
+        mul: per_operation(mul),
+        inverse: per_operation(inverse),
+        ntt,
+    }
+}
+
+/// Measures a [`PrimeField64`] strategy, in the shape the candidate registry takes
+fn measure_prime<F: PrimeField64 + Meta>() -> Row {
+    measure::<Prime<F>>()
+}
+
+fn main() {
+    let mut rows = vec![
+        measure_prime::<Modulo>(),
+        measure_prime::<Reduce159>(),
+        measure_prime::<Barrett>(),
+        measure_prime::<Crandall>(),
+        measure_prime::<Montgomery>(),
+        measure_prime::<Plantard>(),
+        measure_prime::<Limbs32>(),
+        measure_prime::<GenericPrimeField<P64>>(),
+        measure_prime::<PseudoMersenne<64, GOLDILOCKS_C>>(),
+    ];
+    rows.extend(candidate_fields!(measure_prime).map(|measure| measure()));
+    rows.extend([
+        measure_prime::<babybear::Modulo>(),
+        measure_prime::<babybear::Barrett>(),
+        measure_prime::<babybear::Montgomery>(),
+        measure_prime::<koalabear::Modulo>(),
+        measure_prime::<koalabear::Barrett>(),
+        measure_prime::<koalabear::Montgomery>(),
+        measure_prime::<koalabear::Fold>(),
+        measure_prime::<mersenne31::Modulo>(),
+        measure_prime::<mersenne31::Fold>(),
+        measure_prime::<mersenne61::Modulo>(),
+        measure_prime::<mersenne61::Fold>(),
+        measure::<P128Cios>(),
+        measure::<P128Specialized>(),
+        measure::<Stark252Cios>(),
+        measure::<Stark252Specialized>(),
+        measure::<Bn254Cios>(),
+        measure::<Bls12381FrCios>(),
+        measure::<Secp256k1Cios>(),
+        measure::<Secp256k1PseudoMersenne>(),
+        measure::<Curve25519Cios>(),
+        measure::<Curve25519Saturated>(),
+        measure::<Fp2>(),
+        measure::<Fp3>(),
+        measure::<babybear::Fp4>(),
+        measure::<babybear::Fp5>(),
+    ]);
+
+    println!(
+        "| field | bits | two-adicity | strategy | add (ns) | mul (ns) | inverse (ns) | ntt 2^{LOG_NTT} (µs) |"
+    );
+    println!("|---|---:|---:|---|---:|---:|---:|---:|");
+    for row in rows {
+        let ntt = row.ntt.map_or("n/a".to_string(), |ntt| format!("{ntt:.1}"));
+        println!(
+            "| {} | {} | {} | {} | {:.2} | {:.2} | {:.1} | {ntt} |",
+            row.meta,
+            row.meta.bits,
+            row.meta.two_adicity,
+            row.strategy,
+            row.add,
+            row.mul,
+            row.inverse
+        );
+    }
+}
@@ -0,0 +1,71 @@
+//! Compares the wgpu kernels end to end against the CPU slice kernels
+//!
+//! Every GPU iteration uploads the operands, dispatches one shader and reads
+//! the results back, so the throughput includes both transfers. Without an
+//! adapter only the CPU rows run. Run it with
+//! `cargo bench --bench gpu --features gpu`.
+
+use criterion::*;
+use prime_field::gpu::Gpu;
+
+fn gpu_benchmark(c: &mut Criterion) {
+    let gpu = Gpu::new()
+        .inspect(|gpu| eprintln!("GPU adapter: {:?}", gpu.adapter_info()))
+        .inspect_err(|err| eprintln!("skipping GPU rows: {err}"))
+        .ok();
+
+    let mut group = c.benchmark_group("gpu");
+    group.sample_size(20);
+
+    for log_len in [10, 14, 18, 20] {
+        let len = 1 << log_len;
+        let a = prime_field::random_elements_exact(len);
+        let b = prime_field::random_elements_exact(len);
+        let wide: Vec<u128> = a
+            .iter()
+            .zip(&b)
+            .map(|(&x, &y)| x as u128 * y as u128)
+            .collect();
+        let mut dst = vec![0; len];
+        group.throughput(Throughput::Elements(a.len() as u64));
+
+        let id = BenchmarkId::new("add/cpu", len);
+        group.bench_function(id, |bencher| {
+            bencher.iter(|| prime_field::add_slices(&mut dst, &a, &b));
+        });
+
+        let id = BenchmarkId::new("mul/cpu", len);
+        group.bench_function(id, |bencher| {
+            bencher.iter(|| prime_field::mul_slices(&mut dst, &a, &b));
+        });
+
+        let id = BenchmarkId::new("reduce/cpu", len);
+        group.bench_function(id, |bencher| {
+            bencher.iter(|| {
+                for (d, &x) in dst.iter_mut().zip(&wide) {
+                    *d = prime_field::reduce_u64(x);
+                }
+            });
+        });
+
+        if let Some(gpu) = &gpu {
+            let id = BenchmarkId::new("add/gpu", len);
+            group.bench_function(id, |bencher| {
+                bencher.iter(|| gpu.add_slices(&mut dst, &a, &b));
+            });
+
+            let id = BenchmarkId::new("mul/gpu", len);
+            group.bench_function(id, |bencher| {
+                bencher.iter(|| gpu.mul_slices(&mut dst, &a, &b));
+            });
+
+            let id = BenchmarkId::new("reduce/gpu", len);
+            group.bench_function(id, |bencher| {
+                bencher.iter(|| gpu.reduce_slice(&mut dst, &wide));
+            });
+        }
+    }
+}
+
+criterion_group!(gpu, gpu_benchmark);
+criterion_main!(gpu);
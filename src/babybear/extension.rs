@@ -0,0 +1,299 @@
+//! The binomial extensions F\[u\]/(u^D - W) of BabyBear of degrees 4 and 5
+//!
+//! Coefficients are kept in Montgomery form. Residues below 2^31 leave room
+//! to accumulate each output coefficient over 64 bits, so `mul` spends one
+//! `reduce_montgomery` per coefficient rather than one per product.
+
+use core::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use super::*;
+use crate::meta::Meta;
+
+/// p * 2^32, the bound below which `reduce_montgomery` returns a canonical value
+const MONTGOMERY_BOUND: u64 = (P as u64) << 32;
+
+/// The degree-4 extension, over u^4 = 11
+pub type Fp4 = BinomialExtension<4, 11>;
+
+/// The degree-5 extension, over u^5 = 2
+pub type Fp5 = BinomialExtension<5, 2>;
+
+/// An element c0 + c1 * u + ... + c(D-1) * u^(D-1) of F\[u\]/(u^D - W)
+///
+/// u^D - W must be irreducible, which for the degrees here means `W` is not a
+/// square when D = 4 and not a fifth power when D = 5.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BinomialExtension<const D: usize, const W: u32>([u32; D]);
+
+/// Multiplies a residue in either form by a small integer
+#[inline(always)]
+const fn mul_small(x: u32, y: u32) -> u32 {
+    reduce_barrett(x as u64 * y as u64)
+}
+
+/// Adds a term of at most `MONTGOMERY_BOUND` to a sum kept below it
+///
+/// Products of residues qualify, so the total fits in a `u64` and one
+/// conditional subtraction restores the bound.
+#[inline(always)]
+const fn accumulate(sum: u64, term: u64) -> u64 {
+    let sum = sum + term;
+    let (reduced, borrow) = sum.overflowing_sub(MONTGOMERY_BOUND);
+    if borrow {
+        sum
+    } else {
+        reduced
+    }
+}
+
+impl<const D: usize, const W: u32> BinomialExtension<D, W> {
+    /// The additive identity
+    pub const ZERO: Self = Self([0; D]);
+    /// The multiplicative identity
+    pub const ONE: Self = Self::from_base(1);
+
+    /// Creates an element from canonical coefficients
+    pub const fn new(coefficients: [u32; D]) -> Self {
+        let mut c = [0; D];
+        let mut i = 0;
+        while i < D {
+            c[i] = to_montgomery(coefficients[i]);
+            i += 1;
+        }
+        Self(c)
+    }
+
+    /// Embeds a canonical base field element
+    pub const fn from_base(c0: u32) -> Self {
+        let mut c = [0; D];
+        c[0] = to_montgomery(c0);
+        Self(c)
+    }
+
+    /// Returns the canonical coefficients
+    pub const fn value(self) -> [u32; D] {
+        let mut c = [0; D];
+        let mut i = 0;
+        while i < D {
+            c[i] = from_montgomery(self.0[i]);
+            i += 1;
+        }
+        c
+    }
+
+    /// Multiplies by reducing every product, as a reference for `mul`
+    #[inline(always)]
+    pub fn mul_schoolbook(self, rhs: Self) -> Self {
+        let mut c = [0; D];
+        for i in 0..D {
+            for j in 0..D {
+                let product = mul_montgomery(self.0[i], rhs.0[j]);
+                if i + j < D {
+                    c[i + j] = add(c[i + j], product);
+                } else {
+                    c[i + j - D] = add(c[i + j - D], mul_small(product, W));
+                }
+            }
+        }
+        Self(c)
+    }
+
+    /// Multiplies with D * (D + 1) / 2 products, Karatsuba style
+    ///
+    /// Each cross term a_i * b_j + a_j * b_i is (a_i + a_j) * (b_i + b_j) less
+    /// the diagonal products, which are subtracted by adding their complement
+    /// to `MONTGOMERY_BOUND`. The coefficients past u^D are reduced separately
+    /// before folding in by W, so this still makes 2 * D - 1 reductions.
+    #[inline(always)]
+    pub fn mul_karatsuba(self, rhs: Self) -> Self {
+        let (a, b) = (self.0, rhs.0);
+        let diagonal: [u64; D] = core::array::from_fn(|i| a[i] as u64 * b[i] as u64);
+        let (mut low, mut high) = ([0u64; D], [0u64; D]);
+        let mut place = |degree: usize, term: u64| {
+            let sum = if degree < D {
+                &mut low[degree]
+            } else {
+                &mut high[degree - D]
+            };
+            *sum = accumulate(*sum, term);
+        };
+        for i in 0..D {
+            place(2 * i, diagonal[i]);
+            for j in i + 1..D {
+                let cross = add(a[i], a[j]) as u64 * add(b[i], b[j]) as u64;
+                place(i + j, cross);
+                place(i + j, MONTGOMERY_BOUND - diagonal[i]);
+                place(i + j, MONTGOMERY_BOUND - diagonal[j]);
+            }
+        }
+        Self(core::array::from_fn(|k| {
+            let wrapped = mul_small(reduce_montgomery(high[k]), W);
+            add(reduce_montgomery(low[k]), wrapped)
+        }))
+    }
+
+    /// Squares using `mul`
+    #[inline(always)]
+    pub fn square(self) -> Self {
+        self * self
+    }
+}
+
+impl<const D: usize, const W: u32> Default for BinomialExtension<D, W> {
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl<const D: usize, const W: u32> Meta for BinomialExtension<D, W> {
+    const META: FieldMeta = META.extension(D);
+}
+
+impl<const D: usize, const W: u32> Add for BinomialExtension<D, W> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn add(self, rhs: Self) -> Self {
+        Self(core::array::from_fn(|i| add(self.0[i], rhs.0[i])))
+    }
+}
+
+impl<const D: usize, const W: u32> Sub for BinomialExtension<D, W> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn sub(self, rhs: Self) -> Self {
+        Self(core::array::from_fn(|i| sub(self.0[i], rhs.0[i])))
+    }
+}
+
+impl<const D: usize, const W: u32> Mul for BinomialExtension<D, W> {
+    type Output = Self;
+
+    /// Multiplies with one reduction per coefficient
+    ///
+    /// The coefficients of `rhs` are scaled by W up front, so a product a_i *
+    /// b_j wrapping past u^D needs no further work.
+    #[inline(always)]
+    fn mul(self, rhs: Self) -> Self {
+        let (a, b) = (self.0, rhs.0);
+        let wrapped: [u32; D] = core::array::from_fn(|j| mul_small(b[j], W));
+        Self(core::array::from_fn(|k| {
+            let mut sum = 0;
+            for i in 0..D {
+                let b_j = if i <= k { b[k - i] } else { wrapped[D + k - i] };
+                sum = accumulate(sum, a[i] as u64 * b_j as u64);
+            }
+            reduce_montgomery(sum)
+        }))
+    }
+}
+
+impl<const D: usize, const W: u32> Neg for BinomialExtension<D, W> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn neg(self) -> Self {
+        Self::ZERO - self
+    }
+}
+
+impl<const D: usize, const W: u32> AddAssign for BinomialExtension<D, W> {
+    #[inline(always)]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<const D: usize, const W: u32> SubAssign for BinomialExtension<D, W> {
+    #[inline(always)]
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<const D: usize, const W: u32> MulAssign for BinomialExtension<D, W> {
+    #[inline(always)]
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+    use crate::traits::PrimeField64;
+
+    fn pow(mut base: u32, mut exp: u32) -> u32 {
+        let mut acc = 1;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                acc = mul_modulo(acc, base);
+            }
+            base = mul_modulo(base, base);
+            exp >>= 1;
+        }
+        acc
+    }
+
+    /// Multiplies canonical coefficients with `%`, folding by W
+    fn mul_naive<const D: usize, const W: u32>(x: [u32; D], y: [u32; D]) -> [u32; D] {
+        let mut c = [0; D];
+        for i in 0..D {
+            for j in 0..D {
+                let product = mul_modulo(x[i], y[j]);
+                if i + j < D {
+                    c[i + j] = add_modulo(c[i + j], product);
+                } else {
+                    c[i + j - D] = add_modulo(c[i + j - D], mul_modulo(product, W));
+                }
+            }
+        }
+        c
+    }
+
+    fn check<const D: usize, const W: u32>() {
+        let random = Modulo::random(D * 1_000);
+        let max = [P - 1; D];
+        for (x, y) in random
+            .chunks(D)
+            .map(|c| core::array::from_fn(|i| c[i] as u32))
+            .chain([max, max])
+            .tuple_windows()
+        {
+            let (x_e, y_e) = (
+                BinomialExtension::<D, W>::new(x),
+                BinomialExtension::<D, W>::new(y),
+            );
+            assert_eq!((x_e * y_e).value(), mul_naive::<D, W>(x, y));
+            assert_eq!(x_e * y_e, x_e.mul_schoolbook(y_e));
+            assert_eq!(x_e * y_e, x_e.mul_karatsuba(y_e));
+            assert_eq!(x_e * BinomialExtension::ONE, x_e);
+            assert_eq!(x_e - y_e + y_e, x_e);
+            assert_eq!(-x_e + x_e, BinomialExtension::ZERO);
+        }
+
+        let mut u = [0; D];
+        u[1] = 1;
+        let u = BinomialExtension::<D, W>::new(u);
+        let u_d = (0..D).fold(BinomialExtension::ONE, |acc, _| acc * u);
+        assert_eq!(u_d, BinomialExtension::from_base(W));
+    }
+
+    #[test]
+    fn field_axioms() {
+        check::<4, 11>();
+        check::<5, 2>();
+    }
+
+    #[test]
+    fn irreducible() {
+        // with p ≡ 1 mod 4, u^4 - W is irreducible iff W is not a square
+        assert_eq!(P % 4, 1);
+        assert_eq!(pow(11, (P - 1) / 2), P - 1);
+        assert_eq!((P - 1) % 5, 0);
+        assert_ne!(pow(2, (P - 1) / 5), 1);
+    }
+}
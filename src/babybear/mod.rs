@@ -0,0 +1,45 @@
+//! The BabyBear field `P` = 2^31 - 2^27 + 1 and its reduction strategies
+//!
+//! Residues are `u32`; products fit in a `u64`. The strategy types implement
+//! [`PrimeField64`](crate::traits::PrimeField64) on zero-extended residues, so
+//! the benchmarks written for Goldilocks run unchanged against them. The
+//! degree-4 and degree-5 extensions [`Fp4`] and [`Fp5`] build on the
+//! Montgomery form.
+
+use crate::field31::field31_strategies;
+use crate::meta::FieldMeta;
+
+mod extension;
+
+pub use extension::*;
+
+/// 2^31 - 2^27 + 1
+pub const P: u32 = 0x7800_0001;
+
+/// The description shared by the strategies below
+pub const META: FieldMeta = FieldMeta::new("babybear", &[P as u64], Some(31));
+
+/// floor(2^64 / p) for Barrett reduction
+pub const BARRETT_MU: u64 = u64::MAX / P as u64;
+
+/// p^-1 mod 2^32, for Montgomery reduction
+pub const MONTGOMERY_INV: u32 = 0x8800_0001;
+
+/// (2^32)^2 mod p, used to move canonical values into Montgomery form
+pub const R2: u32 = 0x45dd_dde3;
+
+field31_strategies!("babybear", "BabyBear");
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn constants() {
+        assert_eq!(P, (1 << 31) - (1 << 27) + 1);
+        assert_eq!((META.bits, META.two_adicity), (31, 27));
+        assert_eq!(P.wrapping_mul(MONTGOMERY_INV), 1);
+        assert_eq!(R2 as u128, (1u128 << 64) % P as u128);
+        assert_eq!(BARRETT_MU, ((1u128 << 64) / P as u128) as u64);
+    }
+}
@@ -0,0 +1,391 @@
+//! The binary fields GF(2^64) and GF(2^128), as a baseline for the prime fields
+//!
+//! Elements are polynomials over GF(2) in the bits of a `u64` or `u128`, so
+//! addition is XOR and multiplication is a carry-less product reduced by the
+//! field polynomial. The product uses PCLMULQDQ on x86_64 or PMULL on aarch64
+//! when the target enables it, and the portable `clmul_soft` otherwise; the
+//! slice kernels also detect the instruction at runtime with [`is_available`].
+
+/// Low terms of the GF(2^64) polynomial x^64 + x^4 + x^3 + x + 1
+pub const POLY64: u64 = 0x1b;
+
+/// Low terms of the GF(2^128) polynomial x^128 + x^7 + x^2 + x + 1
+pub const POLY128: u128 = 0x87;
+
+/// Computes the carry-less product of `x` and `y` with shifts and XORs
+///
+/// Four bits of `y` are handled per step through a table of the multiples of
+/// `x` by each 4-bit polynomial.
+pub const fn clmul_soft(x: u64, y: u64) -> u128 {
+    let x = x as u128;
+    let mut table = [0u128; 16];
+    let mut i = 1;
+    while i < 16 {
+        table[i] = if i % 2 == 0 {
+            table[i / 2] << 1
+        } else {
+            table[i - 1] ^ x
+        };
+        i += 1;
+    }
+
+    let mut product = 0;
+    let mut shift = 0;
+    while shift < 64 {
+        product ^= table[(y >> shift) as usize & 0xf] << shift;
+        shift += 4;
+    }
+    product
+}
+
+/// Returns whether PCLMULQDQ on x86_64 or PMULL on aarch64 is enabled at
+/// compile time or, with `std`, detected at runtime
+#[inline]
+pub fn is_available() -> bool {
+    #[cfg(any(
+        all(target_arch = "x86_64", target_feature = "pclmulqdq"),
+        all(target_arch = "aarch64", target_feature = "aes"),
+    ))]
+    {
+        true
+    }
+    #[cfg(all(
+        target_arch = "x86_64",
+        not(target_feature = "pclmulqdq"),
+        feature = "std"
+    ))]
+    {
+        std::is_x86_feature_detected!("pclmulqdq")
+    }
+    #[cfg(all(target_arch = "aarch64", not(target_feature = "aes"), feature = "std"))]
+    {
+        std::arch::is_aarch64_feature_detected!("aes")
+    }
+    #[cfg(not(any(
+        all(
+            target_arch = "x86_64",
+            any(target_feature = "pclmulqdq", feature = "std")
+        ),
+        all(target_arch = "aarch64", any(target_feature = "aes", feature = "std")),
+    )))]
+    {
+        false
+    }
+}
+
+/// Computes the carry-less product of `x` and `y` with PCLMULQDQ
+///
+/// # Safety
+///
+/// Outside PCLMULQDQ code, the caller must ensure the CPU supports PCLMULQDQ,
+/// for example with [`is_available`].
+#[cfg(target_arch = "x86_64")]
+#[inline]
+#[target_feature(enable = "pclmulqdq")]
+pub fn clmul_hw(x: u64, y: u64) -> u128 {
+    use core::arch::x86_64::{
+        _mm_clmulepi64_si128, _mm_cvtsi128_si64, _mm_cvtsi64_si128, _mm_unpackhi_epi64,
+    };
+
+    let product = _mm_clmulepi64_si128(_mm_cvtsi64_si128(x as i64), _mm_cvtsi64_si128(y as i64), 0);
+    let lo = _mm_cvtsi128_si64(product) as u64;
+    let hi = _mm_cvtsi128_si64(_mm_unpackhi_epi64(product, product)) as u64;
+    (hi as u128) << 64 | lo as u128
+}
+
+/// Computes the carry-less product of `x` and `y` with PMULL
+///
+/// # Safety
+///
+/// Outside AES code, the caller must ensure the CPU supports the AES
+/// extension, which includes PMULL, for example with [`is_available`].
+#[cfg(target_arch = "aarch64")]
+#[inline]
+#[target_feature(enable = "aes")]
+pub fn clmul_hw(x: u64, y: u64) -> u128 {
+    core::arch::aarch64::vmull_p64(x, y)
+}
+
+/// Computes the carry-less product of `x` and `y`
+///
+/// This uses [`clmul_hw`] when the target enables it at compile time, and
+/// [`clmul_soft`] otherwise; the slice kernels also detect it at runtime.
+#[inline(always)]
+pub fn clmul(x: u64, y: u64) -> u128 {
+    #[cfg(any(
+        all(target_arch = "x86_64", target_feature = "pclmulqdq"),
+        all(target_arch = "aarch64", target_feature = "aes"),
+    ))]
+    {
+        // the cfg above guarantees the target supports the instruction
+        unsafe { clmul_hw(x, y) }
+    }
+    #[cfg(not(any(
+        all(target_arch = "x86_64", target_feature = "pclmulqdq"),
+        all(target_arch = "aarch64", target_feature = "aes"),
+    )))]
+    {
+        clmul_soft(x, y)
+    }
+}
+
+/// Reduces a 128-bit carry-less product modulo the GF(2^64) polynomial
+///
+/// x^64 ≡ x^4 + x^3 + x + 1, so the high word folds in shifted by those
+/// exponents; the at most four bits shifted out fold in once more.
+#[inline(always)]
+pub const fn reduce64(x: u128) -> u64 {
+    let (lo, hi) = (x as u64, (x >> 64) as u64);
+    let overflow = hi >> 60 ^ hi >> 61 ^ hi >> 63;
+    let hi = hi ^ overflow;
+    lo ^ hi ^ hi << 1 ^ hi << 3 ^ hi << 4
+}
+
+/// Multiplies two GF(2^64) elements
+#[inline(always)]
+pub fn mul64(x: u64, y: u64) -> u64 {
+    reduce64(clmul(x, y))
+}
+
+/// Multiplies two GF(2^64) elements with `clmul_soft`
+#[inline(always)]
+pub const fn mul64_soft(x: u64, y: u64) -> u64 {
+    reduce64(clmul_soft(x, y))
+}
+
+/// Multiplies two GF(2^64) elements with [`clmul_hw`]
+///
+/// # Safety
+///
+/// The caller must ensure the CPU supports [`clmul_hw`], for example with
+/// [`is_available`].
+#[cfg(target_arch = "x86_64")]
+#[inline]
+#[target_feature(enable = "pclmulqdq")]
+pub fn mul64_hw(x: u64, y: u64) -> u64 {
+    reduce64(clmul_hw(x, y))
+}
+
+/// Multiplies two GF(2^64) elements with [`clmul_hw`]
+///
+/// # Safety
+///
+/// The caller must ensure the CPU supports [`clmul_hw`], for example with
+/// [`is_available`].
+#[cfg(target_arch = "aarch64")]
+#[inline]
+#[target_feature(enable = "aes")]
+pub fn mul64_hw(x: u64, y: u64) -> u64 {
+    reduce64(clmul_hw(x, y))
+}
+
+/// Reduces a 256-bit carry-less product, as low and high halves, modulo the
+/// GF(2^128) polynomial
+#[inline(always)]
+pub const fn reduce128(lo: u128, hi: u128) -> u128 {
+    let overflow = hi >> 121 ^ hi >> 126 ^ hi >> 127;
+    let hi = hi ^ overflow;
+    lo ^ hi ^ hi << 1 ^ hi << 2 ^ hi << 7
+}
+
+/// Computes the 256-bit carry-less product of `x` and `y` from three 64-bit
+/// products by `clmul`, Karatsuba style
+#[inline(always)]
+fn clmul128(x: u128, y: u128, clmul: impl Fn(u64, u64) -> u128) -> (u128, u128) {
+    let (x_0, x_1) = (x as u64, (x >> 64) as u64);
+    let (y_0, y_1) = (y as u64, (y >> 64) as u64);
+    let lo = clmul(x_0, y_0);
+    let hi = clmul(x_1, y_1);
+    let mid = clmul(x_0 ^ x_1, y_0 ^ y_1) ^ lo ^ hi;
+    (lo ^ mid << 64, hi ^ mid >> 64)
+}
+
+/// Multiplies two GF(2^128) elements
+#[inline(always)]
+pub fn mul128(x: u128, y: u128) -> u128 {
+    let (lo, hi) = clmul128(x, y, clmul);
+    reduce128(lo, hi)
+}
+
+/// Multiplies two GF(2^128) elements with `clmul_soft`
+#[inline(always)]
+pub fn mul128_soft(x: u128, y: u128) -> u128 {
+    let (lo, hi) = clmul128(x, y, clmul_soft);
+    reduce128(lo, hi)
+}
+
+/// Multiplies two GF(2^128) elements with [`clmul_hw`]
+///
+/// # Safety
+///
+/// The caller must ensure the CPU supports [`clmul_hw`], for example with
+/// [`is_available`].
+#[cfg(target_arch = "x86_64")]
+#[inline]
+#[target_feature(enable = "pclmulqdq")]
+pub fn mul128_hw(x: u128, y: u128) -> u128 {
+    let (lo, hi) = clmul128(x, y, |x, y| clmul_hw(x, y));
+    reduce128(lo, hi)
+}
+
+/// Multiplies two GF(2^128) elements with [`clmul_hw`]
+///
+/// # Safety
+///
+/// The caller must ensure the CPU supports [`clmul_hw`], for example with
+/// [`is_available`].
+#[cfg(target_arch = "aarch64")]
+#[inline]
+#[target_feature(enable = "aes")]
+pub fn mul128_hw(x: u128, y: u128) -> u128 {
+    let (lo, hi) = clmul128(x, y, |x, y| clmul_hw(x, y));
+    reduce128(lo, hi)
+}
+
+/// Defines a safe elementwise product kernel that checks [`is_available`]
+/// once and multiplies with `$hw` or, without the instruction, `$soft`
+macro_rules! product_slice_kernel {
+    ($(#[$doc:meta])* $name:ident, $element:ty, $hw:ident, $soft:ident) => {
+        $(#[$doc])*
+        ///
+        /// # Panics
+        ///
+        /// Panics if the slices differ in length.
+        pub fn $name(dst: &mut [$element], a: &[$element], b: &[$element]) {
+            #[cfg(target_arch = "x86_64")]
+            #[target_feature(enable = "pclmulqdq")]
+            fn hardware(dst: &mut [$element], a: &[$element], b: &[$element]) {
+                for ((d, &x), &y) in dst.iter_mut().zip(a).zip(b) {
+                    *d = $hw(x, y);
+                }
+            }
+
+            #[cfg(target_arch = "aarch64")]
+            #[target_feature(enable = "aes")]
+            fn hardware(dst: &mut [$element], a: &[$element], b: &[$element]) {
+                for ((d, &x), &y) in dst.iter_mut().zip(a).zip(b) {
+                    *d = $hw(x, y);
+                }
+            }
+
+            assert!(
+                dst.len() == a.len() && a.len() == b.len(),
+                "operands differ in length"
+            );
+            #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+            if is_available() {
+                // the instruction was detected just above
+                return unsafe { hardware(dst, a, b) };
+            }
+            for ((d, &x), &y) in dst.iter_mut().zip(a).zip(b) {
+                *d = $soft(x, y);
+            }
+        }
+    };
+}
+
+product_slice_kernel!(
+    /// Writes the GF(2^64) products a[i] * b[i] into `dst`
+    mul64_slices,
+    u64,
+    mul64_hw,
+    mul64_soft
+);
+
+product_slice_kernel!(
+    /// Writes the GF(2^128) products a[i] * b[i] into `dst`
+    mul128_slices,
+    u128,
+    mul128_hw,
+    mul128_soft
+);
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use ::rand::random;
+
+    use super::*;
+
+    /// Multiplies bit by bit, reducing after every shift
+    fn mul_naive(x: u128, y: u128, bits: u32, poly: u128) -> u128 {
+        let top = 1u128 << (bits - 1);
+        let mask = if bits == 128 {
+            u128::MAX
+        } else {
+            (1 << bits) - 1
+        };
+        let (mut x, mut product) = (x, 0);
+        for i in 0..bits {
+            if y >> i & 1 == 1 {
+                product ^= x;
+            }
+            let carry = x & top != 0;
+            x = x << 1 & mask;
+            if carry {
+                x ^= poly;
+            }
+        }
+        product
+    }
+
+    #[test]
+    fn carry_less_products() {
+        for _ in 0..1_000 {
+            let (x, y) = (random::<u64>(), random::<u64>());
+            let product = mul_naive(x as u128, y as u128, 128, 0);
+            assert_eq!(clmul_soft(x, y), product);
+            assert_eq!(clmul(x, y), product);
+            #[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+            if is_available() {
+                // the instruction was detected just above
+                assert_eq!(unsafe { clmul_hw(x, y) }, product);
+            }
+        }
+        assert_eq!(
+            clmul_soft(u64::MAX, u64::MAX),
+            0x5555_5555_5555_5555_5555_5555_5555_5555
+        );
+    }
+
+    #[test]
+    fn field_products() {
+        let edge_cases = [0, 1, u64::MAX, 1 << 63];
+        for _ in 0..1_000 {
+            let (x, y) = (random::<u64>(), random::<u64>());
+            for (x, y) in [(x, y), (u64::MAX, y), (1 << 63, 1 << 63)] {
+                let expected = mul_naive(x as u128, y as u128, 64, POLY64 as u128) as u64;
+                assert_eq!(mul64(x, y), expected);
+                assert_eq!(mul64_soft(x, y), expected);
+            }
+            let (x, y) = (random::<u128>(), random::<u128>());
+            for (x, y) in [(x, y), (u128::MAX, y), (u128::MAX, u128::MAX)] {
+                let expected = mul_naive(x, y, 128, POLY128);
+                assert_eq!(mul128(x, y), expected);
+                assert_eq!(mul128_soft(x, y), expected);
+            }
+        }
+        for x in edge_cases {
+            assert_eq!(mul64(x, 1), x);
+            assert_eq!(mul128(x as u128, 1), x as u128);
+        }
+    }
+
+    #[test]
+    fn slice_kernels() {
+        for len in [0, 1, 1_001] {
+            let (a, b): (Vec<_>, Vec<_>) = (0..len).map(|_| random::<(u64, u64)>()).unzip();
+            let mut dst = vec![0; len];
+            mul64_slices(&mut dst, &a, &b);
+            for ((&d, &x), &y) in dst.iter().zip(&a).zip(&b) {
+                assert_eq!(d, mul64_soft(x, y));
+            }
+
+            let (a, b): (Vec<_>, Vec<_>) = (0..len).map(|_| random::<(u128, u128)>()).unzip();
+            let mut dst = vec![0; len];
+            mul128_slices(&mut dst, &a, &b);
+            for ((&d, &x), &y) in dst.iter().zip(&a).zip(&b) {
+                assert_eq!(d, mul128_soft(x, y));
+            }
+        }
+    }
+}
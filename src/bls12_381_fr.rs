@@ -0,0 +1,104 @@
+//! The BLS12-381 scalar field Fr over four 64-bit limbs
+//!
+//! Elements are `[u64; 4]` in Montgomery form, least significant limb first,
+//! with the operations of [`MultiLimbModulus`]. r - 1 is divisible by 2^32,
+//! so Fr supports NTTs of up to 2^32 points.
+
+use crate::meta::FieldMeta;
+use crate::multilimb::MultiLimbModulus;
+
+/// The 255-bit group order r of BLS12-381, least significant limb first
+pub const MODULUS: [u64; 4] = [
+    0xffff_ffff_0000_0001,
+    0x53bd_a402_fffe_5bfe,
+    0x3339_d808_09a1_d805,
+    0x73ed_a753_299d_7d48,
+];
+
+/// The description of the field
+pub const META: FieldMeta =
+    FieldMeta::new("bls12_381_fr", &MODULUS, Some(MULTIPLICATIVE_GENERATOR[0]));
+
+/// The Montgomery constants and operations for `MODULUS`
+pub const FIELD: MultiLimbModulus<4> = MultiLimbModulus::new(MODULUS);
+
+/// A generator of the multiplicative group of order r - 1
+pub const MULTIPLICATIVE_GENERATOR: [u64; 4] = [7, 0, 0, 0];
+
+/// The largest k such that 2^k divides r - 1
+pub const TWO_ADICITY: u32 = 32;
+
+/// A primitive 2^32-th root of unity, 7^((r - 1) / 2^32), as a canonical value
+pub const ROOT_OF_UNITY: [u64; 4] = [
+    0x3829_971f_439f_0d2b,
+    0xb636_8350_8c22_80b9,
+    0xd09b_6819_22c8_13b4,
+    0x16a2_a19e_dfe8_1f20,
+];
+
+/// Multiplies two values in Montgomery form using `FIELD.mul_cios`
+#[inline]
+pub const fn mul(x: &[u64; 4], y: &[u64; 4]) -> [u64; 4] {
+    FIELD.mul_cios(x, y)
+}
+
+/// Returns a primitive 2^k-th root of unity in Montgomery form
+///
+/// Panics if `k` exceeds `TWO_ADICITY`.
+pub const fn root_of_unity(k: u32) -> [u64; 4] {
+    assert!(k <= TWO_ADICITY, "k must not exceed the two-adicity");
+    let mut root = FIELD.to_montgomery(&ROOT_OF_UNITY);
+    let mut i = k;
+    while i < TWO_ADICITY {
+        root = mul(&root, &root);
+        i += 1;
+    }
+    root
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use num_bigint::BigUint;
+
+    use super::*;
+
+    fn to_biguint(x: &[u64; 4]) -> BigUint {
+        x.iter()
+            .rev()
+            .fold(BigUint::default(), |acc, &limb| (acc << 64u32) + limb)
+    }
+
+    #[test]
+    fn constants() {
+        let r = to_biguint(&MODULUS);
+        let r_minus_one = &r - 1u32;
+        assert_eq!(r.bits(), 255);
+        assert_eq!((META.bits, META.two_adicity), (255, TWO_ADICITY));
+        assert_eq!(r_minus_one.trailing_zeros(), Some(TWO_ADICITY as u64));
+        assert_eq!(FIELD.neg_inv, 0xffff_fffe_ffff_ffff);
+
+        let generator = to_biguint(&MULTIPLICATIVE_GENERATOR);
+        let exponent = &r_minus_one >> TWO_ADICITY;
+        assert_eq!(generator.modpow(&exponent, &r), to_biguint(&ROOT_OF_UNITY));
+        // 7 is not a square, so every 2-power order is reached
+        assert_eq!(generator.modpow(&(&r_minus_one >> 1u32), &r), r_minus_one);
+    }
+
+    #[test]
+    fn roots_of_unity() {
+        let one = FIELD.to_montgomery(&[1, 0, 0, 0]);
+        let minus_one = FIELD.to_montgomery(&[MODULUS[0] - 1, MODULUS[1], MODULUS[2], MODULUS[3]]);
+        assert_eq!(root_of_unity(0), one);
+        assert_eq!(root_of_unity(1), minus_one);
+        for k in 1..=TWO_ADICITY {
+            let root = root_of_unity(k);
+            assert_eq!(mul(&root, &root), root_of_unity(k - 1));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "k must not exceed the two-adicity")]
+    fn root_of_unity_rejects_large_k() {
+        root_of_unity(TWO_ADICITY + 1);
+    }
+}
@@ -0,0 +1,72 @@
+//! The BN254 (alt_bn128) scalar field over four 64-bit limbs
+//!
+//! Elements are `[u64; 4]` in Montgomery form, least significant limb first,
+//! with the operations of [`MultiLimbModulus`].
+
+use crate::meta::FieldMeta;
+use crate::multilimb::MultiLimbModulus;
+
+/// The 254-bit group order r of BN254, least significant limb first
+pub const MODULUS: [u64; 4] = [
+    0x43e1_f593_f000_0001,
+    0x2833_e848_79b9_7091,
+    0xb850_45b6_8181_585d,
+    0x3064_4e72_e131_a029,
+];
+
+/// The description of the field
+pub const META: FieldMeta = FieldMeta::new("bn254", &MODULUS, Some(5));
+
+/// The Montgomery constants and operations for `MODULUS`
+pub const FIELD: MultiLimbModulus<4> = MultiLimbModulus::new(MODULUS);
+
+/// Multiplies two values in Montgomery form using `FIELD.mul_cios`
+#[inline]
+pub const fn mul(x: &[u64; 4], y: &[u64; 4]) -> [u64; 4] {
+    FIELD.mul_cios(x, y)
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use num_bigint::BigUint;
+
+    use super::*;
+
+    fn to_biguint(x: &[u64; 4]) -> BigUint {
+        x.iter()
+            .rev()
+            .fold(BigUint::default(), |acc, &limb| (acc << 64u32) + limb)
+    }
+
+    #[test]
+    fn constants() {
+        let r = "21888242871839275222246405745257275088548364400416034343698204186575808495617";
+        assert_eq!(to_biguint(&MODULUS), r.parse().unwrap());
+        assert_eq!((META.bits, META.two_adicity), (254, 28));
+        assert_eq!(FIELD.neg_inv, 0xc2e1_f593_efff_ffff);
+        assert_eq!(
+            FIELD.r2,
+            [
+                0x1bb8_e645_ae21_6da7,
+                0x53fe_3ab1_e35c_59e3,
+                0x8c49_833d_53bb_8085,
+                0x0216_d0b1_7f4e_44a5,
+            ]
+        );
+    }
+
+    #[test]
+    fn arithmetic() {
+        let minus_one = FIELD.to_montgomery(&[MODULUS[0] - 1, MODULUS[1], MODULUS[2], MODULUS[3]]);
+        assert_eq!(
+            FIELD.from_montgomery(&mul(&minus_one, &minus_one)),
+            [1, 0, 0, 0]
+        );
+
+        let r = to_biguint(&MODULUS);
+        let (x, y) = ([3, 5, 7, 11], [u64::MAX, 1, 2, 0x0300_0000_0000_0000]);
+        let product =
+            FIELD.from_montgomery(&mul(&FIELD.to_montgomery(&x), &FIELD.to_montgomery(&y)));
+        assert_eq!(to_biguint(&product), to_biguint(&x) * to_biguint(&y) % r);
+    }
+}
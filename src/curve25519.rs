@@ -0,0 +1,240 @@
+//! The Curve25519 field `P` = 2^255 - 19 in two limb layouts
+//!
+//! The saturated layout packs canonical values into four 64-bit limbs and
+//! folds the high half of a product as 2^256 ≡ 38. The unsaturated layout
+//! spreads them over five 51-bit limbs in `u64`s: the 13 spare bits per limb
+//! absorb carries, so additions need no carry propagation at all, and products
+//! are reduced with one carry pass.
+
+use crate::meta::FieldMeta;
+use crate::multilimb::{adc, geq, mac, mul_wide4, sbb};
+
+/// 2^255 - 19, least significant limb first
+pub const MODULUS: [u64; 4] = [u64::MAX - 18, u64::MAX, u64::MAX, u64::MAX >> 1];
+
+/// The description of the field
+pub const META: FieldMeta = FieldMeta::new("curve25519", &MODULUS, Some(2));
+
+/// Mask of the low 51 bits of a radix-2^51 limb
+pub const MASK51: u64 = (1 << 51) - 1;
+
+/// Maps any saturated `x` to its canonical residue
+///
+/// Bit 255 folds in as 19, leaving at most (2^255 - 1) + 19 = p + 37. That is
+/// below 2p, so one conditional subtraction suffices.
+#[inline(always)]
+const fn canonicalize(x: &[u64; 4]) -> [u64; 4] {
+    let top = x[3] >> 63;
+    let (t_0, carry) = adc(x[0], 19 * top, 0);
+    let (t_1, carry) = adc(x[1], 0, carry);
+    let (t_2, carry) = adc(x[2], 0, carry);
+    let t = [t_0, t_1, t_2, (x[3] & (u64::MAX >> 1)) + carry];
+    if geq(&t, &MODULUS) {
+        sbb(&t, &MODULUS).0
+    } else {
+        t
+    }
+}
+
+/// Reduces any 512-bit `x` to its canonical residue
+///
+/// The high half times 38 leaves a carry limb below 39, which is folded in
+/// times 38 once more; wrapping past 2^256 leaves a small value, to which 38
+/// is added back.
+#[inline]
+pub const fn reduce(x: &[u64; 8]) -> [u64; 4] {
+    let mut t = [0u64; 4];
+    let mut carry = 0;
+    let mut i = 0;
+    while i < 4 {
+        (t[i], carry) = mac(x[i], x[i + 4], 38, carry);
+        i += 1;
+    }
+
+    let (t_0, carry) = adc(t[0], carry * 38, 0);
+    let (t_1, carry) = adc(t[1], 0, carry);
+    let (t_2, carry) = adc(t[2], 0, carry);
+    let (t_3, carry) = adc(t[3], 0, carry);
+    let (t_0, carry) = adc(t_0, carry * 38, 0);
+    canonicalize(&[t_0, t_1 + carry, t_2, t_3])
+}
+
+/// Adds two canonical saturated values
+#[inline]
+pub const fn add(x: &[u64; 4], y: &[u64; 4]) -> [u64; 4] {
+    let mut t = [0u64; 4];
+    let mut carry = 0;
+    let mut i = 0;
+    while i < 4 {
+        (t[i], carry) = adc(x[i], y[i], carry);
+        i += 1;
+    }
+    // both are below 2^255, so the sum fits in four limbs
+    canonicalize(&t)
+}
+
+/// Multiplies two canonical saturated values
+#[inline]
+pub const fn mul(x: &[u64; 4], y: &[u64; 4]) -> [u64; 4] {
+    reduce(&mul_wide4(x, y))
+}
+
+/// Converts a canonical saturated value into five radix-2^51 limbs
+pub const fn to_radix51(x: &[u64; 4]) -> [u64; 5] {
+    [
+        x[0] & MASK51,
+        (x[0] >> 51 | x[1] << 13) & MASK51,
+        (x[1] >> 38 | x[2] << 26) & MASK51,
+        (x[2] >> 25 | x[3] << 39) & MASK51,
+        x[3] >> 12,
+    ]
+}
+
+/// Converts radix-2^51 limbs below 2^54 into a canonical saturated value
+pub const fn from_radix51(x: &[u64; 5]) -> [u64; 4] {
+    let x = carry51(x);
+    let mut t = [0u64; 4];
+    let mut acc = x[0] as u128 + ((x[1] as u128) << 51);
+    t[0] = acc as u64;
+    acc = (acc >> 64) + ((x[2] as u128) << 38);
+    t[1] = acc as u64;
+    acc = (acc >> 64) + ((x[3] as u128) << 25);
+    t[2] = acc as u64;
+    t[3] = (acc >> 64) as u64 + (x[4] << 12);
+    canonicalize(&t)
+}
+
+/// Propagates carries so that every limb is below 2^51, except the lowest,
+/// which is below 2^51 + 2^18
+#[inline(always)]
+const fn carry51(x: &[u64; 5]) -> [u64; 5] {
+    let mut t = *x;
+    let mut i = 0;
+    while i < 4 {
+        t[i + 1] += t[i] >> 51;
+        t[i] &= MASK51;
+        i += 1;
+    }
+    t[0] += 19 * (t[4] >> 51);
+    t[4] &= MASK51;
+    t
+}
+
+/// Adds radix-2^51 values limb by limb, without propagating carries
+///
+/// A few such sums of reduced values stay below the 2^54 that `mul51` accepts.
+#[inline]
+pub const fn add51(x: &[u64; 5], y: &[u64; 5]) -> [u64; 5] {
+    [
+        x[0] + y[0],
+        x[1] + y[1],
+        x[2] + y[2],
+        x[3] + y[3],
+        x[4] + y[4],
+    ]
+}
+
+/// Multiplies radix-2^51 values with limbs below 2^54, returning limbs below
+/// 2^51 + 2^18
+///
+/// Partial products landing at 2^255 or above wrap around times 19.
+#[inline]
+pub const fn mul51(x: &[u64; 5], y: &[u64; 5]) -> [u64; 5] {
+    const fn m(a: u64, b: u64) -> u128 {
+        a as u128 * b as u128
+    }
+    let [a_0, a_1, a_2, a_3, a_4] = *x;
+    let [b_0, b_1, b_2, b_3, b_4] = *y;
+    let (b_1_19, b_2_19, b_3_19, b_4_19) = (19 * b_1, 19 * b_2, 19 * b_3, 19 * b_4);
+
+    let r_0 = m(a_0, b_0) + m(a_1, b_4_19) + m(a_2, b_3_19) + m(a_3, b_2_19) + m(a_4, b_1_19);
+    let r_1 = m(a_0, b_1) + m(a_1, b_0) + m(a_2, b_4_19) + m(a_3, b_3_19) + m(a_4, b_2_19);
+    let r_2 = m(a_0, b_2) + m(a_1, b_1) + m(a_2, b_0) + m(a_3, b_4_19) + m(a_4, b_3_19);
+    let r_3 = m(a_0, b_3) + m(a_1, b_2) + m(a_2, b_1) + m(a_3, b_0) + m(a_4, b_4_19);
+    let r_4 = m(a_0, b_4) + m(a_1, b_3) + m(a_2, b_2) + m(a_3, b_1) + m(a_4, b_0);
+
+    let r_1 = r_1 + (r_0 >> 51);
+    let r_2 = r_2 + (r_1 >> 51);
+    let r_3 = r_3 + (r_2 >> 51);
+    let r_4 = r_4 + (r_3 >> 51);
+    let carry = (r_4 >> 51) as u64;
+    let r_0 = (r_0 as u64 & MASK51) + 19 * carry;
+    [
+        r_0 & MASK51,
+        (r_1 as u64 & MASK51) + (r_0 >> 51),
+        r_2 as u64 & MASK51,
+        r_3 as u64 & MASK51,
+        r_4 as u64 & MASK51,
+    ]
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use ::rand::{thread_rng, Rng};
+    use num_bigint::BigUint;
+
+    use super::*;
+
+    fn to_biguint<const N: usize>(x: &[u64; N], radix: u32) -> BigUint {
+        x.iter()
+            .rev()
+            .fold(BigUint::default(), |acc, &limb| (acc << radix) + limb)
+    }
+
+    #[test]
+    fn layouts() {
+        let p = (BigUint::from(1u32) << 255u32) - 19u32;
+        assert_eq!(to_biguint(&MODULUS, 64), p);
+
+        let mut rng = thread_rng();
+        let mut random = || {
+            let mut x = core::array::from_fn::<u64, 4, _>(|_| rng.gen());
+            x[3] >>= 1;
+            canonicalize(&x)
+        };
+        let max = [MODULUS[0] - 1, MODULUS[1], MODULUS[2], MODULUS[3]];
+        for _ in 0..1_000 {
+            let (x, y) = (random(), random());
+            for (x, y) in [(x, y), (max, y), (max, max), ([0; 4], y)] {
+                let (a, b) = (to_biguint(&x, 64), to_biguint(&y, 64));
+                let (x_51, y_51) = (to_radix51(&x), to_radix51(&y));
+                assert_eq!(to_biguint(&x_51, 51), a);
+                assert_eq!(from_radix51(&x_51), x);
+
+                let product = &a * &b % &p;
+                assert_eq!(to_biguint(&mul(&x, &y), 64), product);
+                assert_eq!(to_biguint(&from_radix51(&mul51(&x_51, &y_51)), 64), product);
+                assert_eq!(to_biguint(&add(&x, &y), 64), (&a + &b) % &p);
+
+                // sums of up to four values stay within the 2^54 limb bound
+                let sum = add51(&add51(&x_51, &y_51), &add51(&x_51, &y_51));
+                let expected = (&a + &b) * 2u32 * &b % &p;
+                assert_eq!(to_biguint(&from_radix51(&mul51(&sum, &y_51)), 64), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn reductions() {
+        let p = (BigUint::from(1u32) << 255u32) - 19u32;
+        for x in [
+            [0; 8],
+            [u64::MAX; 8],
+            [u64::MAX, u64::MAX, u64::MAX, u64::MAX, 0, 0, 0, 0],
+        ] {
+            assert_eq!(to_biguint(&reduce(&x), 64), to_biguint(&x, 64) % &p);
+        }
+        // 2^256 - 1 folds to exactly p + 37, the largest value before the subtraction
+        assert_eq!(canonicalize(&[u64::MAX; 4]), [37, 0, 0, 0]);
+        let top = [MASK51 + (1 << 18), MASK51, MASK51, MASK51, MASK51];
+        assert_eq!(
+            to_biguint(&from_radix51(&top), 64),
+            to_biguint(&top, 51) % &p
+        );
+        let loose = [(1 << 54) - 1; 5];
+        assert_eq!(
+            to_biguint(&from_radix51(&loose), 64),
+            to_biguint(&loose, 51) % &p
+        );
+    }
+}
@@ -0,0 +1,83 @@
+//! Cycle counts on RISC-V, for measuring kernels on boards without `std`
+//!
+//! [`read`] returns the `cycle` counter of the Zicntr extension, which SiFive
+//! cores implement; cores without it raise an illegal-instruction exception.
+//! Linux 6.6 and later also trap user-space reads unless the
+//! `kernel.perf_user_access` sysctl is 2, so hosted benchmarks should stay
+//! with Criterion's wall-clock time.
+
+use core::arch::asm;
+use core::hint::black_box;
+
+use crate::traits::PrimeField64;
+
+/// Returns the number of cycles since an arbitrary point in the past
+#[inline(always)]
+pub fn read() -> u64 {
+    #[cfg(target_arch = "riscv64")]
+    {
+        let cycles: u64;
+        // reads a counter without side effects
+        unsafe { asm!("rdcycle {}", out(reg) cycles, options(nomem, nostack)) };
+        cycles
+    }
+    #[cfg(target_arch = "riscv32")]
+    {
+        // the high half is read again in case the low half wrapped in between
+        loop {
+            let (hi, lo, hi_again): (u32, u32, u32);
+            unsafe {
+                asm!(
+                    "rdcycleh {hi}",
+                    "rdcycle {lo}",
+                    "rdcycleh {hi_again}",
+                    hi = out(reg) hi,
+                    lo = out(reg) lo,
+                    hi_again = out(reg) hi_again,
+                    options(nomem, nostack),
+                );
+            }
+            if hi == hi_again {
+                return (hi as u64) << 32 | lo as u64;
+            }
+        }
+    }
+}
+
+/// Runs `f`, returning its result and the number of cycles it took
+#[inline(always)]
+pub fn count<T>(f: impl FnOnce() -> T) -> (T, u64) {
+    let start = read();
+    let result = f();
+    let end = read();
+    (result, end.wrapping_sub(start))
+}
+
+/// Returns the average cycles per multiplication in a dependent chain of `n`
+///
+/// Each product feeds the next, so this is the latency of [`PrimeField64::mul`]
+/// for strategy `F`, including the loop overhead of one iteration.
+pub fn mul_latency<F: PrimeField64>(n: u32) -> u64 {
+    let x = black_box(F::MODULUS - 2);
+    let (product, cycles) = count(|| (0..n).fold(x, |acc, _| F::mul(acc, x)));
+    black_box(product);
+    cycles / n.max(1) as u64
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::goldilocks::{Limbs32, Reduce159};
+
+    #[test]
+    #[cfg_attr(
+        target_os = "linux",
+        ignore = "Linux traps rdcycle unless kernel.perf_user_access is 2"
+    )]
+    fn counter_advances() {
+        let (_, cycles) = count(|| black_box((0..1_000u64).sum::<u64>()));
+        assert!(cycles > 0);
+        assert!(mul_latency::<Reduce159>(100) > 0);
+        assert!(mul_latency::<Limbs32>(100) > 0);
+    }
+}
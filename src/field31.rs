@@ -0,0 +1,240 @@
+//! The reduction strategies shared by the 31-bit fields
+//!
+//! [`babybear`](crate::babybear) and [`koalabear`](crate::koalabear) differ
+//! only in their constants, so [`field31_strategies`] defines the arithmetic
+//! and the strategy types once against the constants in scope.
+
+/// Defines the `u32` arithmetic and the `Modulo`, `Barrett` and `Montgomery`
+/// strategies of a 31-bit field, together with their tests
+///
+/// The calling module provides `P`, `META`, `BARRETT_MU`, `MONTGOMERY_INV`
+/// and `R2`; `$name` prefixes the strategy names and `$title` the docs. None
+/// of the reductions depend on the shape of `P` beyond 2p < 2^32.
+macro_rules! field31_strategies {
+    ($name:literal, $title:literal) => {
+        /// Performs addition with modulo using `%` operator
+        pub const fn add_modulo(x: u32, y: u32) -> u32 {
+            ((x as u64 + y as u64) % P as u64) as u32
+        }
+
+        /// Performs addition of canonical values, adding `P` back under a borrow mask
+        ///
+        /// Since 2p < 2^32, the sum itself cannot overflow.
+        pub const fn add(x: u32, y: u32) -> u32 {
+            let (reduced, borrow) = (x + y).overflowing_sub(P);
+            reduced.wrapping_add(P & (borrow as u32).wrapping_neg())
+        }
+
+        /// Performs subtraction of canonical values, adding `P` back under a borrow mask
+        pub const fn sub(x: u32, y: u32) -> u32 {
+            let (diff, borrow) = x.overflowing_sub(y);
+            diff.wrapping_add(P & (borrow as u32).wrapping_neg())
+        }
+
+        /// Performs multiplication with modulo using `%` operator
+        pub const fn mul_modulo(x: u32, y: u32) -> u32 {
+            (x as u64 * y as u64 % P as u64) as u32
+        }
+
+        /// Reduces any `u64` to its canonical residue using Barrett reduction
+        ///
+        /// The quotient estimate from the high half of x * μ is short by at most one.
+        #[inline(always)]
+        pub const fn reduce_barrett(x: u64) -> u32 {
+            let q = ((x as u128 * BARRETT_MU as u128) >> 64) as u64;
+            let r = x - q * P as u64;
+            if r >= P as u64 {
+                (r - P as u64) as u32
+            } else {
+                r as u32
+            }
+        }
+
+        /// Performs multiplication with `reduce_barrett`
+        pub const fn mul_barrett(x: u32, y: u32) -> u32 {
+            reduce_barrett(x as u64 * y as u64)
+        }
+
+        /// Computes x * 2^-32 mod p for x < p * 2^32, with 32-bit limbs
+        ///
+        /// t * p agrees with x in the low 32 bits, so only the high words are
+        /// subtracted, and a borrow adds p back.
+        #[inline(always)]
+        pub const fn reduce_montgomery(x: u64) -> u32 {
+            let t = (x as u32).wrapping_mul(MONTGOMERY_INV);
+            let (diff, borrow) = x.overflowing_sub(t as u64 * P as u64);
+            ((diff >> 32) as u32).wrapping_add(P & (borrow as u32).wrapping_neg())
+        }
+
+        /// Performs multiplication with `reduce_montgomery`, for values in Montgomery form
+        pub const fn mul_montgomery(x: u32, y: u32) -> u32 {
+            reduce_montgomery(x as u64 * y as u64)
+        }
+
+        /// Moves a canonical `x` into Montgomery form, x * 2^32 mod p
+        pub const fn to_montgomery(x: u32) -> u32 {
+            reduce_montgomery(x as u64 * R2 as u64)
+        }
+
+        /// Moves `x` out of Montgomery form, x * 2^-32 mod p
+        pub const fn from_montgomery(x: u32) -> u32 {
+            reduce_montgomery(x as u64)
+        }
+
+        #[doc = concat!("Adds and reduces ", $title, " residues using the `%` operator")]
+        #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+        pub struct Modulo;
+
+        impl $crate::traits::PrimeField64 for Modulo {
+            const MODULUS: u64 = P as u64;
+            const NAME: &'static str = concat!($name, "/mod");
+
+            #[inline(always)]
+            fn add(x: u64, y: u64) -> u64 {
+                add_modulo(x as u32, y as u32) as u64
+            }
+
+            #[inline(always)]
+            fn reduce(x: u128) -> u64 {
+                (x % P as u128) as u64
+            }
+        }
+
+        #[doc = concat!(
+            "Adds ", $title, " residues under a mask and reduces using `reduce_barrett`"
+        )]
+        #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+        pub struct Barrett;
+
+        impl $crate::traits::PrimeField64 for Barrett {
+            const MODULUS: u64 = P as u64;
+            const NAME: &'static str = concat!($name, "/barrett");
+
+            #[inline(always)]
+            fn add(x: u64, y: u64) -> u64 {
+                add(x as u32, y as u32) as u64
+            }
+
+            #[inline(always)]
+            fn sub(x: u64, y: u64) -> u64 {
+                sub(x as u32, y as u32) as u64
+            }
+
+            #[inline(always)]
+            fn mul(x: u64, y: u64) -> u64 {
+                mul_barrett(x as u32, y as u32) as u64
+            }
+
+            #[inline(always)]
+            fn reduce(x: u128) -> u64 {
+                debug_assert!(x >> 64 == 0, "input must fit in 64 bits");
+                reduce_barrett(x as u64) as u64
+            }
+        }
+
+        #[doc = concat!(
+            "Stores ", $title, " residues in Montgomery form and reduces using `reduce_montgomery`"
+        )]
+        #[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+        pub struct Montgomery;
+
+        impl $crate::traits::PrimeField64 for Montgomery {
+            const MODULUS: u64 = P as u64;
+            const NAME: &'static str = concat!($name, "/montgomery");
+
+            #[inline(always)]
+            fn add(x: u64, y: u64) -> u64 {
+                add(x as u32, y as u32) as u64
+            }
+
+            #[inline(always)]
+            fn sub(x: u64, y: u64) -> u64 {
+                sub(x as u32, y as u32) as u64
+            }
+
+            #[inline(always)]
+            fn mul(x: u64, y: u64) -> u64 {
+                mul_montgomery(x as u32, y as u32) as u64
+            }
+
+            #[inline(always)]
+            fn reduce(x: u128) -> u64 {
+                debug_assert!(x < (P as u128) << 32, "input must be below p * 2^32");
+                reduce_montgomery(x as u64) as u64
+            }
+        }
+
+        $crate::meta::impl_meta!(META; Modulo, Barrett, Montgomery);
+
+        #[cfg(all(test, feature = "std"))]
+        mod strategy_tests {
+            use itertools::Itertools;
+
+            use super::*;
+            use $crate::traits::PrimeField64;
+
+            fn random_elements(n: usize) -> Vec<u32> {
+                Modulo::random(n).into_iter().map(|x| x as u32).collect()
+            }
+
+            #[test]
+            fn arithmetic() {
+                let edge_cases = [0, 1, P / 2, P / 2 + 1, P - 1, P - 1];
+                for (&x, &y) in random_elements(1_000)
+                    .iter()
+                    .chain(&edge_cases)
+                    .tuple_windows()
+                {
+                    let expected = mul_modulo(x, y);
+                    assert_eq!(add(x, y), add_modulo(x, y));
+                    assert_eq!(add(sub(x, y), y), x);
+                    assert_eq!(mul_barrett(x, y), expected);
+                    assert_eq!(from_montgomery(to_montgomery(x)), x);
+                    let product = mul_montgomery(to_montgomery(x), to_montgomery(y));
+                    assert_eq!(from_montgomery(product), expected);
+                }
+            }
+
+            #[test]
+            fn reductions() {
+                let max = (P as u64 - 1) * (P as u64 - 1);
+                for x in [0, 1, P as u64, max, u64::MAX] {
+                    assert_eq!(reduce_barrett(x) as u64, x % P as u64);
+                }
+                for x in [0, 1, P as u64, max, ((P as u64) << 32) - 1] {
+                    assert_eq!(to_montgomery(reduce_montgomery(x)) as u64, x % P as u64);
+                }
+            }
+
+            #[test]
+            fn strategies() {
+                fn check<F: PrimeField64>() {
+                    for (&x, &y) in F::random(1_000).iter().tuple_windows() {
+                        let sum = add_modulo(x as u32, y as u32) as u64;
+                        assert_eq!(F::add(x, y), sum);
+                        assert_eq!(F::sub(sum, y), x);
+                    }
+                }
+                check::<Modulo>();
+                check::<Barrett>();
+                check::<Montgomery>();
+
+                for (&x, &y) in Modulo::random(1_000).iter().tuple_windows() {
+                    let expected = mul_modulo(x as u32, y as u32) as u64;
+                    assert_eq!(Modulo::mul(x, y), expected);
+                    assert_eq!(Barrett::mul(x, y), expected);
+                    let (x_m, y_m) = (
+                        to_montgomery(x as u32) as u64,
+                        to_montgomery(y as u32) as u64,
+                    );
+                    assert_eq!(
+                        from_montgomery(Montgomery::mul(x_m, y_m) as u32) as u64,
+                        expected
+                    );
+                }
+            }
+        }
+    };
+}
+
+pub(crate) use field31_strategies;
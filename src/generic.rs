@@ -0,0 +1,456 @@
+//! Prime fields over an arbitrary 64-bit modulus
+
+use crate::meta::{ConstName, FieldMeta, Meta};
+use crate::reduce::{barrett_mu, montgomery_inv, montgomery_r2, mul_hi_u128};
+use crate::traits::PrimeField64;
+
+/// Computes the largest k such that 2^k divides p - 1
+pub const fn two_adicity(p: u64) -> u32 {
+    assert!(p > 2 && p % 2 == 1, "modulus must be an odd prime");
+    (p - 1).trailing_zeros()
+}
+
+/// Computes base^exp mod p by square-and-multiply
+const fn pow_mod(base: u64, exp: u64, p: u64) -> u64 {
+    let (mut base, mut exp, mut acc) = (base as u128 % p as u128, exp, 1u128);
+    while exp > 0 {
+        if exp & 1 == 1 {
+            acc = acc * base % p as u128;
+        }
+        base = base * base % p as u128;
+        exp >>= 1;
+    }
+    acc as u64
+}
+
+/// Checks that `g` generates the multiplicative group modulo the prime `p`
+///
+/// g^((p - 1) / q) must differ from 1 for every prime factor q of p - 1. The
+/// factors below 2^16 are found by trial division; whatever cofactor remains
+/// is tested as if prime, so a failure is conclusive but a pass is only
+/// certain when p - 1 is 2^16-smooth apart from one prime factor.
+pub const fn is_generator(g: u64, p: u64) -> bool {
+    if g.is_multiple_of(p) {
+        return false;
+    }
+    let (mut rest, mut q) = (p - 1, 2);
+    while q < 1 << 16 && q * q <= rest {
+        if rest.is_multiple_of(q) {
+            if pow_mod(g, (p - 1) / q, p) == 1 {
+                return false;
+            }
+            while rest.is_multiple_of(q) {
+                rest /= q;
+            }
+        }
+        q += 1;
+    }
+    rest == 1 || pow_mod(g, (p - 1) / rest, p) != 1
+}
+
+/// A prime field for an arbitrary odd 64-bit prime `P`
+///
+/// All reduction constants are computed at compile time, so no structure of
+/// `P` is exploited. Values are canonical; multiplication uses Barrett
+/// reduction, and Montgomery-form helpers are provided alongside.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct GenericPrimeField<const P: u64>;
+
+impl<const P: u64> GenericPrimeField<P> {
+    /// floor(2^128 / P)
+    pub const MU: u128 = barrett_mu(P);
+    /// P^-1 mod 2^64
+    pub const INV: u64 = montgomery_inv(P);
+    /// (2^64)^2 mod P
+    pub const R2: u64 = montgomery_r2(P);
+    /// The largest k such that 2^k divides P - 1
+    pub const TWO_ADICITY: u32 = two_adicity(P);
+
+    // the field is named by its modulus in hex, since P has no other name here
+    const FIELD_NAME: ConstName = ConstName::new("0x").push_radix(P, 16);
+    const STRATEGY_NAME: ConstName = Self::FIELD_NAME.push_str("/generic");
+
+    /// Subtracts two canonical values
+    #[inline(always)]
+    pub const fn sub(x: u64, y: u64) -> u64 {
+        let (diff, borrow) = x.overflowing_sub(y);
+        if borrow {
+            diff.wrapping_add(P)
+        } else {
+            diff
+        }
+    }
+
+    /// Reduces any `u128` modulo `P` using Barrett reduction
+    #[inline(always)]
+    pub const fn reduce_barrett(x: u128) -> u64 {
+        // q underestimates floor(x / P) by at most one
+        let q = mul_hi_u128(x, Self::MU);
+        let mut r = x - q * P as u128;
+        if r >= P as u128 {
+            r -= P as u128;
+        }
+        r as u64
+    }
+
+    /// Computes x * 2^-64 mod P for x < P * 2^64
+    #[inline(always)]
+    pub const fn reduce_montgomery(x: u128) -> u64 {
+        // m * P agrees with x in the low 64 bits, so only the high words differ
+        let m = (x as u64).wrapping_mul(Self::INV);
+        let mp = ((m as u128 * P as u128) >> 64) as u64;
+        let (r, borrow) = ((x >> 64) as u64).overflowing_sub(mp);
+        if borrow {
+            r.wrapping_add(P)
+        } else {
+            r
+        }
+    }
+
+    /// Converts a canonical value into Montgomery form
+    #[inline(always)]
+    pub const fn to_montgomery(x: u64) -> u64 {
+        Self::reduce_montgomery(x as u128 * Self::R2 as u128)
+    }
+
+    /// Converts a value in Montgomery form into a canonical value
+    #[inline(always)]
+    pub const fn from_montgomery(x: u64) -> u64 {
+        Self::reduce_montgomery(x as u128)
+    }
+
+    /// Multiplies two values in Montgomery form
+    #[inline(always)]
+    pub const fn mul_montgomery(x: u64, y: u64) -> u64 {
+        Self::reduce_montgomery(x as u128 * y as u128)
+    }
+}
+
+impl<const P: u64> PrimeField64 for GenericPrimeField<P> {
+    const MODULUS: u64 = P;
+    const NAME: &'static str = Self::STRATEGY_NAME.as_str();
+
+    #[inline(always)]
+    fn add(x: u64, y: u64) -> u64 {
+        let (sum, over) = x.overflowing_add(y);
+        if over || sum >= P {
+            sum.wrapping_sub(P)
+        } else {
+            sum
+        }
+    }
+
+    #[inline(always)]
+    fn sub(x: u64, y: u64) -> u64 {
+        GenericPrimeField::<P>::sub(x, y)
+    }
+
+    #[inline(always)]
+    fn reduce(x: u128) -> u64 {
+        Self::reduce_barrett(x)
+    }
+}
+
+/// No generator is known for an arbitrary `P`, since finding one needs the
+/// factors of P - 1; [`define_prime_field!`](crate::define_prime_field) takes
+/// it from the caller instead.
+impl<const P: u64> Meta for GenericPrimeField<P> {
+    const META: FieldMeta = FieldMeta::new(Self::FIELD_NAME.as_str(), &[P], None);
+}
+
+/// Declares an element type for the prime field modulo `$modulus`
+///
+/// The type is a canonical `u64` newtype built on [`GenericPrimeField`], with
+/// field operators, `pow` and `inverse`. It also implements [`PrimeField64`]
+/// under its own name, so it can be handed to any benchmark written against
+/// that trait.
+///
+/// ```
+/// prime_field::define_prime_field!(P64m59, 0xffff_ffff_ffff_ffc5, 2);
+///
+/// let x = P64m59::new(3);
+/// assert_eq!(x * x.inverse().unwrap(), P64m59::ONE);
+/// ```
+///
+/// The generator is checked with [`is_generator`] at compile time, so a
+/// wrong one does not build:
+///
+/// ```compile_fail
+/// // 2^16 = -1 modulo 65537, so 2 only has order 32
+/// prime_field::define_prime_field!(F65537, 65537, 2);
+/// ```
+///
+/// Led by a registry name, it declares several fields and a macro of that
+/// name listing them: `registry!(f)` is the array of `f::<Field>` for every
+/// field, coerced to function pointers, which a benchmark group loops over.
+///
+/// ```
+/// use prime_field::PrimeField64;
+///
+/// prime_field::define_prime_field! {
+///     candidates;
+///     P64m59 = (0xffff_ffff_ffff_ffc5, 2),
+///     P61 = ((1 << 61) - 1, 37),
+/// }
+///
+/// fn name<F: PrimeField64>() -> &'static str {
+///     F::NAME
+/// }
+///
+/// assert_eq!(candidates!(name).map(|name| name()), ["P64m59", "P61"]);
+/// ```
+#[macro_export]
+macro_rules! define_prime_field {
+    ($registry:ident; $($name:ident = ($modulus:expr, $generator:expr)),+ $(,)?) => {
+        $($crate::define_prime_field!($name, $modulus, $generator);)+
+        $crate::__prime_field_registry!(($) $registry; $($name),+);
+    };
+    ($name:ident, $modulus:expr, $generator:expr) => {
+        const _: () = assert!(
+            $crate::is_generator($generator, $modulus),
+            concat!("the generator of ", stringify!($name), " must have order p - 1")
+        );
+
+        /// Ordered by canonical residue, which is not compatible with the field operations
+        #[derive(Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        pub struct $name(u64);
+
+        // not every expansion uses the whole API
+        #[allow(dead_code)]
+        impl $name {
+            /// The prime modulus
+            pub const MODULUS: u64 = $modulus;
+            /// A generator of the multiplicative group
+            pub const GENERATOR: Self = Self::new($generator);
+            /// The additive identity
+            pub const ZERO: Self = Self(0);
+            /// The multiplicative identity
+            pub const ONE: Self = Self(1);
+
+            /// Creates an element from any `u64`, reducing it modulo `MODULUS` first
+            pub const fn new(x: u64) -> Self {
+                Self(x % Self::MODULUS)
+            }
+
+            /// Returns the canonical residue of this element
+            pub const fn value(self) -> u64 {
+                self.0
+            }
+
+            /// Raises this element to the power `exp` by square-and-multiply
+            pub fn pow(self, mut exp: u64) -> Self {
+                let mut base = self;
+                let mut acc = Self::ONE;
+                while exp > 0 {
+                    if exp & 1 == 1 {
+                        acc *= base;
+                    }
+                    base *= base;
+                    exp >>= 1;
+                }
+                acc
+            }
+
+            /// Returns the multiplicative inverse, or `None` for zero
+            pub fn inverse(self) -> Option<Self> {
+                if self == Self::ZERO {
+                    None
+                } else {
+                    Some(self.pow(Self::MODULUS - 2))
+                }
+            }
+        }
+
+        impl $crate::PrimeField64 for $name {
+            const MODULUS: u64 = $modulus;
+            const NAME: &'static str = stringify!($name);
+
+            #[inline(always)]
+            fn add(x: u64, y: u64) -> u64 {
+                <$crate::GenericPrimeField<{ $modulus }> as $crate::PrimeField64>::add(x, y)
+            }
+
+            #[inline(always)]
+            fn sub(x: u64, y: u64) -> u64 {
+                $crate::GenericPrimeField::<{ $modulus }>::sub(x, y)
+            }
+
+            #[inline(always)]
+            fn reduce(x: u128) -> u64 {
+                $crate::GenericPrimeField::<{ $modulus }>::reduce_barrett(x)
+            }
+        }
+
+        impl $crate::Meta for $name {
+            const META: $crate::FieldMeta =
+                $crate::FieldMeta::new(stringify!($name), &[$modulus], Some($generator));
+        }
+
+        impl From<u64> for $name {
+            fn from(x: u64) -> Self {
+                Self::new(x)
+            }
+        }
+
+        impl ::core::fmt::Debug for $name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                write!(f, "{}({})", stringify!($name), self.0)
+            }
+        }
+
+        impl ::core::ops::Add for $name {
+            type Output = Self;
+
+            #[inline(always)]
+            fn add(self, rhs: Self) -> Self {
+                Self(<Self as $crate::PrimeField64>::add(self.0, rhs.0))
+            }
+        }
+
+        impl ::core::ops::Sub for $name {
+            type Output = Self;
+
+            #[inline(always)]
+            fn sub(self, rhs: Self) -> Self {
+                Self(<Self as $crate::PrimeField64>::sub(self.0, rhs.0))
+            }
+        }
+
+        impl ::core::ops::Mul for $name {
+            type Output = Self;
+
+            #[inline(always)]
+            fn mul(self, rhs: Self) -> Self {
+                Self(<Self as $crate::PrimeField64>::mul(self.0, rhs.0))
+            }
+        }
+
+        impl ::core::ops::Neg for $name {
+            type Output = Self;
+
+            #[inline(always)]
+            fn neg(self) -> Self {
+                Self::ZERO - self
+            }
+        }
+
+        impl ::core::ops::AddAssign for $name {
+            #[inline(always)]
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl ::core::ops::SubAssign for $name {
+            #[inline(always)]
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl ::core::ops::MulAssign for $name {
+            #[inline(always)]
+            fn mul_assign(&mut self, rhs: Self) {
+                *self = *self * rhs;
+            }
+        }
+    };
+}
+
+/// Defines the registry macro of [`define_prime_field!`], taking `$` as a
+/// token so that the inner macro can declare its own metavariables
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __prime_field_registry {
+    (($d:tt) $registry:ident; $($name:ident),+) => {
+        /// Lists `$f::<Field>` for every field declared with this registry
+        #[allow(unused_macros)]
+        macro_rules! $registry {
+            ($d f:ident) => {
+                [$($d f::<$name>),+]
+            };
+        }
+    };
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+    use crate::goldilocks::{add_modulo, mul_modulo, P64};
+    use crate::rand::random_elements;
+
+    #[test]
+    fn generic_prime_field() {
+        fn check<const P: u64>(expected_two_adicity: u32) {
+            type F<const P: u64> = GenericPrimeField<P>;
+
+            assert_eq!(F::<P>::TWO_ADICITY, expected_two_adicity);
+            assert_eq!(P.wrapping_mul(F::<P>::INV), 1);
+
+            let operands = F::<P>::random(1_000);
+            let edge_cases = [0, 1, P - 1, P - 2];
+            for (&x, &y) in operands.iter().chain(&edge_cases).tuple_windows() {
+                let p = P as u128;
+                assert_eq!(F::<P>::add(x, y) as u128, (x as u128 + y as u128) % p);
+                assert_eq!(F::<P>::mul(x, y) as u128, (x as u128 * y as u128) % p);
+
+                let (xm, ym) = (F::<P>::to_montgomery(x), F::<P>::to_montgomery(y));
+                assert_eq!(F::<P>::from_montgomery(xm), x);
+                let product = F::<P>::from_montgomery(F::<P>::mul_montgomery(xm, ym));
+                assert_eq!(product as u128, (x as u128 * y as u128) % p);
+            }
+            assert_eq!(
+                F::<P>::reduce_barrett(u128::MAX) as u128,
+                u128::MAX % P as u128
+            );
+        }
+
+        check::<P64>(32);
+        check::<0xffff_ffff_ffff_ffc5>(2); // 2^64 - 59
+        check::<0x1fff_ffff_ffff_ffff>(1); // 2^61 - 1
+        check::<0x7800_0001>(27); // 2^31 - 2^27 + 1
+        check::<65537>(16);
+
+        assert_eq!(GenericPrimeField::<P64>::NAME, "0xffffffff00000001/generic");
+        assert_eq!(GenericPrimeField::<65537>::META.name, "0x10001");
+        assert_eq!(GenericPrimeField::<65537>::META.generator, None);
+    }
+
+    #[test]
+    fn generators() {
+        assert!(is_generator(7, P64));
+        assert!(!is_generator(2, P64));
+        assert!(is_generator(3, 65537));
+        assert!(!is_generator(2, 65537));
+        assert!(!is_generator(0, 65537));
+        assert!(is_generator(37, (1 << 61) - 1));
+    }
+
+    #[test]
+    fn define_prime_field() {
+        define_prime_field!(Goldilocks, P64, 7);
+        define_prime_field!(Small, 65537, 3);
+
+        assert_eq!(<Goldilocks as PrimeField64>::NAME, "Goldilocks");
+        for (&x, &y) in random_elements(1_000).iter().tuple_windows() {
+            let (a, b) = (Goldilocks::new(x), Goldilocks::new(y));
+            assert_eq!((a + b).value(), add_modulo(x, y));
+            assert_eq!((a * b).value(), mul_modulo(x, y));
+            assert_eq!(a - b + b, a);
+            assert_eq!(-a + a, Goldilocks::ZERO);
+        }
+
+        // a generator has order p - 1, and no smaller order dividing it
+        assert_eq!(Small::GENERATOR.pow(65536), Small::ONE);
+        assert_ne!(Small::GENERATOR.pow(32768), Small::ONE);
+        for q in [2, 3, 5, 17, 257, 65537] {
+            let power = Goldilocks::GENERATOR.pow((P64 - 1) / q);
+            assert_ne!(power, Goldilocks::ONE, "7^((p - 1) / {q})");
+        }
+        assert_eq!(Small::new(65537 + 5).value(), 5);
+        assert_eq!(Small::ZERO.inverse(), None);
+        assert_eq!(Small::new(2) * Small::new(2).inverse().unwrap(), Small::ONE);
+    }
+}
@@ -0,0 +1,132 @@
+//! aarch64 assembly with `mul`/`umulh` and conditional selects
+//!
+//! [`mul`] forms the two halves of the 128-bit product with `mul` and
+//! `umulh`, and [`reduce`] corrects each borrow or carry with a `csel` on the
+//! flags of the `subs` or `adds` that produced it. Both instructions are part
+//! of the base instruction set, so the [`Umulh`] strategy is always defined.
+
+use core::arch::asm;
+
+use crate::goldilocks::{add_with_sub_u128, sub_branchless, P64};
+use crate::traits::{PrimeField64, Reducer};
+
+/// Reduces hi * 2^64 + lo to its canonical residue
+///
+/// With hi = a * 2^32 + b, 2^96 ≡ -1 and 2^64 ≡ `EPSILON` give
+/// lo - a + b * `EPSILON`. On aarch64 the carry flag is set when a
+/// subtraction does not borrow, so a borrow selects on `cc` and a carry on `cs`.
+#[inline(always)]
+fn reduce_parts(hi: u64, lo: u64) -> u64 {
+    let mut r = lo;
+    // only registers and flags are touched
+    unsafe {
+        asm!(
+            "mov {eps}, #0xffffffff",
+            "lsr {a}, {hi}, #32",
+            "subs {r}, {r}, {a}",
+            "sub {t}, {r}, {eps}",
+            "csel {r}, {t}, {r}, cc",
+            "and {a}, {hi}, #0xffffffff",
+            "lsl {t}, {a}, #32",
+            "sub {t}, {t}, {a}",
+            "adds {r}, {r}, {t}",
+            "add {t}, {r}, {eps}",
+            "csel {r}, {t}, {r}, cs",
+            // r >= p exactly when r + EPSILON carries
+            "adds {t}, {r}, {eps}",
+            "csel {r}, {t}, {r}, cs",
+            hi = in(reg) hi,
+            r = inout(reg) r,
+            a = out(reg) _,
+            t = out(reg) _,
+            eps = out(reg) _,
+            options(pure, nomem, nostack),
+        );
+    }
+    r
+}
+
+/// Reduces a product of two field elements
+#[inline]
+pub fn reduce(x: u128) -> u64 {
+    reduce_parts((x >> 64) as u64, x as u64)
+}
+
+/// Multiplies two canonical residues with `mul` and `umulh`
+#[inline]
+pub fn mul(x: u64, y: u64) -> u64 {
+    let (hi, lo): (u64, u64);
+    // only registers are touched, and neither instruction sets flags
+    unsafe {
+        asm!(
+            "mul {lo}, {x}, {y}",
+            "umulh {hi}, {x}, {y}",
+            x = in(reg) x,
+            y = in(reg) y,
+            hi = out(reg) hi,
+            lo = out(reg) lo,
+            options(pure, nomem, nostack, preserves_flags),
+        );
+    }
+    reduce_parts(hi, lo)
+}
+
+/// Adds with `u128` subtraction and multiplies with the assembly [`mul`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Umulh;
+
+impl PrimeField64 for Umulh {
+    const MODULUS: u64 = P64;
+    const NAME: &'static str = "umulh";
+
+    #[inline(always)]
+    fn add(x: u64, y: u64) -> u64 {
+        add_with_sub_u128(x, y)
+    }
+
+    #[inline(always)]
+    fn sub(x: u64, y: u64) -> u64 {
+        sub_branchless(x, y)
+    }
+
+    #[inline(always)]
+    fn mul(x: u64, y: u64) -> u64 {
+        mul(x, y)
+    }
+
+    #[inline(always)]
+    fn reduce(x: u128) -> u64 {
+        reduce(x)
+    }
+}
+
+impl Reducer for Umulh {}
+
+crate::meta::impl_meta!(crate::goldilocks::constants::META; Umulh);
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::goldilocks::mul_modulo;
+    use crate::rand::random_elements;
+
+    #[test]
+    fn assembly_matches_reference() {
+        let edge_cases = [0, 1, 0xffff_ffff, 1 << 32, 1 << 63, P64 - 2, P64 - 1];
+        let pairs = edge_cases
+            .iter()
+            .flat_map(|&x| edge_cases.iter().map(move |&y| (x, y)))
+            .chain(
+                random_elements(1_000)
+                    .into_iter()
+                    .zip(random_elements(1_000)),
+            );
+        for (x, y) in pairs {
+            assert_eq!(mul(x, y), mul_modulo(x, y), "{x} * {y}");
+            assert_eq!(reduce(x as u128 * y as u128), mul_modulo(x, y));
+        }
+        for x in [u128::MAX, (P64 as u128) << 64, P64 as u128, u128::MAX >> 64] {
+            assert_eq!(reduce(x), (x % P64 as u128) as u64, "{x}");
+        }
+    }
+}
@@ -0,0 +1,20 @@
+//! Goldilocks multiplication in hand-written inline assembly
+//!
+//! Each architecture gets a strategy whose reduction is a fixed instruction
+//! sequence, to compare against what rustc generates for
+//! [`reduce_u64`](super::reduce_u64) from `u128` arithmetic. Only the module
+//! for the target architecture is compiled, and its items are re-exported here.
+
+#[cfg(target_arch = "aarch64")]
+mod aarch64;
+#[cfg(target_arch = "riscv64")]
+mod riscv64;
+#[cfg(target_arch = "x86_64")]
+mod x86_64;
+
+#[cfg(target_arch = "aarch64")]
+pub use aarch64::*;
+#[cfg(target_arch = "riscv64")]
+pub use riscv64::*;
+#[cfg(target_arch = "x86_64")]
+pub use x86_64::*;
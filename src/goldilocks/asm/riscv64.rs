@@ -0,0 +1,141 @@
+//! riscv64 assembly with `mul`/`mulhu` and `sltu` corrections
+//!
+//! [`mul`] forms the two halves of the 128-bit product with `mul` and
+//! `mulhu`. RISC-V has no flags, so [`reduce`] recovers each borrow or carry
+//! with `sltu` and widens it to `EPSILON` with `neg` and a shift, without
+//! branches or the Zicond extension. Both multiplies are in the M extension,
+//! which all of rustc's riscv64 targets include, so the [`Mulhu`] strategy is
+//! always defined.
+
+use core::arch::asm;
+
+use crate::goldilocks::{add_with_sub_u128, sub_branchless, P64};
+use crate::traits::{PrimeField64, Reducer};
+
+/// Reduces hi * 2^64 + lo to its canonical residue
+///
+/// With hi = a * 2^32 + b, 2^96 ≡ -1 and 2^64 ≡ `EPSILON` give
+/// lo - a + b * `EPSILON`. A borrow or carry of 1 becomes `EPSILON` as the
+/// high half of its negation.
+#[inline(always)]
+fn reduce_parts(hi: u64, lo: u64) -> u64 {
+    let mut r = lo;
+    // only registers are touched
+    unsafe {
+        asm!(
+            "srli {a}, {hi}, 32",
+            "sltu {c}, {r}, {a}",
+            "sub {r}, {r}, {a}",
+            "neg {c}, {c}",
+            "srli {c}, {c}, 32",
+            "sub {r}, {r}, {c}",
+            "slli {a}, {hi}, 32",
+            "srli {c}, {a}, 32",
+            "sub {a}, {a}, {c}",
+            "add {r}, {r}, {a}",
+            "sltu {c}, {r}, {a}",
+            "neg {c}, {c}",
+            "srli {c}, {c}, 32",
+            "add {r}, {r}, {c}",
+            // r >= p exactly when r + EPSILON carries
+            "li {a}, -1",
+            "srli {a}, {a}, 32",
+            "add {c}, {r}, {a}",
+            "sltu {c}, {c}, {r}",
+            "neg {c}, {c}",
+            "and {c}, {c}, {a}",
+            "add {r}, {r}, {c}",
+            hi = in(reg) hi,
+            r = inout(reg) r,
+            a = out(reg) _,
+            c = out(reg) _,
+            options(pure, nomem, nostack),
+        );
+    }
+    r
+}
+
+/// Reduces a product of two field elements
+#[inline]
+pub fn reduce(x: u128) -> u64 {
+    reduce_parts((x >> 64) as u64, x as u64)
+}
+
+/// Multiplies two canonical residues with `mul` and `mulhu`
+#[inline]
+pub fn mul(x: u64, y: u64) -> u64 {
+    let (hi, lo): (u64, u64);
+    // only registers are touched
+    unsafe {
+        asm!(
+            "mul {lo}, {x}, {y}",
+            "mulhu {hi}, {x}, {y}",
+            x = in(reg) x,
+            y = in(reg) y,
+            hi = out(reg) hi,
+            lo = out(reg) lo,
+            options(pure, nomem, nostack),
+        );
+    }
+    reduce_parts(hi, lo)
+}
+
+/// Adds with `u128` subtraction and multiplies with the assembly [`mul`]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Mulhu;
+
+impl PrimeField64 for Mulhu {
+    const MODULUS: u64 = P64;
+    const NAME: &'static str = "mulhu";
+
+    #[inline(always)]
+    fn add(x: u64, y: u64) -> u64 {
+        add_with_sub_u128(x, y)
+    }
+
+    #[inline(always)]
+    fn sub(x: u64, y: u64) -> u64 {
+        sub_branchless(x, y)
+    }
+
+    #[inline(always)]
+    fn mul(x: u64, y: u64) -> u64 {
+        mul(x, y)
+    }
+
+    #[inline(always)]
+    fn reduce(x: u128) -> u64 {
+        reduce(x)
+    }
+}
+
+impl Reducer for Mulhu {}
+
+crate::meta::impl_meta!(crate::goldilocks::constants::META; Mulhu);
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::goldilocks::mul_modulo;
+    use crate::rand::random_elements;
+
+    #[test]
+    fn assembly_matches_reference() {
+        let edge_cases = [0, 1, 0xffff_ffff, 1 << 32, 1 << 63, P64 - 2, P64 - 1];
+        let pairs = edge_cases
+            .iter()
+            .flat_map(|&x| edge_cases.iter().map(move |&y| (x, y)))
+            .chain(
+                random_elements(1_000)
+                    .into_iter()
+                    .zip(random_elements(1_000)),
+            );
+        for (x, y) in pairs {
+            assert_eq!(mul(x, y), mul_modulo(x, y), "{x} * {y}");
+            assert_eq!(reduce(x as u128 * y as u128), mul_modulo(x, y));
+        }
+        for x in [u128::MAX, (P64 as u128) << 64, P64 as u128, u128::MAX >> 64] {
+            assert_eq!(reduce(x), (x % P64 as u128) as u64, "{x}");
+        }
+    }
+}
@@ -0,0 +1,274 @@
+//! x86_64 assembly with BMI2 and ADX
+//!
+//! [`mul`] forms the 128-bit product with `mulx` and reduces it in a fixed
+//! sequence of `sub`/`sbb`/`add`/`cmovc` instructions. [`sum_of_products`]
+//! keeps two 192-bit accumulators whose carry chains run on separate flags
+//! with `adcx` and `adox`, so consecutive products do not wait on each
+//! other's carries.
+//!
+//! The operations carry `#[target_feature(enable = "bmi2,adx")]` as in
+//! [`avx2`](crate::goldilocks::avx2). The [`MulxAdx`] strategy exists only
+//! when both features are enabled at compile time, for example with
+//! `-C target-cpu=native`, so that its operations inline into generic code.
+
+use core::arch::asm;
+
+#[cfg(all(target_feature = "bmi2", target_feature = "adx"))]
+use crate::goldilocks::{add_with_sub_u128, sub_branchless, P64};
+use crate::goldilocks::{reduce_u64, sub_winterfell};
+#[cfg(all(target_feature = "bmi2", target_feature = "adx"))]
+use crate::traits::{PrimeField64, Reducer};
+
+/// Returns whether BMI2 and ADX are enabled at compile time or, with `std`, detected at runtime
+#[inline]
+pub fn is_available() -> bool {
+    #[cfg(all(target_feature = "bmi2", target_feature = "adx"))]
+    {
+        true
+    }
+    #[cfg(all(
+        not(all(target_feature = "bmi2", target_feature = "adx")),
+        feature = "std"
+    ))]
+    {
+        std::is_x86_feature_detected!("bmi2") && std::is_x86_feature_detected!("adx")
+    }
+    #[cfg(all(
+        not(all(target_feature = "bmi2", target_feature = "adx")),
+        not(feature = "std")
+    ))]
+    {
+        false
+    }
+}
+
+/// Reduces hi * 2^64 + lo to its canonical residue
+///
+/// With hi = a * 2^32 + b, 2^96 ≡ -1 and 2^64 ≡ `EPSILON` give
+/// lo - a + b * `EPSILON`. A 32-bit `sbb` of a register with itself turns a
+/// borrow or carry directly into the `EPSILON` that corrects it.
+#[inline(always)]
+fn reduce_parts(hi: u64, lo: u64) -> u64 {
+    let mut r = lo;
+    // only registers and flags are touched
+    unsafe {
+        asm!(
+            "mov {a}, {hi}",
+            "shr {a}, 32",
+            "mov {b:e}, {hi:e}",
+            "sub {r}, {a}",
+            "sbb {m:e}, {m:e}",
+            "sub {r}, {m}",
+            "mov {a}, {b}",
+            "shl {a}, 32",
+            "sub {a}, {b}",
+            "add {r}, {a}",
+            "sbb {m:e}, {m:e}",
+            "add {r}, {m}",
+            // r >= p exactly when r + EPSILON carries
+            "mov {m:e}, 0xffffffff",
+            "mov {a}, {r}",
+            "add {a}, {m}",
+            "cmovc {r}, {a}",
+            hi = in(reg) hi,
+            r = inout(reg) r,
+            a = out(reg) _,
+            b = out(reg) _,
+            m = out(reg) _,
+            options(pure, nomem, nostack),
+        );
+    }
+    r
+}
+
+/// Reduces a product of two field elements
+///
+/// # Safety
+///
+/// Outside BMI2 and ADX code, the caller must ensure the CPU supports both,
+/// for example with [`is_available`].
+#[inline]
+#[target_feature(enable = "bmi2,adx")]
+pub fn reduce(x: u128) -> u64 {
+    reduce_parts((x >> 64) as u64, x as u64)
+}
+
+/// Multiplies two canonical residues with `mulx`
+///
+/// # Safety
+///
+/// Outside BMI2 and ADX code, the caller must ensure the CPU supports both,
+/// for example with [`is_available`].
+#[inline]
+#[target_feature(enable = "bmi2,adx")]
+pub fn mul(x: u64, y: u64) -> u64 {
+    let (hi, lo): (u64, u64);
+    // mulx reads rdx and writes two registers, leaving the flags alone
+    unsafe {
+        asm!(
+            "mulx {hi}, {lo}, {y}",
+            in("rdx") x,
+            y = in(reg) y,
+            hi = out(reg) hi,
+            lo = out(reg) lo,
+            options(pure, nomem, nostack, preserves_flags),
+        );
+    }
+    reduce_parts(hi, lo)
+}
+
+/// Reduces lo + overflows * 2^128, with 2^128 ≡ -2^32
+#[inline(always)]
+fn reduce_wide(lo: u128, overflows: u64) -> u64 {
+    sub_winterfell(reduce_u64(lo), reduce_u64((overflows as u128) << 32))
+}
+
+/// Computes the sum of the products a[i] * b[i] with a single final reduction
+///
+/// Pairs of products are added into two 192-bit accumulators, the even ones
+/// along the carry flag with `adcx` and the odd ones along the overflow flag
+/// with `adox`. Without BMI2 and ADX this is
+/// [`sum_of_products`](crate::goldilocks::sum_of_products).
+///
+/// # Panics
+///
+/// Panics if `a` and `b` differ in length.
+pub fn sum_of_products(a: &[u64], b: &[u64]) -> u64 {
+    #[target_feature(enable = "bmi2,adx")]
+    fn dual_chain(a: &[u64], b: &[u64]) -> u64 {
+        let [mut lo0, mut hi0, mut top0, mut lo1, mut hi1, mut top1] = [0u64; 6];
+        let (a_pairs, b_pairs) = (a.chunks_exact(2), b.chunks_exact(2));
+        let (a_rest, b_rest) = (a_pairs.remainder(), b_pairs.remainder());
+        for (x, y) in a_pairs.zip(b_pairs) {
+            // each pair is two readable elements of each slice
+            unsafe {
+                asm!(
+                    // clears both the carry and the overflow flag
+                    "xor {zero:e}, {zero:e}",
+                    "mov rdx, [{x}]",
+                    "mulx {ph}, {pl}, [{y}]",
+                    "adcx {lo0}, {pl}",
+                    "adcx {hi0}, {ph}",
+                    // mulx and adox leave the carry flag for the next adcx
+                    "mov rdx, [{x} + 8]",
+                    "mulx {ph}, {pl}, [{y} + 8]",
+                    "adox {lo1}, {pl}",
+                    "adox {hi1}, {ph}",
+                    "adcx {top0}, {zero}",
+                    "adox {top1}, {zero}",
+                    x = in(reg) x.as_ptr(),
+                    y = in(reg) y.as_ptr(),
+                    lo0 = inout(reg) lo0,
+                    hi0 = inout(reg) hi0,
+                    top0 = inout(reg) top0,
+                    lo1 = inout(reg) lo1,
+                    hi1 = inout(reg) hi1,
+                    top1 = inout(reg) top1,
+                    zero = out(reg) _,
+                    pl = out(reg) _,
+                    ph = out(reg) _,
+                    out("rdx") _,
+                    options(pure, readonly, nostack),
+                );
+            }
+        }
+
+        let (sum, carry) =
+            ((hi0 as u128) << 64 | lo0 as u128).overflowing_add((hi1 as u128) << 64 | lo1 as u128);
+        let (sum, rest_carry) = match (a_rest, b_rest) {
+            (&[x], &[y]) => sum.overflowing_add(x as u128 * y as u128),
+            _ => (sum, false),
+        };
+        reduce_wide(sum, top0 + top1 + carry as u64 + rest_carry as u64)
+    }
+
+    assert_eq!(a.len(), b.len(), "operands differ in length");
+    if is_available() {
+        // the target features were detected just above
+        unsafe { dual_chain(a, b) }
+    } else {
+        crate::goldilocks::sum_of_products(a, b)
+    }
+}
+
+/// Adds with `u128` subtraction and multiplies with the assembly [`mul`]
+///
+/// Only defined when BMI2 and ADX are enabled at compile time.
+#[cfg(all(target_feature = "bmi2", target_feature = "adx"))]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct MulxAdx;
+
+// BMI2 and ADX are enabled for the whole build wherever this impl exists
+#[cfg(all(target_feature = "bmi2", target_feature = "adx"))]
+impl PrimeField64 for MulxAdx {
+    const MODULUS: u64 = P64;
+    const NAME: &'static str = "mulx_adx";
+
+    #[inline(always)]
+    fn add(x: u64, y: u64) -> u64 {
+        add_with_sub_u128(x, y)
+    }
+
+    #[inline(always)]
+    fn sub(x: u64, y: u64) -> u64 {
+        sub_branchless(x, y)
+    }
+
+    #[inline(always)]
+    fn mul(x: u64, y: u64) -> u64 {
+        unsafe { mul(x, y) }
+    }
+
+    #[inline(always)]
+    fn reduce(x: u128) -> u64 {
+        unsafe { reduce(x) }
+    }
+}
+
+#[cfg(all(target_feature = "bmi2", target_feature = "adx"))]
+impl Reducer for MulxAdx {}
+
+#[cfg(all(target_feature = "bmi2", target_feature = "adx"))]
+crate::meta::impl_meta!(crate::goldilocks::constants::META; MulxAdx);
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::goldilocks::{mul_modulo, P64};
+    use crate::rand::{random_elements, random_elements_exact};
+
+    #[test]
+    fn assembly_matches_reference() {
+        if !is_available() {
+            return;
+        }
+        let edge_cases = [0, 1, 0xffff_ffff, 1 << 32, 1 << 63, P64 - 2, P64 - 1];
+        let pairs = edge_cases
+            .iter()
+            .flat_map(|&x| edge_cases.iter().map(move |&y| (x, y)))
+            .chain(
+                random_elements(1_000)
+                    .into_iter()
+                    .zip(random_elements(1_000)),
+            );
+        for (x, y) in pairs {
+            // the features were detected above
+            unsafe {
+                assert_eq!(mul(x, y), mul_modulo(x, y), "{x} * {y}");
+                assert_eq!(reduce(x as u128 * y as u128), mul_modulo(x, y));
+            }
+        }
+        for x in [u128::MAX, (P64 as u128) << 64, P64 as u128, u128::MAX >> 64] {
+            assert_eq!(unsafe { reduce(x) }, (x % P64 as u128) as u64, "{x}");
+        }
+
+        for len in [0, 1, 2, 7, 1_000] {
+            let a = [&random_elements_exact(len)[..], &[P64 - 1; 9]].concat();
+            let b = [&random_elements_exact(len)[..], &[P64 - 1; 9]].concat();
+            assert_eq!(
+                sum_of_products(&a, &b),
+                crate::goldilocks::sum_of_products(&a, &b)
+            );
+        }
+    }
+}
@@ -0,0 +1,264 @@
+//! Four-lane packed arithmetic on canonical `P64` residues with AVX2
+//!
+//! The lane operations carry `#[target_feature(enable = "avx2")]`, so they can
+//! be called without `unsafe` only from other AVX2 code. The slice kernels are
+//! safe everywhere: each call checks [`is_available`] and falls back to scalar
+//! code without AVX2.
+//!
+//! AVX2 has neither unsigned 64-bit comparisons nor a 64-bit multiply, so
+//! comparisons flip the sign bit of both sides to reuse the signed one, and
+//! products are assembled from four 32 x 32-bit `_mm256_mul_epu32` products.
+
+use core::arch::x86_64::*;
+
+#[cfg(target_feature = "avx2")]
+use super::packed::PackedField;
+use super::slice::packed_slice_kernel;
+use super::{add_winterfell, reduce_u64, sub_winterfell, P64};
+
+/// The number of residues in a packed vector
+pub const LANES: usize = 4;
+
+/// 2^64 - `P64` = 2^32 - 1, which is also 2^64 mod p
+const EPSILON: u64 = 0xffff_ffff;
+
+/// Returns whether AVX2 is enabled at compile time or, with `std`, detected at runtime
+#[inline]
+pub fn is_available() -> bool {
+    #[cfg(target_feature = "avx2")]
+    {
+        true
+    }
+    #[cfg(all(not(target_feature = "avx2"), feature = "std"))]
+    {
+        std::is_x86_feature_detected!("avx2")
+    }
+    #[cfg(all(not(target_feature = "avx2"), not(feature = "std")))]
+    {
+        false
+    }
+}
+
+/// Returns all-ones lanes where x > y as unsigned integers
+#[inline]
+#[target_feature(enable = "avx2")]
+fn cmpgt_u64(x: __m256i, y: __m256i) -> __m256i {
+    let sign = _mm256_set1_epi64x(i64::MIN);
+    _mm256_cmpgt_epi64(_mm256_xor_si256(x, sign), _mm256_xor_si256(y, sign))
+}
+
+/// Narrows all-ones mask lanes to `EPSILON`
+#[inline]
+#[target_feature(enable = "avx2")]
+fn epsilon_where(mask: __m256i) -> __m256i {
+    _mm256_srli_epi64::<32>(mask)
+}
+
+/// Adds canonical lanes
+///
+/// A sum that wraps past 2^64 is short by 2^64 ≡ `EPSILON`, and one at or
+/// above p is reduced by wrapping it the same way, so both add `EPSILON`.
+///
+/// # Safety
+///
+/// Outside AVX2 code, the caller must ensure the CPU supports AVX2, for
+/// example with [`is_available`].
+#[inline]
+#[target_feature(enable = "avx2")]
+pub fn add(x: __m256i, y: __m256i) -> __m256i {
+    let sum = _mm256_add_epi64(x, y);
+    let carry = cmpgt_u64(x, sum);
+    let above = cmpgt_u64(sum, _mm256_set1_epi64x((P64 - 1) as i64));
+    _mm256_add_epi64(sum, epsilon_where(_mm256_or_si256(carry, above)))
+}
+
+/// Subtracts canonical lanes, taking `EPSILON` off the lanes that borrow
+///
+/// # Safety
+///
+/// Outside AVX2 code, the caller must ensure the CPU supports AVX2, for
+/// example with [`is_available`].
+#[inline]
+#[target_feature(enable = "avx2")]
+pub fn sub(x: __m256i, y: __m256i) -> __m256i {
+    let diff = _mm256_sub_epi64(x, y);
+    let borrow = cmpgt_u64(y, x);
+    _mm256_sub_epi64(diff, epsilon_where(borrow))
+}
+
+/// Computes the full 128-bit products of the lanes, as high and low halves
+#[inline]
+#[target_feature(enable = "avx2")]
+fn mul_wide(x: __m256i, y: __m256i) -> (__m256i, __m256i) {
+    let x_hi = _mm256_srli_epi64::<32>(x);
+    let y_hi = _mm256_srli_epi64::<32>(y);
+    let lo_lo = _mm256_mul_epu32(x, y);
+    let lo_hi = _mm256_mul_epu32(x, y_hi);
+    let hi_lo = _mm256_mul_epu32(x_hi, y);
+    let hi_hi = _mm256_mul_epu32(x_hi, y_hi);
+
+    // each partial sum stays below 2^64, as in schoolbook multiplication
+    let mask = _mm256_set1_epi64x(EPSILON as i64);
+    let t = _mm256_add_epi64(hi_lo, _mm256_srli_epi64::<32>(lo_lo));
+    let u = _mm256_add_epi64(lo_hi, _mm256_and_si256(t, mask));
+    let lo = _mm256_or_si256(_mm256_slli_epi64::<32>(u), _mm256_and_si256(lo_lo, mask));
+    let hi = _mm256_add_epi64(
+        hi_hi,
+        _mm256_add_epi64(_mm256_srli_epi64::<32>(t), _mm256_srli_epi64::<32>(u)),
+    );
+    (hi, lo)
+}
+
+/// Reduces 128-bit lanes to canonical residues
+///
+/// With hi = a * 2^32 + b, 2^96 ≡ -1 and 2^64 ≡ `EPSILON` give
+/// lo - a + b * `EPSILON`, each step correcting a borrow or carry by `EPSILON`.
+#[inline]
+#[target_feature(enable = "avx2")]
+fn reduce(hi: __m256i, lo: __m256i) -> __m256i {
+    let hi_hi = _mm256_srli_epi64::<32>(hi);
+    let t = _mm256_sub_epi64(lo, hi_hi);
+    let t = _mm256_sub_epi64(t, epsilon_where(cmpgt_u64(hi_hi, lo)));
+
+    let hi_lo = _mm256_and_si256(hi, _mm256_set1_epi64x(EPSILON as i64));
+    let product = _mm256_sub_epi64(_mm256_slli_epi64::<32>(hi_lo), hi_lo);
+    let sum = _mm256_add_epi64(t, product);
+    let sum = _mm256_add_epi64(sum, epsilon_where(cmpgt_u64(t, sum)));
+
+    let above = cmpgt_u64(sum, _mm256_set1_epi64x((P64 - 1) as i64));
+    _mm256_add_epi64(sum, epsilon_where(above))
+}
+
+/// Multiplies canonical lanes
+///
+/// # Safety
+///
+/// Outside AVX2 code, the caller must ensure the CPU supports AVX2, for
+/// example with [`is_available`].
+#[inline]
+#[target_feature(enable = "avx2")]
+pub fn mul(x: __m256i, y: __m256i) -> __m256i {
+    let (hi, lo) = mul_wide(x, y);
+    reduce(hi, lo)
+}
+
+packed_slice_kernel!(
+    /// Writes the elementwise sums a[i] + b[i] into `dst`, four lanes at a time
+    add_slices,
+    "avx2",
+    is_available(),
+    __m256i,
+    _mm256_loadu_si256,
+    _mm256_storeu_si256,
+    add,
+    add_winterfell
+);
+
+packed_slice_kernel!(
+    /// Writes the elementwise differences a[i] - b[i] into `dst`, four lanes at a time
+    sub_slices,
+    "avx2",
+    is_available(),
+    __m256i,
+    _mm256_loadu_si256,
+    _mm256_storeu_si256,
+    sub,
+    sub_winterfell
+);
+
+packed_slice_kernel!(
+    /// Writes the elementwise products a[i] * b[i] into `dst`, four lanes at a time
+    mul_slices,
+    "avx2",
+    is_available(),
+    __m256i,
+    _mm256_loadu_si256,
+    _mm256_storeu_si256,
+    mul,
+    |x: u64, y: u64| reduce_u64(x as u128 * y as u128)
+);
+
+/// Four packed residues, implementing [`PackedField`] when AVX2 is enabled at compile time
+#[cfg(target_feature = "avx2")]
+#[derive(Clone, Copy, Debug)]
+pub struct PackedAvx2(pub __m256i);
+
+// AVX2 is enabled for the whole build wherever this impl exists
+#[cfg(target_feature = "avx2")]
+impl PackedField for PackedAvx2 {
+    const WIDTH: usize = LANES;
+    const NAME: &'static str = "avx2";
+
+    #[inline(always)]
+    fn from_slice(values: &[u64]) -> Self {
+        let values = &values[..LANES];
+        Self(unsafe { _mm256_loadu_si256(values.as_ptr().cast()) })
+    }
+
+    #[inline(always)]
+    fn write_to_slice(self, out: &mut [u64]) {
+        let out = &mut out[..LANES];
+        unsafe { _mm256_storeu_si256(out.as_mut_ptr().cast(), self.0) }
+    }
+
+    #[inline(always)]
+    fn broadcast(x: u64) -> Self {
+        Self(unsafe { _mm256_set1_epi64x(x as i64) })
+    }
+
+    #[inline(always)]
+    fn add(self, rhs: Self) -> Self {
+        Self(unsafe { add(self.0, rhs.0) })
+    }
+
+    #[inline(always)]
+    fn sub(self, rhs: Self) -> Self {
+        Self(unsafe { sub(self.0, rhs.0) })
+    }
+
+    #[inline(always)]
+    fn mul(self, rhs: Self) -> Self {
+        Self(unsafe { mul(self.0, rhs.0) })
+    }
+
+    #[inline(always)]
+    fn horizontal_sum(self) -> u64 {
+        let mut lanes = [0; LANES];
+        self.write_to_slice(&mut lanes);
+        super::sum(&lanes)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::goldilocks::{add_modulo, mul_modulo, sub_modulo};
+    use crate::rand::random_elements_exact;
+
+    #[test]
+    fn packed_kernels() {
+        let edge_cases = [0, 1, EPSILON, EPSILON + 1, 1 << 63, P64 - 2, P64 - 1];
+        let (edge_a, edge_b): (Vec<_>, Vec<_>) = edge_cases
+            .iter()
+            .flat_map(|&x| edge_cases.iter().map(move |&y| (x, y)))
+            .unzip();
+        for len in [0, 3, 4, 1_001] {
+            let a = [&random_elements_exact(len)[..], &edge_a].concat();
+            let b = [&random_elements_exact(len)[..], &edge_b].concat();
+            let mut dst = vec![0; a.len()];
+
+            add_slices(&mut dst, &a, &b);
+            for ((&d, &x), &y) in dst.iter().zip(&a).zip(&b) {
+                assert_eq!(d, add_modulo(x, y));
+            }
+            sub_slices(&mut dst, &a, &b);
+            for ((&d, &x), &y) in dst.iter().zip(&a).zip(&b) {
+                assert_eq!(d, sub_modulo(x, y));
+            }
+            mul_slices(&mut dst, &a, &b);
+            for ((&d, &x), &y) in dst.iter().zip(&a).zip(&b) {
+                assert_eq!(d, mul_modulo(x, y), "{x} * {y}");
+            }
+        }
+    }
+}
@@ -0,0 +1,357 @@
+//! Eight-lane packed arithmetic on canonical `P64` residues with AVX-512
+//!
+//! Laid out like [`super::avx2`], with twice the lanes. AVX-512F compares
+//! unsigned 64-bit lanes into mask registers directly, so each carry, borrow
+//! or canonicalization is one masked add or subtract of `EPSILON`.
+//!
+//! Products come in two variants. [`mul`] assembles them from four 32 x 32-bit
+//! `_mm512_mul_epu32` products as in AVX2. [`mul_ifma`] splits each operand at
+//! bit 52 and uses the AVX-512 IFMA52 multiply-adds, which take seven
+//! instructions per 128-bit product but need fewer shifts and adds to combine.
+
+use core::arch::x86_64::*;
+
+#[cfg(target_feature = "avx512f")]
+use super::packed::PackedField;
+use super::slice::packed_slice_kernel;
+use super::{add_winterfell, reduce_u64, sub_winterfell, P64};
+
+/// The number of residues in a packed vector
+pub const LANES: usize = 8;
+
+/// 2^64 - `P64` = 2^32 - 1, which is also 2^64 mod p
+const EPSILON: u64 = 0xffff_ffff;
+
+/// The low 52 bits, the operand width of the IFMA52 multiply-adds
+const MASK52: u64 = (1 << 52) - 1;
+
+/// Returns whether AVX-512F is enabled at compile time or, with `std`, detected at runtime
+#[inline]
+pub fn is_available() -> bool {
+    #[cfg(target_feature = "avx512f")]
+    {
+        true
+    }
+    #[cfg(all(not(target_feature = "avx512f"), feature = "std"))]
+    {
+        std::is_x86_feature_detected!("avx512f")
+    }
+    #[cfg(all(not(target_feature = "avx512f"), not(feature = "std")))]
+    {
+        false
+    }
+}
+
+/// Returns whether AVX-512F and AVX-512 IFMA are both available, as with [`is_available`]
+#[inline]
+pub fn is_ifma_available() -> bool {
+    #[cfg(target_feature = "avx512ifma")]
+    {
+        is_available()
+    }
+    #[cfg(all(not(target_feature = "avx512ifma"), feature = "std"))]
+    {
+        is_available() && std::is_x86_feature_detected!("avx512ifma")
+    }
+    #[cfg(all(not(target_feature = "avx512ifma"), not(feature = "std")))]
+    {
+        false
+    }
+}
+
+#[inline]
+#[target_feature(enable = "avx512f")]
+fn epsilon() -> __m512i {
+    _mm512_set1_epi64(EPSILON as i64)
+}
+
+/// Adds canonical lanes
+///
+/// A sum that wraps past 2^64 is short by 2^64 ≡ `EPSILON`, and one at or
+/// above p is reduced by wrapping it the same way, so both add `EPSILON`.
+///
+/// # Safety
+///
+/// Outside AVX-512F code, the caller must ensure the CPU supports AVX-512F,
+/// for example with [`is_available`].
+#[inline]
+#[target_feature(enable = "avx512f")]
+pub fn add(x: __m512i, y: __m512i) -> __m512i {
+    let sum = _mm512_add_epi64(x, y);
+    let carry = _mm512_cmplt_epu64_mask(sum, x);
+    let above = _mm512_cmpge_epu64_mask(sum, _mm512_set1_epi64(P64 as i64));
+    _mm512_mask_add_epi64(sum, carry | above, sum, epsilon())
+}
+
+/// Subtracts canonical lanes, taking `EPSILON` off the lanes that borrow
+///
+/// # Safety
+///
+/// Outside AVX-512F code, the caller must ensure the CPU supports AVX-512F,
+/// for example with [`is_available`].
+#[inline]
+#[target_feature(enable = "avx512f")]
+pub fn sub(x: __m512i, y: __m512i) -> __m512i {
+    let diff = _mm512_sub_epi64(x, y);
+    let borrow = _mm512_cmplt_epu64_mask(x, y);
+    _mm512_mask_sub_epi64(diff, borrow, diff, epsilon())
+}
+
+/// Computes the full 128-bit products of the lanes, as high and low halves
+#[inline]
+#[target_feature(enable = "avx512f")]
+fn mul_wide(x: __m512i, y: __m512i) -> (__m512i, __m512i) {
+    let x_hi = _mm512_srli_epi64::<32>(x);
+    let y_hi = _mm512_srli_epi64::<32>(y);
+    let lo_lo = _mm512_mul_epu32(x, y);
+    let lo_hi = _mm512_mul_epu32(x, y_hi);
+    let hi_lo = _mm512_mul_epu32(x_hi, y);
+    let hi_hi = _mm512_mul_epu32(x_hi, y_hi);
+
+    // each partial sum stays below 2^64, as in schoolbook multiplication
+    let t = _mm512_add_epi64(hi_lo, _mm512_srli_epi64::<32>(lo_lo));
+    let u = _mm512_add_epi64(lo_hi, _mm512_and_si512(t, epsilon()));
+    let lo = _mm512_or_si512(
+        _mm512_slli_epi64::<32>(u),
+        _mm512_and_si512(lo_lo, epsilon()),
+    );
+    let hi = _mm512_add_epi64(
+        hi_hi,
+        _mm512_add_epi64(_mm512_srli_epi64::<32>(t), _mm512_srli_epi64::<32>(u)),
+    );
+    (hi, lo)
+}
+
+/// Computes the full 128-bit products of the lanes with IFMA52
+///
+/// With x = x1 * 2^52 + x0 and y likewise, the product is
+/// lo + mid * 2^52 + top * 2^104, where lo holds the low half of x0 * y0, mid
+/// its high half plus the low halves of the cross terms, and top the high
+/// halves of the cross terms plus x1 * y1 < 2^24. Every multiply-add stays
+/// below 2^52 per operand, and the sums below 2^64.
+#[inline]
+#[target_feature(enable = "avx512f,avx512ifma")]
+fn mul_wide_ifma(x: __m512i, y: __m512i) -> (__m512i, __m512i) {
+    let mask = _mm512_set1_epi64(MASK52 as i64);
+    let (x0, x1) = (_mm512_and_si512(x, mask), _mm512_srli_epi64::<52>(x));
+    let (y0, y1) = (_mm512_and_si512(y, mask), _mm512_srli_epi64::<52>(y));
+    let zero = _mm512_setzero_si512();
+
+    let lo = _mm512_madd52lo_epu64(zero, x0, y0);
+    let mid = _mm512_madd52hi_epu64(zero, x0, y0);
+    let mid = _mm512_madd52lo_epu64(mid, x0, y1);
+    let mid = _mm512_madd52lo_epu64(mid, x1, y0);
+    let top = _mm512_madd52hi_epu64(zero, x0, y1);
+    let top = _mm512_madd52hi_epu64(top, x1, y0);
+    let top = _mm512_madd52lo_epu64(top, x1, y1);
+
+    // the high half is below 2^64, so the wrapping sum below is exact
+    let lo = _mm512_or_si512(lo, _mm512_slli_epi64::<52>(mid));
+    let hi = _mm512_add_epi64(_mm512_srli_epi64::<12>(mid), _mm512_slli_epi64::<40>(top));
+    (hi, lo)
+}
+
+/// Reduces 128-bit lanes to canonical residues
+///
+/// With hi = a * 2^32 + b, 2^96 ≡ -1 and 2^64 ≡ `EPSILON` give
+/// lo - a + b * `EPSILON`, each step correcting a borrow or carry by `EPSILON`.
+#[inline]
+#[target_feature(enable = "avx512f")]
+fn reduce(hi: __m512i, lo: __m512i) -> __m512i {
+    let hi_hi = _mm512_srli_epi64::<32>(hi);
+    let t = _mm512_sub_epi64(lo, hi_hi);
+    let t = _mm512_mask_sub_epi64(t, _mm512_cmplt_epu64_mask(lo, hi_hi), t, epsilon());
+
+    let hi_lo = _mm512_and_si512(hi, epsilon());
+    let product = _mm512_sub_epi64(_mm512_slli_epi64::<32>(hi_lo), hi_lo);
+    let sum = _mm512_add_epi64(t, product);
+    let sum = _mm512_mask_add_epi64(sum, _mm512_cmplt_epu64_mask(sum, t), sum, epsilon());
+
+    let above = _mm512_cmpge_epu64_mask(sum, _mm512_set1_epi64(P64 as i64));
+    _mm512_mask_add_epi64(sum, above, sum, epsilon())
+}
+
+/// Multiplies canonical lanes
+///
+/// # Safety
+///
+/// Outside AVX-512F code, the caller must ensure the CPU supports AVX-512F,
+/// for example with [`is_available`].
+#[inline]
+#[target_feature(enable = "avx512f")]
+pub fn mul(x: __m512i, y: __m512i) -> __m512i {
+    let (hi, lo) = mul_wide(x, y);
+    reduce(hi, lo)
+}
+
+/// Multiplies canonical lanes, forming the products with IFMA52
+///
+/// # Safety
+///
+/// Outside AVX-512 IFMA code, the caller must ensure the CPU supports
+/// AVX-512F and AVX-512 IFMA, for example with [`is_ifma_available`].
+#[inline]
+#[target_feature(enable = "avx512f,avx512ifma")]
+pub fn mul_ifma(x: __m512i, y: __m512i) -> __m512i {
+    let (hi, lo) = mul_wide_ifma(x, y);
+    reduce(hi, lo)
+}
+
+packed_slice_kernel!(
+    /// Writes the elementwise sums a[i] + b[i] into `dst`, eight lanes at a time
+    add_slices,
+    "avx512f",
+    is_available(),
+    __m512i,
+    _mm512_loadu_si512,
+    _mm512_storeu_si512,
+    add,
+    add_winterfell
+);
+
+packed_slice_kernel!(
+    /// Writes the elementwise differences a[i] - b[i] into `dst`, eight lanes at a time
+    sub_slices,
+    "avx512f",
+    is_available(),
+    __m512i,
+    _mm512_loadu_si512,
+    _mm512_storeu_si512,
+    sub,
+    sub_winterfell
+);
+
+packed_slice_kernel!(
+    /// Writes the elementwise products a[i] * b[i] into `dst`, eight lanes at a time
+    mul_slices,
+    "avx512f",
+    is_available(),
+    __m512i,
+    _mm512_loadu_si512,
+    _mm512_storeu_si512,
+    mul,
+    |x: u64, y: u64| reduce_u64(x as u128 * y as u128)
+);
+
+packed_slice_kernel!(
+    /// Writes the elementwise products a[i] * b[i] into `dst`, eight lanes at a
+    /// time with IFMA52
+    mul_slices_ifma,
+    "avx512f,avx512ifma",
+    is_ifma_available(),
+    __m512i,
+    _mm512_loadu_si512,
+    _mm512_storeu_si512,
+    mul_ifma,
+    |x: u64, y: u64| reduce_u64(x as u128 * y as u128)
+);
+
+/// Eight packed residues, implementing [`PackedField`] when AVX-512F is enabled
+/// at compile time
+///
+/// Products use [`mul_ifma`] when AVX-512 IFMA is enabled as well.
+#[cfg(target_feature = "avx512f")]
+#[derive(Clone, Copy, Debug)]
+pub struct PackedAvx512(pub __m512i);
+
+// AVX-512F, and IFMA where it is used, are enabled for the whole build wherever this impl exists
+#[cfg(target_feature = "avx512f")]
+impl PackedField for PackedAvx512 {
+    const WIDTH: usize = LANES;
+    const NAME: &'static str = if cfg!(target_feature = "avx512ifma") {
+        "avx512_ifma"
+    } else {
+        "avx512"
+    };
+
+    #[inline(always)]
+    fn from_slice(values: &[u64]) -> Self {
+        let values = &values[..LANES];
+        Self(unsafe { _mm512_loadu_si512(values.as_ptr().cast()) })
+    }
+
+    #[inline(always)]
+    fn write_to_slice(self, out: &mut [u64]) {
+        let out = &mut out[..LANES];
+        unsafe { _mm512_storeu_si512(out.as_mut_ptr().cast(), self.0) }
+    }
+
+    #[inline(always)]
+    fn broadcast(x: u64) -> Self {
+        Self(unsafe { _mm512_set1_epi64(x as i64) })
+    }
+
+    #[inline(always)]
+    fn add(self, rhs: Self) -> Self {
+        Self(unsafe { add(self.0, rhs.0) })
+    }
+
+    #[inline(always)]
+    fn sub(self, rhs: Self) -> Self {
+        Self(unsafe { sub(self.0, rhs.0) })
+    }
+
+    #[inline(always)]
+    fn mul(self, rhs: Self) -> Self {
+        #[cfg(target_feature = "avx512ifma")]
+        let product = unsafe { mul_ifma(self.0, rhs.0) };
+        #[cfg(not(target_feature = "avx512ifma"))]
+        let product = unsafe { mul(self.0, rhs.0) };
+        Self(product)
+    }
+
+    #[inline(always)]
+    fn horizontal_sum(self) -> u64 {
+        let mut lanes = [0; LANES];
+        self.write_to_slice(&mut lanes);
+        super::sum(&lanes)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::goldilocks::{add_modulo, mul_modulo, sub_modulo};
+    use crate::rand::random_elements_exact;
+
+    #[test]
+    fn packed_kernels() {
+        let edge_cases = [
+            0,
+            1,
+            EPSILON,
+            EPSILON + 1,
+            MASK52,
+            1 << 52,
+            1 << 63,
+            P64 - 2,
+            P64 - 1,
+        ];
+        let (edge_a, edge_b): (Vec<_>, Vec<_>) = edge_cases
+            .iter()
+            .flat_map(|&x| edge_cases.iter().map(move |&y| (x, y)))
+            .unzip();
+        for len in [0, 7, 8, 1_001] {
+            let a = [&random_elements_exact(len)[..], &edge_a].concat();
+            let b = [&random_elements_exact(len)[..], &edge_b].concat();
+            let mut dst = vec![0; a.len()];
+
+            add_slices(&mut dst, &a, &b);
+            for ((&d, &x), &y) in dst.iter().zip(&a).zip(&b) {
+                assert_eq!(d, add_modulo(x, y));
+            }
+            sub_slices(&mut dst, &a, &b);
+            for ((&d, &x), &y) in dst.iter().zip(&a).zip(&b) {
+                assert_eq!(d, sub_modulo(x, y));
+            }
+            mul_slices(&mut dst, &a, &b);
+            for ((&d, &x), &y) in dst.iter().zip(&a).zip(&b) {
+                assert_eq!(d, mul_modulo(x, y), "{x} * {y}");
+            }
+            mul_slices_ifma(&mut dst, &a, &b);
+            for ((&d, &x), &y) in dst.iter().zip(&a).zip(&b) {
+                assert_eq!(d, mul_modulo(x, y), "{x} * {y} with IFMA");
+            }
+        }
+    }
+}
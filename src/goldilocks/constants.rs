@@ -0,0 +1,149 @@
+//! Constants of the Goldilocks field `P64`
+
+use super::P64;
+use crate::meta::FieldMeta;
+
+/// A generator of the multiplicative group of order `P64 - 1`
+pub const MULTIPLICATIVE_GENERATOR: u64 = 7;
+
+/// The non-residue W of the extensions F\[X\]/(X^2 - W) and F\[X\]/(X^3 - W)
+///
+/// As a generator, 7 is neither a square nor a cube.
+pub const NONRESIDUE: u64 = MULTIPLICATIVE_GENERATOR;
+
+/// The largest k such that 2^k divides `P64 - 1`
+pub const TWO_ADICITY: u32 = 32;
+
+/// The description shared by all strategies
+pub const META: FieldMeta = FieldMeta::new("goldilocks", &[P64], Some(MULTIPLICATIVE_GENERATOR));
+
+/// The prime factorization of `P64 - 1` = 2^32 * 3 * 5 * 17 * 257 * 65537
+pub const FACTORS_OF_P_MINUS_1: [(u64, u32); 6] = [
+    (2, TWO_ADICITY),
+    (3, 1),
+    (5, 1),
+    (17, 1),
+    (257, 1),
+    (65537, 1),
+];
+
+/// `ROOTS_OF_UNITY[k]` is a primitive 2^k-th root of unity, 7^((p - 1) / 2^k)
+///
+/// Each entry is the square of the next one.
+pub const ROOTS_OF_UNITY: [u64; TWO_ADICITY as usize + 1] = [
+    0x0000_0000_0000_0001,
+    0xffff_ffff_0000_0000,
+    0x0001_0000_0000_0000,
+    0xffff_fffe_ff00_0001,
+    0xefff_ffff_0000_0001,
+    0x0000_3fff_ffff_c000,
+    0x0000_0080_0000_0000,
+    0xf800_07ff_0800_0001,
+    0xbf79_143c_e60c_a966,
+    0x1905_d02a_5c41_1f4e,
+    0x9d8f_2ad7_8bfe_d972,
+    0x0653_b480_1da1_c8cf,
+    0xf2c3_5199_959d_fcb6,
+    0x1544_ef23_35d1_7997,
+    0xe0ee_0993_10bb_a1e2,
+    0xf6b2_cffe_2306_baac,
+    0x54df_9630_bf79_450e,
+    0xabd0_a6e8_aa3d_8a0e,
+    0x8128_1a7b_05f9_beac,
+    0xfbd4_1c6b_8caa_3302,
+    0x30ba_2ecd_5e93_e76d,
+    0xf502_aef5_3232_2654,
+    0x4b2a_18ad_e672_46b5,
+    0xea9d_5a13_36fb_c98b,
+    0x86cd_cc31_c307_e171,
+    0x4bba_f597_6ecf_efd8,
+    0xed41_d05b_78d6_e286,
+    0x10d7_8dd8_915a_171d,
+    0x5904_9500_004a_4485,
+    0xdfa8_c93b_a46d_2666,
+    0x7e9b_d009_b86a_0845,
+    0x400a_7f75_5588_e659,
+    0x1856_29dc_da58_878c,
+];
+
+/// `ROOTS_OF_UNITY_INV[k]` is the inverse of `ROOTS_OF_UNITY[k]`
+pub const ROOTS_OF_UNITY_INV: [u64; TWO_ADICITY as usize + 1] = [
+    0x0000_0000_0000_0001,
+    0xffff_ffff_0000_0000,
+    0xfffe_ffff_0000_0001,
+    0x0000_00ff_ffff_ff00,
+    0x0000_0010_0000_0000,
+    0xffff_fffe_fffc_0001,
+    0xfdff_ffff_0000_0001,
+    0xffef_ffff_0000_0011,
+    0x1d62_e30f_a4a4_eeb0,
+    0x3de1_9c67_cf49_6a74,
+    0x3b9a_e9d1_d8d8_7589,
+    0x76a4_0e08_66a8_e50d,
+    0x9af0_1e43_1fbd_6ea0,
+    0x3712_791d_9eb0_314a,
+    0x4097_30a1_895a_dfb6,
+    0x158e_e068_c824_1329,
+    0x6d34_1b1c_9a04_ed19,
+    0xcc9e_5a57_b834_3b3f,
+    0x22e1_fbf0_3f8b_95d6,
+    0x46a2_3c48_234c_7df9,
+    0xef88_5696_9fe6_ed7b,
+    0xa520_08ac_564a_2368,
+    0xd46e_5a4c_3645_8c11,
+    0x4bb9_aee3_72cf_655e,
+    0x10eb_8452_6381_4db7,
+    0xc01f_93fc_71bb_0b9b,
+    0xea52_f593_bb20_759a,
+    0x91f3_853f_38e6_75d9,
+    0x3ea7_eab8_d885_7184,
+    0xe4d1_4a11_4454_645d,
+    0xe243_4909_eec4_f00b,
+    0x95c0_ec9a_7ab5_0701,
+    0x76b6_b635_b6fc_8719,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{mul_modulo, P64};
+
+    #[test]
+    fn roots_of_unity() {
+        assert_eq!(ROOTS_OF_UNITY[0], 1);
+        assert_eq!(ROOTS_OF_UNITY[1], P64 - 1);
+        for k in 1..=TWO_ADICITY as usize {
+            let root = ROOTS_OF_UNITY[k];
+            assert_eq!(mul_modulo(root, root), ROOTS_OF_UNITY[k - 1]);
+            assert_eq!(mul_modulo(root, ROOTS_OF_UNITY_INV[k]), 1);
+        }
+
+        // the generator is not a square, so its 2^32-th root of unity is primitive
+        let mut x = MULTIPLICATIVE_GENERATOR;
+        let mut exp = (P64 - 1) >> TWO_ADICITY;
+        let mut root = 1;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                root = mul_modulo(root, x);
+            }
+            x = mul_modulo(x, x);
+            exp >>= 1;
+        }
+        assert_eq!(root, ROOTS_OF_UNITY[TWO_ADICITY as usize]);
+    }
+
+    #[test]
+    fn factorization() {
+        let product = FACTORS_OF_P_MINUS_1
+            .iter()
+            .map(|&(q, e)| q.pow(e))
+            .product::<u64>();
+        assert_eq!(product, P64 - 1);
+    }
+
+    #[test]
+    fn nonresidue() {
+        assert!(!crate::is_square(NONRESIDUE));
+        assert_ne!(crate::pow(NONRESIDUE, (P64 - 1) / 3), 1);
+    }
+}
@@ -0,0 +1,1058 @@
+//! Field elements of `P64`, generic over their representation
+
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::iter::{Product, Sum};
+use core::marker::PhantomData;
+use core::num::ParseIntError;
+use core::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, Sub, SubAssign};
+use core::str::FromStr;
+
+use super::*;
+
+/// An element of the field modulo `P64`
+///
+/// The value is kept canonical in the representation chosen by `R`, so values
+/// backed by different strategies cannot be mixed up by accident.
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct FieldElement<R = NativeReducer>(u64, PhantomData<R>);
+
+impl<R: Reducer> FieldElement<R> {
+    /// The additive identity
+    pub const ZERO: Self = Self::new_const(0);
+    /// The multiplicative identity
+    pub const ONE: Self = Self::new_const(1);
+
+    /// Creates an element from any `u64` in a const context
+    pub const fn new_const(x: u64) -> Self {
+        let repr = (x as u128 % P128) * R::REPR_FACTOR as u128 % P128;
+        Self(repr as u64, PhantomData)
+    }
+
+    /// Creates an element from any `u64`, reducing it modulo `P64` first
+    #[inline(always)]
+    pub fn new(x: u64) -> Self {
+        Self(R::to_repr(to_canonical(x)), PhantomData)
+    }
+
+    /// Creates an element from any `u128`, reducing it modulo `P64` first
+    #[inline(always)]
+    pub fn from_u128(x: u128) -> Self {
+        Self(R::to_repr(reduce_u64(x)), PhantomData)
+    }
+
+    /// Creates an element from any `i64`, mapping negative `x` to `P64 - |x|`
+    #[inline(always)]
+    pub fn from_i64(x: i64) -> Self {
+        let magnitude = Self::new(x.unsigned_abs());
+        if x < 0 {
+            -magnitude
+        } else {
+            magnitude
+        }
+    }
+
+    /// Creates an element from any `i128`, mapping negative `x` to `P64 - |x|`
+    #[inline(always)]
+    pub fn from_i128(x: i128) -> Self {
+        let magnitude = Self::from_u128(x.unsigned_abs());
+        if x < 0 {
+            -magnitude
+        } else {
+            magnitude
+        }
+    }
+
+    /// Returns the canonical residue of this element
+    #[inline(always)]
+    pub fn value(self) -> u64 {
+        R::from_repr(self.0)
+    }
+
+    /// Returns the balanced representative, in `[-(p - 1) / 2, (p - 1) / 2]`
+    ///
+    /// This is the inverse of [`FieldElement::from_i64`] on that range.
+    #[inline(always)]
+    pub fn to_signed(self) -> i64 {
+        to_signed(self.value())
+    }
+
+    /// Returns the internal representation of this element
+    #[inline(always)]
+    pub fn repr(self) -> u64 {
+        self.0
+    }
+
+    /// Encodes the canonical residue as little-endian bytes
+    #[inline(always)]
+    pub fn to_le_bytes(self) -> [u8; 8] {
+        self.value().to_le_bytes()
+    }
+
+    /// Encodes the canonical residue as big-endian bytes
+    #[inline(always)]
+    pub fn to_be_bytes(self) -> [u8; 8] {
+        self.value().to_be_bytes()
+    }
+
+    /// Decodes a canonical residue from little-endian bytes, or `None` if it is not canonical
+    #[inline(always)]
+    pub fn from_le_bytes(bytes: [u8; 8]) -> Option<Self> {
+        Self::from_canonical(u64::from_le_bytes(bytes))
+    }
+
+    /// Decodes a canonical residue from big-endian bytes, or `None` if it is not canonical
+    #[inline(always)]
+    pub fn from_be_bytes(bytes: [u8; 8]) -> Option<Self> {
+        Self::from_canonical(u64::from_be_bytes(bytes))
+    }
+
+    #[inline(always)]
+    fn from_canonical(x: u64) -> Option<Self> {
+        if is_canonical(x) {
+            Some(Self(R::to_repr(x), PhantomData))
+        } else {
+            None
+        }
+    }
+
+    /// Multiplies this element by a small integer using `mul_small`
+    ///
+    /// Scaling by an integer commutes with every representation, so this is
+    /// valid for all strategies.
+    #[inline(always)]
+    pub fn mul_small(self, y: u32) -> Self {
+        Self(mul_small(self.0, y), PhantomData)
+    }
+
+    /// Divides this element by two using `halve`
+    ///
+    /// Like `mul_small`, halving commutes with every representation.
+    #[inline(always)]
+    pub fn halve(self) -> Self {
+        Self(halve(self.0), PhantomData)
+    }
+
+    /// Computes self * b + c, with a single reduction where `R` supports it
+    #[inline(always)]
+    pub fn mul_add(self, b: Self, c: Self) -> Self {
+        Self(R::mul_add(self.0, b.0, c.0), PhantomData)
+    }
+
+    /// Squares this element
+    #[inline(always)]
+    pub fn square(self) -> Self {
+        Self(R::square(self.0), PhantomData)
+    }
+
+    /// Raises this element to the power `exp` by square-and-multiply
+    pub fn pow(self, mut exp: u64) -> Self {
+        let mut base = self;
+        let mut acc = Self::ONE;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                acc *= base;
+            }
+            base *= base;
+            exp >>= 1;
+        }
+        acc
+    }
+
+    /// Returns the multiplicative inverse, or `None` for zero
+    pub fn inverse(self) -> Option<Self> {
+        if self == Self::ZERO {
+            None
+        } else {
+            // x^(p - 2) = x^-1 by Fermat's little theorem
+            Some(self.pow(P64 - 2))
+        }
+    }
+}
+
+/// A canonical residue, as produced by `mul_modulo` and `mul_reduce159`
+pub type Canonical = FieldElement<Reduce159>;
+
+/// A residue in Montgomery form, as produced by `mul_reduce_montgomery`
+///
+/// Mixing domains is a type error:
+///
+/// ```compile_fail
+/// use prime_field::{Canonical, MontgomeryForm};
+///
+/// let _ = Canonical::new(2) * MontgomeryForm::new(3);
+/// ```
+pub type MontgomeryForm = FieldElement<Montgomery>;
+
+impl<R: Reducer> FieldElement<R> {
+    /// Moves this element into the representation of another strategy
+    #[inline(always)]
+    pub fn convert<S: Reducer>(self) -> FieldElement<S> {
+        FieldElement(S::to_repr(R::from_repr(self.0)), PhantomData)
+    }
+}
+
+impl Canonical {
+    /// Converts this element into Montgomery form
+    #[inline(always)]
+    pub fn to_montgomery(self) -> MontgomeryForm {
+        self.convert()
+    }
+}
+
+impl MontgomeryForm {
+    /// Converts this element out of Montgomery form
+    #[inline(always)]
+    pub fn to_canonical(self) -> Canonical {
+        self.convert()
+    }
+}
+
+impl<R: Reducer> From<u64> for FieldElement<R> {
+    fn from(x: u64) -> Self {
+        Self::new(x)
+    }
+}
+
+impl<R: Reducer> From<u128> for FieldElement<R> {
+    fn from(x: u128) -> Self {
+        Self::from_u128(x)
+    }
+}
+
+impl<R: Reducer> From<i64> for FieldElement<R> {
+    fn from(x: i64) -> Self {
+        Self::from_i64(x)
+    }
+}
+
+impl<R: Reducer> From<i128> for FieldElement<R> {
+    fn from(x: i128) -> Self {
+        Self::from_i128(x)
+    }
+}
+
+impl<R: Reducer> fmt::Debug for FieldElement<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "FieldElement({})", self.value())
+    }
+}
+
+/// Hashes the canonical residue, so elements hash alike in every representation
+impl<R: Reducer> Hash for FieldElement<R> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.value().hash(state);
+    }
+}
+
+/// Orders elements by their canonical residues
+///
+/// This ordering exists so that elements can be sorted, deduplicated and used
+/// as `BTreeMap` keys. It is not compatible with the field operations: for
+/// example, `a < b` does not imply `a + c < b + c`.
+impl<R: Reducer> Ord for FieldElement<R> {
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        self.value().cmp(&other.value())
+    }
+}
+
+impl<R: Reducer> PartialOrd for FieldElement<R> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<R: Reducer> fmt::Display for FieldElement<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Display::fmt(&self.value(), f)
+    }
+}
+
+impl<R: Reducer> fmt::LowerHex for FieldElement<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::LowerHex::fmt(&self.value(), f)
+    }
+}
+
+/// Error returned when parsing a [`FieldElement`] from a string fails
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ParseFieldElementError {
+    /// The string is not a decimal or `0x`-prefixed hexadecimal `u64`
+    Invalid(ParseIntError),
+    /// The value is not less than `P64`
+    NonCanonical(u64),
+}
+
+impl fmt::Display for ParseFieldElementError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Invalid(err) => write!(f, "invalid field element: {err}"),
+            Self::NonCanonical(x) => write!(f, "non-canonical field element: {x}"),
+        }
+    }
+}
+
+impl core::error::Error for ParseFieldElementError {}
+
+impl<R: Reducer> FromStr for FieldElement<R> {
+    type Err = ParseFieldElementError;
+
+    /// Parses a canonical residue written in decimal or as `0x`-prefixed hex
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parsed = match s.strip_prefix("0x").or_else(|| s.strip_prefix("0X")) {
+            Some(hex) => u64::from_str_radix(hex, 16),
+            None => s.parse::<u64>(),
+        };
+        let x = parsed.map_err(ParseFieldElementError::Invalid)?;
+        if !is_canonical(x) {
+            return Err(ParseFieldElementError::NonCanonical(x));
+        }
+        Ok(Self::new(x))
+    }
+}
+
+impl<R: Reducer> Add for FieldElement<R> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn add(self, rhs: Self) -> Self {
+        Self(R::add(self.0, rhs.0), PhantomData)
+    }
+}
+
+impl<R: Reducer> Sub for FieldElement<R> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn sub(self, rhs: Self) -> Self {
+        Self(R::sub(self.0, rhs.0), PhantomData)
+    }
+}
+
+impl<R: Reducer> Mul for FieldElement<R> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn mul(self, rhs: Self) -> Self {
+        Self(R::mul(self.0, rhs.0), PhantomData)
+    }
+}
+
+impl<R: Reducer> Neg for FieldElement<R> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn neg(self) -> Self {
+        Self(neg_branchless(self.0), PhantomData)
+    }
+}
+
+impl<R: Reducer> AddAssign for FieldElement<R> {
+    #[inline(always)]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<R: Reducer> SubAssign for FieldElement<R> {
+    #[inline(always)]
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<R: Reducer> MulAssign for FieldElement<R> {
+    #[inline(always)]
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<R: Reducer> Div for FieldElement<R> {
+    type Output = Self;
+
+    /// Multiplies by the inverse of `rhs`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` is zero.
+    #[inline(always)]
+    fn div(self, rhs: Self) -> Self {
+        self.mul(rhs.inverse().expect("division by zero"))
+    }
+}
+
+impl<R: Reducer> DivAssign for FieldElement<R> {
+    #[inline(always)]
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+impl<R: Reducer> Rem for FieldElement<R> {
+    type Output = Self;
+
+    /// Every nonzero element divides every other, so the remainder is zero
+    ///
+    /// # Panics
+    ///
+    /// Panics if `rhs` is zero.
+    #[inline(always)]
+    fn rem(self, rhs: Self) -> Self {
+        assert!(rhs != Self::ZERO, "division by zero");
+        Self::ZERO
+    }
+}
+
+macro_rules! impl_ref_ops {
+    ($($op:ident::$f:ident, $op_assign:ident::$f_assign:ident);*) => {$(
+        impl<'a, R: Reducer> $op<&'a Self> for FieldElement<R> {
+            type Output = Self;
+
+            #[inline(always)]
+            fn $f(self, rhs: &'a Self) -> Self {
+                self.$f(*rhs)
+            }
+        }
+
+        impl<'a, R: Reducer> $op_assign<&'a Self> for FieldElement<R> {
+            #[inline(always)]
+            fn $f_assign(&mut self, rhs: &'a Self) {
+                self.$f_assign(*rhs)
+            }
+        }
+    )*};
+}
+
+impl_ref_ops!(
+    Add::add, AddAssign::add_assign;
+    Sub::sub, SubAssign::sub_assign;
+    Mul::mul, MulAssign::mul_assign;
+    Div::div, DivAssign::div_assign
+);
+
+impl<R: Reducer> Sum for FieldElement<R> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ZERO, Add::add)
+    }
+}
+
+impl<'a, R: Reducer> Sum<&'a Self> for FieldElement<R> {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+impl<R: Reducer> Product for FieldElement<R> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::ONE, Mul::mul)
+    }
+}
+
+impl<'a, R: Reducer> Product<&'a Self> for FieldElement<R> {
+    fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().product()
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_impl {
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    use super::*;
+
+    impl<R: Reducer> Serialize for FieldElement<R> {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_u64(self.value())
+        }
+    }
+
+    impl<'de, R: Reducer> Deserialize<'de> for FieldElement<R> {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let x = u64::deserialize(deserializer)?;
+            if !is_canonical(x) {
+                return Err(D::Error::custom(ParseFieldElementError::NonCanonical(x)));
+            }
+            Ok(Self::new(x))
+        }
+    }
+}
+
+/// Serializes a [`FieldElement`] as a `0x`-prefixed hex string
+///
+/// Elements serialize as canonical `u64`s by default; use this module with
+/// `#[serde(with = "prime_field::serde_hex")]` to select the hex form for a
+/// field instead. Deserialization accepts anything [`FromStr`] does.
+#[cfg(feature = "serde")]
+pub mod serde_hex {
+    use serde::de::{Error, Visitor};
+    use serde::{Deserializer, Serializer};
+
+    use super::*;
+
+    pub fn serialize<R: Reducer, S: Serializer>(
+        x: &FieldElement<R>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(&format_args!("{x:#x}"))
+    }
+
+    pub fn deserialize<'de, R: Reducer, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<FieldElement<R>, D::Error> {
+        deserializer.deserialize_str(HexVisitor(PhantomData))
+    }
+
+    struct HexVisitor<R>(PhantomData<R>);
+
+    impl<R: Reducer> Visitor<'_> for HexVisitor<R> {
+        type Value = FieldElement<R>;
+
+        fn expecting(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str("a canonical field element as a string")
+        }
+
+        fn visit_str<E: Error>(self, s: &str) -> Result<Self::Value, E> {
+            s.parse().map_err(E::custom)
+        }
+    }
+}
+
+/// Elements have the layout of their `u64` representation, so slices can be
+/// cast to and from `&[u64]` without copying. Casting into elements does not
+/// check that values are canonical representations; that is up to the caller.
+#[cfg(feature = "bytemuck")]
+unsafe impl<R: Reducer + 'static> bytemuck::Pod for FieldElement<R> {}
+
+#[cfg(feature = "bytemuck")]
+unsafe impl<R: Reducer> bytemuck::Zeroable for FieldElement<R> {}
+
+/// Elements are `Copy` and so cannot wipe themselves on drop; wrap buffers of
+/// secret elements in [`zeroize::Zeroizing`] instead.
+#[cfg(feature = "zeroize")]
+impl<R: Reducer> zeroize::DefaultIsZeroes for FieldElement<R> {}
+
+#[cfg(feature = "num-traits")]
+mod num_traits_impl {
+    use num_traits::{Inv, Num, One, Pow, Zero};
+
+    use super::*;
+
+    impl<R: Reducer> Zero for FieldElement<R> {
+        fn zero() -> Self {
+            Self::ZERO
+        }
+
+        fn is_zero(&self) -> bool {
+            *self == Self::ZERO
+        }
+    }
+
+    impl<R: Reducer> One for FieldElement<R> {
+        fn one() -> Self {
+            Self::ONE
+        }
+    }
+
+    impl<R: Reducer> Num for FieldElement<R> {
+        type FromStrRadixErr = ParseFieldElementError;
+
+        /// Parses a canonical residue in the given radix, like [`FromStr`]
+        fn from_str_radix(str: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+            let x = u64::from_str_radix(str, radix).map_err(ParseFieldElementError::Invalid)?;
+            if !is_canonical(x) {
+                return Err(ParseFieldElementError::NonCanonical(x));
+            }
+            Ok(Self::new(x))
+        }
+    }
+
+    impl<R: Reducer> Inv for FieldElement<R> {
+        type Output = Self;
+
+        /// # Panics
+        ///
+        /// Panics if `self` is zero.
+        fn inv(self) -> Self {
+            self.inverse().expect("inverse of zero")
+        }
+    }
+
+    impl<R: Reducer> Pow<u64> for FieldElement<R> {
+        type Output = Self;
+
+        fn pow(self, exp: u64) -> Self {
+            FieldElement::pow(self, exp)
+        }
+    }
+}
+
+#[cfg(feature = "subtle")]
+mod subtle_impl {
+    use subtle::{Choice, ConditionallySelectable, ConstantTimeEq};
+
+    use super::*;
+
+    impl<R: Reducer> ConstantTimeEq for FieldElement<R> {
+        fn ct_eq(&self, other: &Self) -> Choice {
+            self.0.ct_eq(&other.0)
+        }
+    }
+
+    impl<R: Reducer> ConditionallySelectable for FieldElement<R> {
+        fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+            Self(u64::conditional_select(&a.0, &b.0, choice), PhantomData)
+        }
+    }
+}
+
+#[cfg(feature = "ff")]
+mod ff_impl {
+    use ff::{Field, PrimeField};
+    use rand_core::RngCore;
+    use subtle::{Choice, CtOption};
+
+    use super::*;
+
+    impl<R: Reducer + Send + Sync + 'static> Field for FieldElement<R> {
+        const ZERO: Self = Self::ZERO;
+        const ONE: Self = Self::ONE;
+
+        fn random(mut rng: impl RngCore) -> Self {
+            loop {
+                let x = rng.next_u64();
+                if x < P64 {
+                    return Self::new(x);
+                }
+            }
+        }
+
+        fn square(&self) -> Self {
+            *self * *self
+        }
+
+        fn double(&self) -> Self {
+            *self + *self
+        }
+
+        fn invert(&self) -> CtOption<Self> {
+            // x^(p - 2) = x^-1 by Fermat's little theorem
+            CtOption::new(Field::pow(self, [P64 - 2]), !self.is_zero())
+        }
+
+        fn sqrt_ratio(num: &Self, div: &Self) -> (Choice, Self) {
+            ff::helpers::sqrt_ratio_generic(num, div)
+        }
+
+        fn sqrt(&self) -> CtOption<Self> {
+            // (t - 1) / 2 where t = (p - 1) / 2^32
+            ff::helpers::sqrt_tonelli_shanks(self, [(1 << 31) - 1])
+        }
+    }
+
+    impl<R: Reducer + Send + Sync + 'static> PrimeField for FieldElement<R> {
+        /// Canonical residue in little-endian byte order
+        type Repr = [u8; 8];
+
+        fn from_repr(repr: Self::Repr) -> CtOption<Self> {
+            let x = u64::from_le_bytes(repr);
+            CtOption::new(Self::new(x), Choice::from((x < P64) as u8))
+        }
+
+        fn from_u128(v: u128) -> Self {
+            FieldElement::from_u128(v)
+        }
+
+        fn to_repr(&self) -> Self::Repr {
+            self.to_le_bytes()
+        }
+
+        fn is_odd(&self) -> Choice {
+            Choice::from((self.value() & 1) as u8)
+        }
+
+        const MODULUS: &'static str = "0xffffffff00000001";
+        const NUM_BITS: u32 = 64;
+        const CAPACITY: u32 = 63;
+        const TWO_INV: Self = Self::new_const(0x7fff_ffff_8000_0001);
+        const MULTIPLICATIVE_GENERATOR: Self = Self::new_const(constants::MULTIPLICATIVE_GENERATOR);
+        const S: u32 = constants::TWO_ADICITY;
+        /// 7^((p - 1) / 2^32)
+        const ROOT_OF_UNITY: Self = Self::new_const(constants::ROOTS_OF_UNITY[32]);
+        const ROOT_OF_UNITY_INV: Self = Self::new_const(constants::ROOTS_OF_UNITY_INV[32]);
+        /// 7^(2^32)
+        const DELTA: Self = Self::new_const(0xaa5b_2509_f86b_b4d4);
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+    use crate::rand::random_elements;
+
+    /// Runs a generic `check` with the `%`, 159-bit and Montgomery reducers
+    macro_rules! for_each_reducer {
+        ($check:ident) => {
+            $check::<Modulo>();
+            $check::<Reduce159>();
+            $check::<Montgomery>();
+        };
+    }
+
+    fn element_ops<R: Reducer>() {
+        let operands = R::random(1_000);
+        for (&x, &y) in operands.iter().tuple_windows() {
+            let (a, b) = (FieldElement::<R>::new(x), FieldElement::<R>::new(y));
+            assert_eq!(a.value(), x);
+            assert_eq!((a + b).value(), add_modulo(x, y));
+            assert_eq!((a * b).value(), mul_modulo(x, y));
+            assert_eq!((a - b + b), a);
+            assert_eq!((-a + a).value(), 0);
+        }
+    }
+
+    #[test]
+    fn element_equivalence() {
+        element_ops::<Modulo>();
+        element_ops::<Reduce159>();
+        element_ops::<Barrett>();
+        element_ops::<Crandall>();
+        element_ops::<Montgomery>();
+        element_ops::<Plantard>();
+        element_ops::<Limbs32>();
+    }
+
+    #[test]
+    fn element_edge_cases() {
+        fn check<R: Reducer>() {
+            let max = FieldElement::<R>::new(P64 - 1);
+            let one = FieldElement::<R>::new(1);
+            assert_eq!(FieldElement::<R>::new(P64).value(), 0);
+            assert_eq!(FieldElement::<R>::new(u64::MAX).value(), u64::MAX - P64);
+            assert_eq!((max + one).value(), 0);
+            assert_eq!((max * max).value(), 1);
+            assert_eq!((-one).value(), P64 - 1);
+            assert_eq!((-FieldElement::<R>::new(0)).value(), 0);
+        }
+        check::<Modulo>();
+        check::<Reduce159>();
+        check::<Barrett>();
+        check::<Crandall>();
+        check::<Montgomery>();
+        check::<Plantard>();
+        check::<Limbs32>();
+    }
+
+    #[cfg(feature = "ff")]
+    #[test]
+    fn ff_prime_field() {
+        use ff::{Field, PrimeField};
+
+        fn check<R: Reducer + Send + Sync + 'static>() {
+            type F<R> = FieldElement<R>;
+
+            assert_eq!(F::<R>::TWO_INV.double(), F::<R>::ONE);
+            assert_eq!(
+                F::<R>::ROOT_OF_UNITY * F::<R>::ROOT_OF_UNITY_INV,
+                F::<R>::ONE
+            );
+            assert_eq!(F::<R>::ROOT_OF_UNITY.pow(1 << 32), F::<R>::ONE);
+            assert_eq!(F::<R>::ROOT_OF_UNITY.pow(1 << 31), -F::<R>::ONE);
+            assert_eq!(F::<R>::MULTIPLICATIVE_GENERATOR.pow(1 << 32), F::<R>::DELTA);
+            assert_eq!(F::<R>::DELTA.pow((1 << 32) - 1), F::<R>::ONE);
+
+            assert!(bool::from(F::<R>::from_repr(P64.to_le_bytes()).is_none()));
+            assert!(bool::from(F::<R>::ZERO.invert().is_none()));
+
+            for _ in 0..100 {
+                let x = F::<R>::random(::rand::thread_rng());
+                assert_eq!(F::<R>::from_repr(x.to_repr()).unwrap(), x);
+                if x != F::<R>::ZERO {
+                    assert_eq!(x * x.invert().unwrap(), F::<R>::ONE);
+                }
+                let root = x.square().sqrt().unwrap();
+                assert!(root == x || root == -x);
+            }
+        }
+        for_each_reducer!(check);
+    }
+
+    #[test]
+    fn element_inverse() {
+        fn check<R: Reducer>() {
+            assert_eq!(FieldElement::<R>::ZERO.inverse(), None);
+            for x in R::random(100).into_iter().chain([1, 2, P64 - 1]) {
+                let x = FieldElement::<R>::new(x);
+                if x != FieldElement::ZERO {
+                    assert_eq!(x * x.inverse().unwrap(), FieldElement::ONE);
+                    assert_eq!(x / x, FieldElement::ONE);
+                }
+            }
+        }
+        for_each_reducer!(check);
+    }
+
+    #[cfg(feature = "num-traits")]
+    #[test]
+    fn num_traits() {
+        use num_traits::{Inv, Num, One, Pow, Zero};
+
+        type F = FieldElement;
+
+        assert!(F::zero().is_zero());
+        assert!(F::one().is_one());
+        assert_eq!(
+            F::from_str_radix("ffffffff00000000", 16),
+            Ok(F::new(P64 - 1))
+        );
+        assert_eq!(
+            F::from_str_radix("ffffffff00000002", 16),
+            Err(ParseFieldElementError::NonCanonical(P64 + 1))
+        );
+        assert!(matches!(
+            F::from_str_radix("x", 10),
+            Err(ParseFieldElementError::Invalid(_))
+        ));
+        assert_eq!(F::new(3).inv() * F::new(3), F::one());
+        assert_eq!(Pow::pow(F::new(3), 4u64), F::new(81));
+        assert_eq!(F::new(5) % F::new(3), F::zero());
+    }
+
+    #[test]
+    fn montgomery_conversions() {
+        for &x in random_elements(1_000).iter().chain(&[0, 1, P64 - 1]) {
+            let canonical = Canonical::new(x);
+            let montgomery = canonical.to_montgomery();
+            assert_eq!(montgomery.value(), x);
+            assert_eq!(montgomery.repr(), mul_modulo(x, 0xffff_ffff));
+            assert_eq!(montgomery.to_canonical(), canonical);
+            assert_eq!(canonical.convert::<Modulo>().repr(), x);
+        }
+    }
+
+    #[test]
+    fn wide_and_signed_constructors() {
+        fn check<R: Reducer>() {
+            type F<R> = FieldElement<R>;
+
+            assert_eq!(F::<R>::from_i64(-1).value(), P64 - 1);
+            assert_eq!(F::<R>::from_i64(i64::MIN).value(), P64 - (1 << 63));
+            assert_eq!(F::<R>::from_i64(i64::MAX).value(), i64::MAX as u64);
+            assert_eq!(F::<R>::from_i128(-1).value(), P64 - 1);
+            assert_eq!(F::<R>::from_i128(-(P128 as i128)).value(), 0);
+            assert_eq!(
+                F::<R>::from_u128(u128::MAX).value() as u128,
+                u128::MAX % P128
+            );
+
+            for &x in random_elements(1_000).iter() {
+                let wide = (x as u128) << 64 | x as u128;
+                assert_eq!(F::<R>::from_u128(wide).value() as u128, wide % P128);
+
+                let signed = x as i64;
+                let expected = (signed as i128).rem_euclid(P128 as i128) as u64;
+                assert_eq!(F::<R>::from_i64(signed).value(), expected);
+
+                let signed = -(wide as i128);
+                let expected = signed.rem_euclid(P128 as i128) as u64;
+                assert_eq!(F::<R>::from_i128(signed).value(), expected);
+                assert_eq!(F::<R>::from(signed), F::<R>::from_i128(signed));
+            }
+        }
+        for_each_reducer!(check);
+    }
+
+    #[test]
+    fn text_round_trip() {
+        fn check<R: Reducer>() {
+            type F<R> = FieldElement<R>;
+
+            for &x in random_elements(1_000).iter().chain(&[0, P64 - 1]) {
+                let a = F::<R>::new(x);
+                assert_eq!(a.to_string(), x.to_string());
+                assert_eq!(format!("{a:#x}"), format!("{x:#x}"));
+                assert_eq!(a.to_string().parse::<F<R>>(), Ok(a));
+                assert_eq!(format!("{a:#x}").parse::<F<R>>(), Ok(a));
+                assert_eq!(format!("0X{a:x}").parse::<F<R>>(), Ok(a));
+            }
+
+            let non_canonical = "0xffffffff00000001".parse::<F<R>>();
+            assert_eq!(
+                non_canonical,
+                Err(ParseFieldElementError::NonCanonical(P64))
+            );
+            assert!(matches!(
+                "0x".parse::<F<R>>(),
+                Err(ParseFieldElementError::Invalid(_))
+            ));
+            assert!(matches!(
+                "-1".parse::<F<R>>(),
+                Err(ParseFieldElementError::Invalid(_))
+            ));
+        }
+        for_each_reducer!(check);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        use serde::{Deserialize, Serialize};
+
+        #[derive(Debug, PartialEq, Serialize, Deserialize)]
+        struct Vector {
+            plain: FieldElement<Montgomery>,
+            #[serde(with = "crate::serde_hex")]
+            hex: FieldElement<Montgomery>,
+        }
+
+        let vector = Vector {
+            plain: FieldElement::new(P64 - 1),
+            hex: FieldElement::new(0xabc),
+        };
+        let json = serde_json::to_string(&vector).unwrap();
+        assert_eq!(json, r#"{"plain":18446744069414584320,"hex":"0xabc"}"#);
+        assert_eq!(serde_json::from_str::<Vector>(&json).unwrap(), vector);
+
+        let non_canonical = format!(r#"{{"plain":{P64},"hex":"0x0"}}"#);
+        assert!(serde_json::from_str::<Vector>(&non_canonical).is_err());
+        let non_canonical = r#"{"plain":0,"hex":"0xffffffff00000001"}"#;
+        assert!(serde_json::from_str::<Vector>(non_canonical).is_err());
+    }
+
+    #[test]
+    fn byte_encoding() {
+        fn check<R: Reducer>() {
+            type F<R> = FieldElement<R>;
+
+            for &x in random_elements(1_000).iter().chain(&[0, P64 - 1]) {
+                let a = F::<R>::new(x);
+                assert_eq!(a.to_le_bytes(), x.to_le_bytes());
+                assert_eq!(a.to_be_bytes(), x.to_be_bytes());
+                assert_eq!(F::<R>::from_le_bytes(a.to_le_bytes()), Some(a));
+                assert_eq!(F::<R>::from_be_bytes(a.to_be_bytes()), Some(a));
+            }
+            for x in [P64, P64 + 1, u64::MAX] {
+                assert_eq!(F::<R>::from_le_bytes(x.to_le_bytes()), None);
+                assert_eq!(F::<R>::from_be_bytes(x.to_be_bytes()), None);
+            }
+        }
+        for_each_reducer!(check);
+    }
+
+    #[cfg(feature = "zeroize")]
+    #[test]
+    fn zeroize() {
+        use zeroize::{Zeroize, Zeroizing};
+
+        let mut x = FieldElement::<Montgomery>::new(42);
+        x.zeroize();
+        assert_eq!(x, FieldElement::ZERO);
+
+        let mut elements = random_elements(100)
+            .into_iter()
+            .map(FieldElement::<Montgomery>::new)
+            .collect::<Vec<_>>();
+        elements[..].zeroize();
+        assert!(elements.iter().all(|&x| x.repr() == 0));
+
+        let buffer = Zeroizing::new(vec![FieldElement::<Reduce159>::ONE; 100]);
+        assert_eq!(buffer.len(), 100);
+    }
+
+    #[cfg(feature = "subtle")]
+    #[test]
+    fn constant_time() {
+        use subtle::{ConditionallySelectable, ConstantTimeEq};
+
+        for x in [0, 1, P64 - 1, P64, P64 + 1, u64::MAX] {
+            assert_eq!(conditional_reduce(x), to_canonical(x));
+        }
+        for x in random_elements(1_000) {
+            assert_eq!(conditional_reduce(x), x);
+            assert_eq!(
+                conditional_reduce(x.wrapping_add(P64)),
+                to_canonical(x.wrapping_add(P64))
+            );
+        }
+
+        let (a, b) = (FieldElement::<Montgomery>::new(3), FieldElement::new(5));
+        assert!(bool::from(a.ct_eq(&a)));
+        assert!(!bool::from(a.ct_eq(&b)));
+        assert_eq!(FieldElement::conditional_select(&a, &b, 0.into()), a);
+        assert_eq!(FieldElement::conditional_select(&a, &b, 1.into()), b);
+    }
+
+    #[test]
+    fn canonical_ordering() {
+        use std::collections::BTreeSet;
+
+        let values = random_elements(1_000);
+        let mut elements = values
+            .iter()
+            .chain(&values)
+            .map(|&x| FieldElement::<Montgomery>::new(x))
+            .collect::<Vec<_>>();
+        elements.sort();
+        elements.dedup();
+
+        let mut expected = values.clone();
+        expected.sort();
+        expected.dedup();
+        assert_eq!(
+            elements.iter().map(|x| x.value()).collect::<Vec<_>>(),
+            expected
+        );
+
+        let set = values
+            .iter()
+            .map(|&x| FieldElement::<Montgomery>::new(x))
+            .collect::<BTreeSet<_>>();
+        assert_eq!(set.len(), expected.len());
+        assert!(FieldElement::<Montgomery>::new(P64 - 1) > FieldElement::new(1));
+    }
+
+    #[test]
+    fn canonical_hash() {
+        use std::collections::hash_map::DefaultHasher;
+        use std::collections::HashSet;
+
+        fn hash<T: Hash>(x: T) -> u64 {
+            let mut hasher = DefaultHasher::new();
+            x.hash(&mut hasher);
+            hasher.finish()
+        }
+
+        for x in random_elements(100) {
+            let canonical = hash(FieldElement::<Reduce159>::new(x));
+            assert_eq!(hash(FieldElement::<Montgomery>::new(x)), canonical);
+            assert_eq!(hash(FieldElement::<Modulo>::new(x)), canonical);
+        }
+
+        // the 2^4-th roots of unity, found as the powers of a primitive one
+        let root = FieldElement::<Montgomery>::new(constants::ROOTS_OF_UNITY[4]);
+        let roots = (0..64).map(|k| root.pow(k)).collect::<HashSet<_>>();
+        assert_eq!(roots.len(), 16);
+    }
+
+    #[cfg(feature = "bytemuck")]
+    #[test]
+    fn bytemuck_casts() {
+        let values = random_elements(100);
+        let elements: &[FieldElement<Reduce159>] = bytemuck::cast_slice(&values);
+        assert!(elements.iter().zip(&values).all(|(x, &y)| x.value() == y));
+
+        let montgomery = values
+            .iter()
+            .map(|&x| FieldElement::<Montgomery>::new(x))
+            .collect::<Vec<_>>();
+        let reprs: &[u64] = bytemuck::cast_slice(&montgomery);
+        assert!(reprs.iter().zip(&montgomery).all(|(&x, y)| x == y.repr()));
+
+        let zero: FieldElement<Montgomery> = bytemuck::Zeroable::zeroed();
+        assert_eq!(zero, FieldElement::ZERO);
+    }
+}
@@ -0,0 +1,247 @@
+//! The quadratic extension of `P64` by a square root of the non-residue 7
+
+use core::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use super::*;
+use crate::meta::{FieldMeta, Meta};
+
+/// An element c0 + c1 * u of F\[u\]/(u^2 - 7), over base elements backed by `R`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Fp2<R: Reducer = NativeReducer> {
+    /// The constant coefficient
+    pub c0: FieldElement<R>,
+    /// The coefficient of u
+    pub c1: FieldElement<R>,
+}
+
+/// Multiplies a base element by the non-residue W = u^2
+#[inline(always)]
+fn mul_by_w<R: Reducer>(x: FieldElement<R>) -> FieldElement<R> {
+    x.mul_small(constants::NONRESIDUE as u32)
+}
+
+impl<R: Reducer> Fp2<R> {
+    /// The additive identity
+    pub const ZERO: Self = Self::new(FieldElement::ZERO, FieldElement::ZERO);
+    /// The multiplicative identity
+    pub const ONE: Self = Self::new(FieldElement::ONE, FieldElement::ZERO);
+
+    /// Creates the element c0 + c1 * u
+    pub const fn new(c0: FieldElement<R>, c1: FieldElement<R>) -> Self {
+        Self { c0, c1 }
+    }
+
+    /// Embeds a base field element
+    pub const fn from_base(c0: FieldElement<R>) -> Self {
+        Self::new(c0, FieldElement::ZERO)
+    }
+
+    /// Returns the conjugate c0 - c1 * u, the image under Frobenius
+    #[inline(always)]
+    pub fn conjugate(self) -> Self {
+        Self::new(self.c0, -self.c1)
+    }
+
+    /// Returns the image under the k-th power of Frobenius, x -> x^(p^k)
+    ///
+    /// u^(p^k) = 7^((p^k - 1) / 2) * u, and since 7 is a non-residue that
+    /// coefficient alternates between 1 and -1, so odd k conjugate.
+    #[inline(always)]
+    pub fn frobenius(self, k: usize) -> Self {
+        if k.is_multiple_of(2) {
+            self
+        } else {
+            self.conjugate()
+        }
+    }
+
+    /// Returns the norm c0^2 - 7 * c1^2, the product with the conjugate
+    #[inline(always)]
+    pub fn norm(self) -> FieldElement<R> {
+        self.c0.square() - mul_by_w(self.c1.square())
+    }
+
+    /// Multiplies by a base field element
+    #[inline(always)]
+    pub fn mul_by_base(self, x: FieldElement<R>) -> Self {
+        Self::new(self.c0 * x, self.c1 * x)
+    }
+
+    /// Multiplies with four base multiplications, as a reference for `mul`
+    #[inline(always)]
+    pub fn mul_schoolbook(self, rhs: Self) -> Self {
+        Self::new(
+            self.c0 * rhs.c0 + mul_by_w(self.c1 * rhs.c1),
+            self.c0 * rhs.c1 + self.c1 * rhs.c0,
+        )
+    }
+
+    /// Multiplies with three base multiplications, Karatsuba style
+    #[inline(always)]
+    pub fn mul_karatsuba(self, rhs: Self) -> Self {
+        let v0 = self.c0 * rhs.c0;
+        let v1 = self.c1 * rhs.c1;
+        let cross = (self.c0 + self.c1) * (rhs.c0 + rhs.c1);
+        Self::new(v0 + mul_by_w(v1), cross - v0 - v1)
+    }
+
+    /// Squares with two base multiplications
+    ///
+    /// (c0 + c1 * u)^2 = (c0 + c1) * (c0 + 7 * c1) - 8 * c0 * c1 + 2 * c0 * c1 * u
+    #[inline(always)]
+    pub fn square(self) -> Self {
+        let product = self.c0 * self.c1;
+        let c0 = (self.c0 + self.c1) * (self.c0 + mul_by_w(self.c1)) - product.mul_small(8);
+        Self::new(c0, product + product)
+    }
+
+    /// Returns the multiplicative inverse, or `None` for zero
+    ///
+    /// Dividing the conjugate by the norm leaves a single base field inversion.
+    pub fn inverse(self) -> Option<Self> {
+        let norm_inv = self.norm().inverse()?;
+        Some(self.conjugate().mul_by_base(norm_inv))
+    }
+}
+
+impl<R: Reducer> Meta for Fp2<R> {
+    const META: FieldMeta = constants::META.extension(2);
+}
+
+impl<R: Reducer> Add for Fp2<R> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.c0 + rhs.c0, self.c1 + rhs.c1)
+    }
+}
+
+impl<R: Reducer> Sub for Fp2<R> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.c0 - rhs.c0, self.c1 - rhs.c1)
+    }
+}
+
+impl<R: Reducer> Mul for Fp2<R> {
+    type Output = Self;
+
+    /// Multiplies using `mul_karatsuba`
+    #[inline(always)]
+    fn mul(self, rhs: Self) -> Self {
+        self.mul_karatsuba(rhs)
+    }
+}
+
+impl<R: Reducer> Neg for Fp2<R> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn neg(self) -> Self {
+        Self::new(-self.c0, -self.c1)
+    }
+}
+
+impl<R: Reducer> AddAssign for Fp2<R> {
+    #[inline(always)]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<R: Reducer> SubAssign for Fp2<R> {
+    #[inline(always)]
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<R: Reducer> MulAssign for Fp2<R> {
+    #[inline(always)]
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+
+    fn random_elements<R: Reducer>(n: usize) -> Vec<Fp2<R>> {
+        R::random(2 * n)
+            .into_iter()
+            .tuples()
+            .map(|(c0, c1)| Fp2::new(FieldElement::new(c0), FieldElement::new(c1)))
+            .collect()
+    }
+
+    fn pow<R: Reducer>(mut base: Fp2<R>, mut exp: u64) -> Fp2<R> {
+        let mut acc = Fp2::ONE;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                acc *= base;
+            }
+            base = base.square();
+            exp >>= 1;
+        }
+        acc
+    }
+
+    fn check<R: Reducer>() {
+        let max = FieldElement::<R>::new(P64 - 1);
+        let edge_cases = [Fp2::ZERO, Fp2::ONE, Fp2::new(max, max), Fp2::new(max, max)];
+        for (&x, &y) in random_elements::<R>(1_000)
+            .iter()
+            .chain(&edge_cases)
+            .tuple_windows()
+        {
+            assert_eq!(x * y, x.mul_schoolbook(y));
+            assert_eq!(x * y, y * x);
+            assert_eq!(x.square(), x * x);
+            assert_eq!(x - y + y, x);
+            assert_eq!(-x + x, Fp2::ZERO);
+            assert_eq!((x * y).norm(), x.norm() * y.norm());
+            assert_eq!(x * x.conjugate(), Fp2::from_base(x.norm()));
+            match x.inverse() {
+                Some(inverse) => assert_eq!(x * inverse, Fp2::ONE),
+                None => assert_eq!(x, Fp2::ZERO),
+            }
+        }
+    }
+
+    #[test]
+    fn field_axioms() {
+        check::<Reduce159>();
+        check::<Montgomery>();
+        check::<Plantard>();
+    }
+
+    #[test]
+    fn frobenius() {
+        for x in random_elements::<Reduce159>(100) {
+            let mut power = x;
+            for k in 0..5 {
+                assert_eq!(x.frobenius(k), power);
+                power = pow(power, P64);
+            }
+        }
+    }
+
+    #[test]
+    fn nonresidue_has_a_square_root() {
+        let u = Fp2::<Reduce159>::new(FieldElement::ZERO, FieldElement::ONE);
+        assert_eq!(
+            u.square(),
+            Fp2::from_base(FieldElement::new(constants::NONRESIDUE))
+        );
+        assert_eq!(
+            u * u.conjugate(),
+            Fp2::from_base(-FieldElement::new(constants::NONRESIDUE))
+        );
+    }
+}
@@ -0,0 +1,330 @@
+//! The cubic extension of `P64` by a root of x^3 - x - 1
+
+use core::ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub, SubAssign};
+
+use super::*;
+use crate::meta::{FieldMeta, Meta};
+
+/// u^(p^k) for the generator u of the cubic extension and k = 0, 1, 2, as
+/// canonical coefficients
+pub const FROBENIUS_U: [[u64; 3]; 3] = [
+    [0, 1, 0],
+    [
+        0x9352_8dff_f4c6_373d,
+        0x8b79_bf80_a6bd_fc48,
+        0xa304_2afe_90d6_ad26,
+    ],
+    [
+        0x6cad_71ff_0b39_c8c4,
+        0x7486_407e_5942_03b8,
+        0x5cfb_d500_6f29_52db,
+    ],
+];
+
+/// u^(2 * p^k), the squares of `FROBENIUS_U`
+pub const FROBENIUS_U2: [[u64; 3]; 3] = [
+    [0, 0, 1],
+    [
+        0x5cfb_d500_6f29_52dc,
+        0xc9a9_46ff_7a63_1b9f,
+        0x7486_407e_5942_03b8,
+    ],
+    [
+        0xa304_2afe_90d6_ad27,
+        0x3656_b8ff_859c_e462,
+        0x8b79_bf80_a6bd_fc48,
+    ],
+];
+
+/// 3^-1 mod `P64`, for the Toom-3 interpolation
+const INV3: u64 = 0xaaaa_aaaa_0000_0001;
+
+/// An element c0 + c1 * u + c2 * u^2 of F\[u\]/(u^3 - u - 1), over base
+/// elements backed by `R`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Fp3<R: Reducer = NativeReducer> {
+    /// The constant coefficient
+    pub c0: FieldElement<R>,
+    /// The coefficient of u
+    pub c1: FieldElement<R>,
+    /// The coefficient of u^2
+    pub c2: FieldElement<R>,
+}
+
+impl<R: Reducer> Fp3<R> {
+    /// The additive identity
+    pub const ZERO: Self = Self::new(FieldElement::ZERO, FieldElement::ZERO, FieldElement::ZERO);
+    /// The multiplicative identity
+    pub const ONE: Self = Self::new(FieldElement::ONE, FieldElement::ZERO, FieldElement::ZERO);
+
+    /// Creates the element c0 + c1 * u + c2 * u^2
+    pub const fn new(c0: FieldElement<R>, c1: FieldElement<R>, c2: FieldElement<R>) -> Self {
+        Self { c0, c1, c2 }
+    }
+
+    /// Embeds a base field element
+    pub const fn from_base(c0: FieldElement<R>) -> Self {
+        Self::new(c0, FieldElement::ZERO, FieldElement::ZERO)
+    }
+
+    const fn from_canonical(c: [u64; 3]) -> Self {
+        Self::new(
+            FieldElement::new_const(c[0]),
+            FieldElement::new_const(c[1]),
+            FieldElement::new_const(c[2]),
+        )
+    }
+
+    /// Reduces the product coefficients d0 + d1 * u + ... + d4 * u^4, using
+    /// u^3 = u + 1 and u^4 = u^2 + u
+    #[inline(always)]
+    fn reduce(d: [FieldElement<R>; 5]) -> Self {
+        Self::new(d[0] + d[3], d[1] + d[3] + d[4], d[2] + d[4])
+    }
+
+    /// Multiplies by a base field element
+    #[inline(always)]
+    pub fn mul_by_base(self, x: FieldElement<R>) -> Self {
+        Self::new(self.c0 * x, self.c1 * x, self.c2 * x)
+    }
+
+    /// Multiplies with nine base multiplications, as a reference for `mul`
+    #[inline(always)]
+    pub fn mul_schoolbook(self, rhs: Self) -> Self {
+        let (a, b) = (self, rhs);
+        Self::reduce([
+            a.c0 * b.c0,
+            a.c0 * b.c1 + a.c1 * b.c0,
+            a.c0 * b.c2 + a.c1 * b.c1 + a.c2 * b.c0,
+            a.c1 * b.c2 + a.c2 * b.c1,
+            a.c2 * b.c2,
+        ])
+    }
+
+    /// Multiplies with six base multiplications, Karatsuba style
+    #[inline(always)]
+    pub fn mul_karatsuba(self, rhs: Self) -> Self {
+        let (a, b) = (self, rhs);
+        let v0 = a.c0 * b.c0;
+        let v1 = a.c1 * b.c1;
+        let v2 = a.c2 * b.c2;
+        Self::reduce([
+            v0,
+            (a.c0 + a.c1) * (b.c0 + b.c1) - v0 - v1,
+            (a.c0 + a.c2) * (b.c0 + b.c2) - v0 - v2 + v1,
+            (a.c1 + a.c2) * (b.c1 + b.c2) - v1 - v2,
+            v2,
+        ])
+    }
+
+    /// Multiplies by Toom-3 interpolation, with five base multiplications
+    ///
+    /// The operands are evaluated at 0, 1, -1, -2 and infinity, and the product
+    /// interpolated as in Bodrato's sequence. Division by 2 is a halving, but
+    /// division by 3 costs a sixth multiplication.
+    #[inline(always)]
+    pub fn mul_toom3(self, rhs: Self) -> Self {
+        #[inline(always)]
+        fn evaluate<R: Reducer>(x: Fp3<R>) -> [FieldElement<R>; 5] {
+            let even = x.c0 + x.c2;
+            let minus_one = even - x.c1;
+            let minus_two = (minus_one + x.c2).mul_small(2) - x.c0;
+            [x.c0, even + x.c1, minus_one, minus_two, x.c2]
+        }
+        let (a, b) = (evaluate(self), evaluate(rhs));
+        let [r_0, r_1, r_m1, r_m2, r_inf] = core::array::from_fn(|i| a[i] * b[i]);
+
+        let r_3 = (r_m2 - r_1) * FieldElement::new_const(INV3);
+        let r_1 = (r_1 - r_m1).halve();
+        let r_2 = r_m1 - r_0;
+        let r_3 = (r_2 - r_3).halve() + r_inf.mul_small(2);
+        let r_2 = r_2 + r_1 - r_inf;
+        let r_1 = r_1 - r_3;
+        Self::reduce([r_0, r_1, r_2, r_3, r_inf])
+    }
+
+    /// Squares with the six multiplications of `mul`
+    #[inline(always)]
+    pub fn square(self) -> Self {
+        self * self
+    }
+
+    /// Returns the image under the k-th power of Frobenius, x -> x^(p^k)
+    ///
+    /// Frobenius fixes the base field, so only u and u^2 need mapping, and
+    /// their images are precomputed for each k mod 3.
+    #[inline(always)]
+    pub fn frobenius(self, k: usize) -> Self {
+        let k = k % 3;
+        if k == 0 {
+            return self;
+        }
+        Self::from_base(self.c0)
+            + Self::from_canonical(FROBENIUS_U[k]).mul_by_base(self.c1)
+            + Self::from_canonical(FROBENIUS_U2[k]).mul_by_base(self.c2)
+    }
+
+    /// Returns the norm, the product of the three Frobenius conjugates
+    #[inline(always)]
+    pub fn norm(self) -> FieldElement<R> {
+        (self * self.frobenius(1) * self.frobenius(2)).c0
+    }
+
+    /// Returns the multiplicative inverse, or `None` for zero
+    ///
+    /// The product of the other two conjugates divided by the norm leaves a
+    /// single base field inversion.
+    pub fn inverse(self) -> Option<Self> {
+        let others = self.frobenius(1) * self.frobenius(2);
+        let norm_inv = (self * others).c0.inverse()?;
+        Some(others.mul_by_base(norm_inv))
+    }
+}
+
+impl<R: Reducer> Meta for Fp3<R> {
+    const META: FieldMeta = constants::META.extension(3);
+}
+
+impl<R: Reducer> Add for Fp3<R> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.c0 + rhs.c0, self.c1 + rhs.c1, self.c2 + rhs.c2)
+    }
+}
+
+impl<R: Reducer> Sub for Fp3<R> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.c0 - rhs.c0, self.c1 - rhs.c1, self.c2 - rhs.c2)
+    }
+}
+
+impl<R: Reducer> Mul for Fp3<R> {
+    type Output = Self;
+
+    /// Multiplies using `mul_karatsuba`
+    #[inline(always)]
+    fn mul(self, rhs: Self) -> Self {
+        self.mul_karatsuba(rhs)
+    }
+}
+
+impl<R: Reducer> Neg for Fp3<R> {
+    type Output = Self;
+
+    #[inline(always)]
+    fn neg(self) -> Self {
+        Self::new(-self.c0, -self.c1, -self.c2)
+    }
+}
+
+impl<R: Reducer> AddAssign for Fp3<R> {
+    #[inline(always)]
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<R: Reducer> SubAssign for Fp3<R> {
+    #[inline(always)]
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<R: Reducer> MulAssign for Fp3<R> {
+    #[inline(always)]
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+
+    fn random_elements<R: Reducer>(n: usize) -> Vec<Fp3<R>> {
+        R::random(3 * n)
+            .into_iter()
+            .tuples()
+            .map(|(c0, c1, c2)| {
+                Fp3::new(
+                    FieldElement::new(c0),
+                    FieldElement::new(c1),
+                    FieldElement::new(c2),
+                )
+            })
+            .collect()
+    }
+
+    fn pow<R: Reducer>(mut base: Fp3<R>, mut exp: u64) -> Fp3<R> {
+        let mut acc = Fp3::ONE;
+        while exp > 0 {
+            if exp & 1 == 1 {
+                acc *= base;
+            }
+            base = base.square();
+            exp >>= 1;
+        }
+        acc
+    }
+
+    fn check<R: Reducer>() {
+        let max = FieldElement::<R>::new(P64 - 1);
+        let edge_cases = [
+            Fp3::ZERO,
+            Fp3::ONE,
+            Fp3::new(max, max, max),
+            Fp3::new(max, max, max),
+        ];
+        for (&x, &y) in random_elements::<R>(1_000)
+            .iter()
+            .chain(&edge_cases)
+            .tuple_windows()
+        {
+            assert_eq!(x * y, x.mul_schoolbook(y));
+            assert_eq!(x * y, x.mul_toom3(y));
+            assert_eq!(x * y, y * x);
+            assert_eq!(x - y + y, x);
+            assert_eq!(-x + x, Fp3::ZERO);
+            assert_eq!((x * y).norm(), x.norm() * y.norm());
+            match x.inverse() {
+                Some(inverse) => assert_eq!(x * inverse, Fp3::ONE),
+                None => assert_eq!(x, Fp3::ZERO),
+            }
+        }
+    }
+
+    #[test]
+    fn field_axioms() {
+        check::<Reduce159>();
+        check::<Montgomery>();
+        check::<Plantard>();
+    }
+
+    #[test]
+    fn frobenius() {
+        let u = Fp3::<Reduce159>::new(FieldElement::ZERO, FieldElement::ONE, FieldElement::ZERO);
+        assert_eq!(u * u * u, u + Fp3::ONE);
+        for k in 0..3 {
+            assert_eq!(u.frobenius(k), Fp3::from_canonical(FROBENIUS_U[k]));
+            assert_eq!(
+                u.frobenius(k).square(),
+                Fp3::from_canonical(FROBENIUS_U2[k])
+            );
+        }
+        for x in random_elements::<Reduce159>(100) {
+            let mut power = x;
+            for k in 0..7 {
+                assert_eq!(x.frobenius(k), power);
+                power = pow(power, P64);
+            }
+        }
+    }
+}
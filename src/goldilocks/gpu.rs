@@ -0,0 +1,294 @@
+//! Batch arithmetic on canonical `P64` residues in wgpu compute shaders
+//!
+//! A [`Gpu`] uploads whole slices, runs one invocation of `gpu.wgsl` per
+//! element and reads the results back, so its timings include both transfers,
+//! unlike the CPU [`slice`](super::slice) kernels it is benchmarked against.
+//! WGSL has no 64-bit integers, so the shader works on pairs of `u32` limbs
+//! and assembles products from 16 x 16-bit halves. Requires the `gpu` feature.
+
+use core::fmt;
+use std::sync::mpsc;
+
+use wgpu::util::DeviceExt;
+
+/// The invocations per workgroup, as declared in `gpu.wgsl`
+const WORKGROUP_SIZE: u32 = 64;
+
+/// Error returned when no GPU device can be opened
+#[derive(Debug)]
+pub enum GpuError {
+    /// No adapter matched the request
+    Adapter(wgpu::RequestAdapterError),
+    /// The adapter refused to open a device
+    Device(wgpu::RequestDeviceError),
+}
+
+impl fmt::Display for GpuError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Adapter(err) => write!(f, "no GPU adapter: {err}"),
+            Self::Device(err) => write!(f, "no GPU device: {err}"),
+        }
+    }
+}
+
+impl core::error::Error for GpuError {}
+
+/// A device with the compiled kernels of `gpu.wgsl`
+pub struct Gpu {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    info: wgpu::AdapterInfo,
+    add: wgpu::ComputePipeline,
+    mul: wgpu::ComputePipeline,
+    reduce: wgpu::ComputePipeline,
+    /// The most elements one dispatch binds, limited by the 16-byte `u128` inputs
+    max_elements: usize,
+}
+
+impl Gpu {
+    /// Opens the default high-performance adapter, blocking until its device is ready
+    pub fn new() -> Result<Self, GpuError> {
+        let instance = wgpu::Instance::default();
+        let options = wgpu::RequestAdapterOptions {
+            power_preference: wgpu::PowerPreference::HighPerformance,
+            ..Default::default()
+        };
+        let adapter =
+            pollster::block_on(instance.request_adapter(&options)).map_err(GpuError::Adapter)?;
+        let descriptor = wgpu::DeviceDescriptor {
+            label: Some("prime-field"),
+            required_limits: adapter.limits(),
+            ..Default::default()
+        };
+        let (device, queue) =
+            pollster::block_on(adapter.request_device(&descriptor)).map_err(GpuError::Device)?;
+
+        let module = device.create_shader_module(wgpu::include_wgsl!("gpu.wgsl"));
+        let pipeline = |entry_point| {
+            device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: Some(entry_point),
+                layout: None,
+                module: &module,
+                entry_point: Some(entry_point),
+                compilation_options: Default::default(),
+                cache: None,
+            })
+        };
+        let (add, mul, reduce) = (
+            pipeline("add_kernel"),
+            pipeline("mul_kernel"),
+            pipeline("reduce_kernel"),
+        );
+
+        let limits = device.limits();
+        let max_binding = limits
+            .max_storage_buffer_binding_size
+            .min(limits.max_buffer_size);
+        Ok(Self {
+            info: adapter.get_info(),
+            max_elements: (max_binding / 16) as usize,
+            device,
+            queue,
+            add,
+            mul,
+            reduce,
+        })
+    }
+
+    /// Returns the adapter the kernels run on
+    pub fn adapter_info(&self) -> &wgpu::AdapterInfo {
+        &self.info
+    }
+
+    /// Writes the elementwise sums a[i] + b[i] into `dst`
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slices differ in length, or if the device is lost.
+    pub fn add_slices(&self, dst: &mut [u64], a: &[u64], b: &[u64]) {
+        super::slice::assert_same_length(dst, a, b);
+        self.run(
+            &self.add,
+            dst,
+            &[bytemuck::cast_slice(a), bytemuck::cast_slice(b)],
+        );
+    }
+
+    /// Writes the elementwise products a[i] * b[i] into `dst`
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slices differ in length, or if the device is lost.
+    pub fn mul_slices(&self, dst: &mut [u64], a: &[u64], b: &[u64]) {
+        super::slice::assert_same_length(dst, a, b);
+        self.run(
+            &self.mul,
+            dst,
+            &[bytemuck::cast_slice(a), bytemuck::cast_slice(b)],
+        );
+    }
+
+    /// Writes the canonical residue of each `src[i]` into `dst`
+    ///
+    /// # Panics
+    ///
+    /// Panics if the slices differ in length, or if the device is lost.
+    pub fn reduce_slice(&self, dst: &mut [u64], src: &[u128]) {
+        assert_eq!(dst.len(), src.len(), "operands differ in length");
+        self.run(&self.reduce, dst, &[bytemuck::cast_slice(src)]);
+    }
+
+    /// Runs `pipeline` over `dst` in chunks that fit one binding
+    ///
+    /// Each input holds the same number of elements as `dst`, of any width,
+    /// and is bound in order from binding 0; the output is binding 2.
+    fn run(&self, pipeline: &wgpu::ComputePipeline, dst: &mut [u64], inputs: &[&[u8]]) {
+        if dst.is_empty() {
+            return;
+        }
+        let widths = inputs.iter().map(|input| input.len() / dst.len());
+        let widths: Vec<usize> = widths.collect();
+        for (i, chunk) in dst.chunks_mut(self.max_elements).enumerate() {
+            let (start, end) = (i * self.max_elements, i * self.max_elements + chunk.len());
+            let chunk_inputs = inputs
+                .iter()
+                .zip(&widths)
+                .map(|(input, &width)| &input[start * width..end * width]);
+            self.run_chunk(pipeline, chunk, chunk_inputs);
+        }
+    }
+
+    fn run_chunk<'a>(
+        &self,
+        pipeline: &wgpu::ComputePipeline,
+        dst: &mut [u64],
+        inputs: impl Iterator<Item = &'a [u8]>,
+    ) {
+        let input_buffers: Vec<_> = inputs
+            .map(|contents| {
+                self.device
+                    .create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                        label: None,
+                        contents,
+                        usage: wgpu::BufferUsages::STORAGE,
+                    })
+            })
+            .collect();
+        let size = core::mem::size_of_val(dst) as u64;
+        let output = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size,
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC,
+            mapped_at_creation: false,
+        });
+        let staging = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        let entries: Vec<_> = input_buffers
+            .iter()
+            .enumerate()
+            .map(|(binding, buffer)| wgpu::BindGroupEntry {
+                binding: binding as u32,
+                resource: buffer.as_entire_binding(),
+            })
+            .chain([wgpu::BindGroupEntry {
+                binding: 2,
+                resource: output.as_entire_binding(),
+            }])
+            .collect();
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &pipeline.get_bind_group_layout(0),
+            entries: &entries,
+        });
+
+        // wider dispatches than the per-dimension limit continue along y
+        let groups = (dst.len() as u32).div_ceil(WORKGROUP_SIZE);
+        let max_groups = self.device.limits().max_compute_workgroups_per_dimension;
+        let (x, y) = (groups.min(max_groups), groups.div_ceil(max_groups));
+
+        let mut encoder = self.device.create_command_encoder(&Default::default());
+        {
+            let mut pass = encoder.begin_compute_pass(&Default::default());
+            pass.set_pipeline(pipeline);
+            pass.set_bind_group(0, &bind_group, &[]);
+            pass.dispatch_workgroups(x, y, 1);
+        }
+        encoder.copy_buffer_to_buffer(&output, 0, &staging, 0, size);
+        self.queue.submit([encoder.finish()]);
+
+        let (sender, receiver) = mpsc::channel();
+        let slice = staging.slice(..);
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+        self.device
+            .poll(wgpu::PollType::wait_indefinitely())
+            .expect("GPU device lost");
+        receiver
+            .recv()
+            .expect("GPU mapping callback dropped")
+            .expect("GPU buffer mapping failed");
+        let mapped = slice.get_mapped_range().expect("GPU buffer mapping failed");
+        bytemuck::cast_slice_mut(dst).copy_from_slice(&mapped);
+        drop(mapped);
+        staging.unmap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::goldilocks::{add_modulo, mul_modulo, P64};
+    use crate::rand::{random_elements, random_elements_exact};
+
+    #[test]
+    #[ignore = "requires a GPU adapter"]
+    fn kernels_match_reference() {
+        let gpu = Gpu::new().expect("no GPU to run the kernels on");
+
+        let edge_cases = [0, 1, 0xffff_ffff, 1 << 32, 1 << 63, P64 - 2, P64 - 1];
+        let (edge_a, edge_b): (Vec<_>, Vec<_>) = edge_cases
+            .iter()
+            .flat_map(|&x| edge_cases.iter().map(move |&y| (x, y)))
+            .unzip();
+        for len in [0, 1, 1_001] {
+            let a = [&random_elements_exact(len)[..], &edge_a].concat();
+            let b = [&random_elements_exact(len)[..], &edge_b].concat();
+            let mut dst = vec![0; a.len()];
+
+            gpu.add_slices(&mut dst, &a, &b);
+            for ((&d, &x), &y) in dst.iter().zip(&a).zip(&b) {
+                assert_eq!(d, add_modulo(x, y), "{x} + {y}");
+            }
+            gpu.mul_slices(&mut dst, &a, &b);
+            for ((&d, &x), &y) in dst.iter().zip(&a).zip(&b) {
+                assert_eq!(d, mul_modulo(x, y), "{x} * {y}");
+            }
+        }
+
+        let wide: Vec<u128> = [
+            u128::MAX,
+            (P64 as u128) << 64,
+            P64 as u128,
+            u64::MAX as u128,
+        ]
+        .into_iter()
+        .chain(
+            random_elements(1_000)
+                .into_iter()
+                .map(|x| x as u128 * x as u128),
+        )
+        .collect();
+        let mut dst = vec![0; wide.len()];
+        gpu.reduce_slice(&mut dst, &wide);
+        for (&d, &x) in dst.iter().zip(&wide) {
+            assert_eq!(d, (x % P64 as u128) as u64, "{x}");
+        }
+    }
+}
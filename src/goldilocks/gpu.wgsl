@@ -0,0 +1,127 @@
+// Goldilocks arithmetic on canonical residues, one element per invocation
+//
+// WGSL has no 64-bit integers, so a u64 is a vec2<u32>(low, high) and the
+// buffers are flat arrays of u32 limbs, least significant first.
+
+@group(0) @binding(0) var<storage, read> a: array<u32>;
+@group(0) @binding(1) var<storage, read> b: array<u32>;
+@group(0) @binding(2) var<storage, read_write> dst: array<u32>;
+
+// 2^64 - p = 2^32 - 1, which is also 2^64 mod p
+const EPSILON: vec2<u32> = vec2<u32>(0xffffffffu, 0u);
+
+fn add64(x: vec2<u32>, y: vec2<u32>) -> vec2<u32> {
+    let lo = x.x + y.x;
+    return vec2<u32>(lo, x.y + y.y + u32(lo < x.x));
+}
+
+fn sub64(x: vec2<u32>, y: vec2<u32>) -> vec2<u32> {
+    return vec2<u32>(x.x - y.x, x.y - y.y - u32(x.x < y.x));
+}
+
+fn lt64(x: vec2<u32>, y: vec2<u32>) -> bool {
+    return x.y < y.y || (x.y == y.y && x.x < y.x);
+}
+
+// p = 0xffffffff_00000001
+fn at_least_p(x: vec2<u32>) -> bool {
+    return x.y == 0xffffffffu && x.x != 0u;
+}
+
+// A sum that wraps past 2^64 is short by 2^64 ≡ EPSILON, and one at or above
+// p is reduced by wrapping it the same way, so both add EPSILON.
+fn add(x: vec2<u32>, y: vec2<u32>) -> vec2<u32> {
+    let sum = add64(x, y);
+    return select(sum, add64(sum, EPSILON), lt64(sum, x) || at_least_p(sum));
+}
+
+// The full 64-bit product of two u32, from four 16 x 16-bit products
+fn mul32(x: u32, y: u32) -> vec2<u32> {
+    let x0 = x & 0xffffu;
+    let x1 = x >> 16u;
+    let y0 = y & 0xffffu;
+    let y1 = y >> 16u;
+    let p00 = x0 * y0;
+    let p01 = x0 * y1;
+    let p10 = x1 * y0;
+    let p11 = x1 * y1;
+
+    // stays below 2^32, as in schoolbook multiplication
+    let mid = p01 + (p00 >> 16u) + (p10 & 0xffffu);
+    return vec2<u32>((mid << 16u) | (p00 & 0xffffu), p11 + (p10 >> 16u) + (mid >> 16u));
+}
+
+// Reduces x, given as its u32 limbs least significant first, like reduce_limbs32
+//
+// x ≡ a + (b + c) * 2^32 - c - d by 2^64 ≡ 2^32 - 1 and 2^96 ≡ -1; the bit
+// carried out of b + c folds in as 2^32 - 1 once more.
+fn reduce(x: vec4<u32>) -> vec2<u32> {
+    let s = x.y + x.z;
+    let pos = vec2<u32>(x.x, s);
+    let t0 = add64(pos, select(vec2<u32>(0u), EPSILON, s < x.y));
+    let t1 = select(t0, add64(t0, EPSILON), lt64(t0, pos));
+
+    let cd = x.z + x.w;
+    let c_plus_d = vec2<u32>(cd, u32(cd < x.z));
+    let t2 = sub64(t1, c_plus_d);
+    let t3 = select(t2, sub64(t2, EPSILON), lt64(t1, c_plus_d));
+
+    // x - p wraps to x + EPSILON
+    return select(t3, add64(t3, EPSILON), at_least_p(t3));
+}
+
+fn mul(x: vec2<u32>, y: vec2<u32>) -> vec2<u32> {
+    let lo_lo = mul32(x.x, y.x);
+    let lo_hi = mul32(x.x, y.y);
+    let hi_lo = mul32(x.y, y.x);
+    let hi_hi = mul32(x.y, y.y);
+
+    // the middle limb collects three terms, carrying at most 2 into the high half
+    let mid = add64(add64(vec2<u32>(lo_lo.y, 0u), vec2<u32>(lo_hi.x, 0u)), vec2<u32>(hi_lo.x, 0u));
+    let carries = add64(vec2<u32>(lo_hi.y, 0u), add64(vec2<u32>(hi_lo.y, 0u), vec2<u32>(mid.y, 0u)));
+    let hi = add64(hi_hi, carries);
+    return reduce(vec4<u32>(lo_lo.x, mid.x, hi.x, hi.y));
+}
+
+fn load_a(i: u32) -> vec2<u32> {
+    return vec2<u32>(a[2u * i], a[2u * i + 1u]);
+}
+
+fn load_b(i: u32) -> vec2<u32> {
+    return vec2<u32>(b[2u * i], b[2u * i + 1u]);
+}
+
+fn store(i: u32, x: vec2<u32>) {
+    dst[2u * i] = x.x;
+    dst[2u * i + 1u] = x.y;
+}
+
+// Dispatches wider than the per-dimension limit continue along y
+fn element_index(id: vec3<u32>, groups: vec3<u32>) -> u32 {
+    return id.x + id.y * groups.x * 64u;
+}
+
+@compute @workgroup_size(64)
+fn add_kernel(@builtin(global_invocation_id) id: vec3<u32>, @builtin(num_workgroups) groups: vec3<u32>) {
+    let i = element_index(id, groups);
+    if i < arrayLength(&dst) / 2u {
+        store(i, add(load_a(i), load_b(i)));
+    }
+}
+
+@compute @workgroup_size(64)
+fn mul_kernel(@builtin(global_invocation_id) id: vec3<u32>, @builtin(num_workgroups) groups: vec3<u32>) {
+    let i = element_index(id, groups);
+    if i < arrayLength(&dst) / 2u {
+        store(i, mul(load_a(i), load_b(i)));
+    }
+}
+
+// Reads u128 inputs from a as four limbs each
+@compute @workgroup_size(64)
+fn reduce_kernel(@builtin(global_invocation_id) id: vec3<u32>, @builtin(num_workgroups) groups: vec3<u32>) {
+    let i = element_index(id, groups);
+    if i < arrayLength(&dst) / 2u {
+        store(i, reduce(vec4<u32>(a[4u * i], a[4u * i + 1u], a[4u * i + 2u], a[4u * i + 3u])));
+    }
+}
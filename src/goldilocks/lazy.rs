@@ -0,0 +1,93 @@
+//! Accumulation of unreduced products
+
+use core::ops::AddAssign;
+
+use super::{add_winterfell, reduce_u64, P64};
+
+/// 2^128 mod p, for folding the overflow count of the accumulator
+const R128: u64 = P64 - (1 << 32);
+
+/// Computes the full 128-bit product of `a` and `b`, without reduction
+#[inline(always)]
+pub const fn mul_wide(a: u64, b: u64) -> u128 {
+    a as u128 * b as u128
+}
+
+/// An unreduced sum of `u128` terms, such as products from `mul_wide`
+///
+/// Terms are added into a `u128` whose overflows are counted in a `u64`, so
+/// a single product of canonical operands, which is already close to 2^128,
+/// never forces an early reduction. Up to [`LazyAcc::MAX_TERMS`] terms may be
+/// added before `reduce`; beyond that the overflow count itself overflows.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct LazyAcc {
+    lo: u128,
+    hi: u64,
+}
+
+impl LazyAcc {
+    /// The number of terms that can always be added without losing information
+    pub const MAX_TERMS: u64 = u64::MAX;
+
+    /// Returns an empty accumulator
+    pub const fn new() -> Self {
+        Self { lo: 0, hi: 0 }
+    }
+
+    /// Reduces the accumulated sum to its canonical residue
+    #[inline(always)]
+    pub const fn reduce(self) -> u64 {
+        // lo + hi * 2^128, with 2^128 ≡ -2^32
+        add_winterfell(
+            reduce_u64(self.lo),
+            reduce_u64(self.hi as u128 * R128 as u128),
+        )
+    }
+}
+
+impl AddAssign<u128> for LazyAcc {
+    #[inline(always)]
+    fn add_assign(&mut self, term: u128) {
+        let (lo, carry) = self.lo.overflowing_add(term);
+        self.lo = lo;
+        self.hi += carry as u64;
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use itertools::Itertools;
+    use num_bigint::BigUint;
+
+    use super::*;
+    use crate::goldilocks::{add_modulo, mul_modulo};
+    use crate::rand::random_elements;
+
+    #[test]
+    fn lazy_accumulation() {
+        let mut acc = LazyAcc::new();
+        let mut expected = 0;
+        for (&a, &b) in random_elements(1_000).iter().tuple_windows() {
+            acc += mul_wide(a, b);
+            expected = add_modulo(expected, mul_modulo(a, b));
+        }
+        assert_eq!(acc.reduce(), expected);
+        assert_eq!(LazyAcc::default().reduce(), 0);
+    }
+
+    #[test]
+    fn lazy_accumulation_at_max_terms() {
+        // n additions of u128::MAX sum to n * 2^128 - n, i.e. n - 1 overflows
+        let n = LazyAcc::MAX_TERMS - 1;
+        let mut acc = LazyAcc {
+            lo: u128::MAX - n as u128 + 1,
+            hi: n - 1,
+        };
+        acc += u128::MAX;
+        assert_eq!(acc.hi, u64::MAX - 1);
+
+        let sum = BigUint::from(u128::MAX) * LazyAcc::MAX_TERMS;
+        let expected = sum % BigUint::from(P64);
+        assert_eq!(BigUint::from(acc.reduce()), expected);
+    }
+}
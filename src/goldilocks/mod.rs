@@ -0,0 +1,1479 @@
+//! The Goldilocks field `P64` and its reduction strategies
+
+use crate::meta::impl_meta;
+use crate::reduce::*;
+use crate::traits::{PrimeField64, Reducer};
+
+#[cfg(all(
+    feature = "asm",
+    any(
+        target_arch = "x86_64",
+        target_arch = "aarch64",
+        target_arch = "riscv64"
+    )
+))]
+pub mod asm;
+#[cfg(target_arch = "x86_64")]
+pub mod avx2;
+#[cfg(target_arch = "x86_64")]
+pub mod avx512;
+pub mod constants;
+mod element;
+mod fp2;
+mod fp3;
+#[cfg(feature = "gpu")]
+pub mod gpu;
+mod lazy;
+#[cfg(target_arch = "aarch64")]
+pub mod neon;
+pub mod packed;
+#[cfg(feature = "portable-simd")]
+pub mod portable;
+#[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+pub mod simd128;
+pub mod slice;
+mod sqrt_table;
+
+pub use element::*;
+pub use fp2::*;
+pub use fp3::*;
+pub use lazy::*;
+pub use packed::PackedField;
+pub use slice::*;
+pub use sqrt_table::sqrt_table;
+
+// 2^64 - 2^32 + 1
+pub const P64: u64 = 0xffff_ffff_0000_0001;
+pub const P128: u128 = 0xffff_ffff_0000_0001;
+
+/// Performs addition with modulo using `%` operator
+pub const fn add_modulo(x: u64, y: u64) -> u64 {
+    let sum: u128 = x as u128 + y as u128;
+    (sum % P128) as u64
+}
+
+/// Performs addition with subtraction using `u128` type
+pub const fn add_with_sub_u128(x: u64, y: u64) -> u64 {
+    let mut sum: u128 = x as u128 + y as u128;
+    if sum >= P128 {
+        sum -= P128;
+    }
+    sum as u64
+}
+
+/// Performs addition with `.overflowing_sub()`
+pub const fn add_winterfell(x: u64, y: u64) -> u64 {
+    // a + b = a - (p - b)
+    let (x1, c1) = x.overflowing_sub(P64 - y);
+    let adj = 0u32.wrapping_sub(c1 as u32);
+    x1.wrapping_sub(adj as u64)
+}
+
+/// Performs addition with an `if` on the final subtraction of `P64`
+///
+/// The compiler may still lower this to a conditional move.
+pub const fn add_branchy(x: u64, y: u64) -> u64 {
+    let (sum, over) = x.overflowing_add(y);
+    if over || sum >= P64 {
+        sum.wrapping_sub(P64)
+    } else {
+        sum
+    }
+}
+
+/// Performs addition selecting the final subtraction of `P64` with a mask
+pub const fn add_branchless(x: u64, y: u64) -> u64 {
+    let (sum, over) = x.overflowing_add(y);
+    let (reduced, under) = sum.overflowing_sub(P64);
+    // keep the unreduced sum only if it neither overflowed nor reached p
+    let keep = ((under & !over) as u64).wrapping_neg();
+    reduced ^ ((reduced ^ sum) & keep)
+}
+
+/// Performs subtraction with modulo using `%` operator
+pub const fn sub_modulo(x: u64, y: u64) -> u64 {
+    let diff: u128 = x as u128 + P128 - y as u128;
+    (diff % P128) as u64
+}
+
+/// Performs subtraction by adding `P64` back under a borrow mask
+pub const fn sub_branchless(x: u64, y: u64) -> u64 {
+    let (diff, borrow) = x.overflowing_sub(y);
+    diff.wrapping_add(P64 & (borrow as u64).wrapping_neg())
+}
+
+/// Performs subtraction with `.overflowing_sub()`
+pub const fn sub_winterfell(x: u64, y: u64) -> u64 {
+    // on underflow, x - y + 2^64 exceeds the result by 2^64 - p = 2^32 - 1
+    let (x1, c1) = x.overflowing_sub(y);
+    let adj = 0u32.wrapping_sub(c1 as u32);
+    x1.wrapping_sub(adj as u64)
+}
+
+/// Negates a canonical `x`, returning `p - x` except that zero maps to itself
+pub const fn neg(x: u64) -> u64 {
+    if x == 0 {
+        0
+    } else {
+        P64 - x
+    }
+}
+
+/// Negates a canonical `x` like `neg`, masking out `p - 0` instead of branching
+pub const fn neg_branchless(x: u64) -> u64 {
+    (P64 - x) & ((x != 0) as u64).wrapping_neg()
+}
+
+/// Doubles a canonical `x`
+pub const fn double(x: u64) -> u64 {
+    add_winterfell(x, x)
+}
+
+/// Triples a canonical `x`
+pub const fn triple(x: u64) -> u64 {
+    add_winterfell(double(x), x)
+}
+
+/// Computes x / 2 for a canonical `x`
+///
+/// An odd `x` is made even by adding p first; (x + p) / 2 is computed as
+/// x / 2 + (p + 1) / 2 so that the sum cannot overflow.
+pub const fn halve(x: u64) -> u64 {
+    (x >> 1) + ((x & 1) * P64.div_ceil(2))
+}
+
+/// Multiplies a canonical `x` by a small constant `N` with doublings and additions
+///
+/// The loop runs over the bits of `N`, so for a constant `N` it unrolls into a
+/// fixed shift/add sequence with no multiplication.
+pub const fn mul_by_const<const N: u64>(x: u64) -> u64 {
+    let mut acc = 0;
+    let mut bit = u64::BITS - N.leading_zeros();
+    while bit > 0 {
+        bit -= 1;
+        acc = double(acc);
+        if (N >> bit) & 1 == 1 {
+            acc = add_winterfell(acc, x);
+        }
+    }
+    acc
+}
+
+/// Multiplies a canonical `x` by 2^k using shifts
+///
+/// Since 2^96 ≡ -1, 2^k depends only on k mod 192 and the upper half of that
+/// range is a negation; below that, x * 2^k spans at most 160 bits, whose top
+/// 32 bits t fold in as -t * 2^32 by 2^128 ≡ -2^32.
+pub const fn mul_pow2(x: u64, k: u32) -> u64 {
+    let k = k % 192;
+    let (k, negate) = if k >= 96 { (k - 96, true) } else { (k, false) };
+
+    let shifted = (x as u128) << k;
+    let top = if k > 64 { x >> (128 - k) } else { 0 };
+    let low = to_canonical(reduce159_limbs(shifted as u64, (shifted >> 64) as u64));
+    let result = sub_branchless(low, top << 32);
+    if negate {
+        neg(result)
+    } else {
+        result
+    }
+}
+
+/// Performs multiplication with modulo using `%` operator
+pub const fn mul_modulo(x: u64, y: u64) -> u64 {
+    let product: u128 = x as u128 * y as u128;
+    (product % P128) as u64
+}
+
+/// Performs multiplication with `reduce159` as prime-specific modular reduction
+pub const fn mul_reduce159(x: u64, y: u64) -> u64 {
+    let product: u128 = x as u128 * y as u128;
+    reduce159(product)
+}
+
+/// Performs multiplication with `reduce159` and an `if` on the final subtraction of `P64`
+pub const fn mul_branchy(x: u64, y: u64) -> u64 {
+    to_canonical(reduce159(x as u128 * y as u128))
+}
+
+/// Performs multiplication with `reduce159` and a mask on the final subtraction of `P64`
+pub const fn mul_branchless(x: u64, y: u64) -> u64 {
+    to_canonical_branchless(reduce159(x as u128 * y as u128))
+}
+
+/// Computes a * b + c with a single reduction
+///
+/// For canonical operands the sum is at most (p - 1)^2 + p - 1 < 2^128, so
+/// it fits one `u128` accumulation.
+pub const fn mul_add(a: u64, b: u64, c: u64) -> u64 {
+    reduce_u64(a as u128 * b as u128 + c as u128)
+}
+
+/// Performs multiplication by a `u32`, whose product fits in 96 bits and
+/// reduces with `reduce96`
+pub const fn mul_small(x: u64, y: u32) -> u64 {
+    reduce96(x as u128 * y as u128)
+}
+
+/// Multiplies a canonical `x` by the extension field non-residue 7
+///
+/// Computes 8x - x with a shift, so the 67-bit product reduces with
+/// `reduce96` without any multiplication.
+pub const fn mul_by_nonresidue(x: u64) -> u64 {
+    reduce96(((x as u128) << 3) - x as u128)
+}
+
+/// Performs multiplication using only 32 × 32 → 64-bit multiplications
+///
+/// This avoids `u128` entirely, which lowers to slow libcalls on targets
+/// without a native 64 × 64 → 128-bit multiply.
+pub const fn mul_limbs32(x: u64, y: u64) -> u64 {
+    let (lo, hi) = mul_wide_limbs32(x, y);
+    reduce159_limbs(lo, hi)
+}
+
+/// Computes the 128-bit product as its low and high halves using 32-bit limbs
+#[inline(always)]
+pub const fn mul_wide_limbs32(x: u64, y: u64) -> (u64, u64) {
+    const LOWER_MASK: u64 = 0xffff_ffff;
+
+    let (x0, x1) = (x & LOWER_MASK, x >> 32);
+    let (y0, y1) = (y & LOWER_MASK, y >> 32);
+
+    let p00 = x0 * y0;
+    let p01 = x0 * y1;
+    let p10 = x1 * y0;
+    let p11 = x1 * y1;
+
+    // the middle partial products may carry into bit 96 of the product
+    let (mid, mid_carry) = p01.overflowing_add(p10);
+    let (lo, lo_carry) = p00.overflowing_add(mid << 32);
+    let hi = p11 + (mid >> 32) + ((mid_carry as u64) << 32) + lo_carry as u64;
+    (lo, hi)
+}
+
+/// Squares a canonical `x` using 32-bit limbs
+///
+/// The two middle partial products of `mul_wide_limbs32` coincide, so this
+/// needs three 32 × 32 → 64-bit multiplications instead of four.
+pub const fn square(x: u64) -> u64 {
+    let (lo, hi) = square_wide_limbs32(x);
+    to_canonical(reduce159_limbs(lo, hi))
+}
+
+/// Computes the 128-bit square as its low and high halves using 32-bit limbs
+#[inline(always)]
+pub const fn square_wide_limbs32(x: u64) -> (u64, u64) {
+    const LOWER_MASK: u64 = 0xffff_ffff;
+
+    let (x0, x1) = (x & LOWER_MASK, x >> 32);
+
+    let p00 = x0 * x0;
+    let p01 = x0 * x1;
+    let p11 = x1 * x1;
+
+    // doubling the middle partial product may carry into bit 96 of the square
+    let (mid, mid_carry) = (p01 << 1, p01 >> 63);
+    let (lo, lo_carry) = p00.overflowing_add(mid << 32);
+    let hi = p11 + (mid >> 32) + (mid_carry << 32) + lo_carry as u64;
+    (lo, hi)
+}
+
+/// Performs multiplication with `reduce_montgomery` as prime-specific modular reduction
+///
+/// The result is x * y * 2^-64, i.e. a product in Montgomery form; see
+/// [`MontgomeryForm`] for a type that keeps track of this.
+pub const fn mul_reduce_montgomery(x: u64, y: u64) -> u64 {
+    let product: u128 = x as u128 * y as u128;
+    reduce_montgomery(product)
+}
+
+/// Performs multiplication with `reduce_barrett` and the precomputed μ for `P64`
+pub const fn mul_reduce_barrett(x: u64, y: u64) -> u64 {
+    let product: u128 = x as u128 * y as u128;
+    reduce_barrett(product)
+}
+
+/// Performs multiplication with `reduce_crandall`, folding by multiples of ε = 2^32 - 1
+pub const fn mul_reduce_crandall(x: u64, y: u64) -> u64 {
+    let product: u128 = x as u128 * y as u128;
+    reduce_crandall(product)
+}
+
+/// Performs multiplication with `reduce_plantard` on balanced representatives
+///
+/// The result is x * y * 2^-32, i.e. a product in the form of [`Plantard`],
+/// which scales residues by -2^128 ≡ 2^32.
+pub const fn mul_reduce_plantard(x: u64, y: u64) -> u64 {
+    let product = to_signed(x) as i128 * to_signed(y) as i128;
+    from_signed(reduce_plantard(product))
+}
+
+/// (2^64)^2 mod p, used to move canonical values into Montgomery form
+pub const R2: u64 = 0xffff_fffe_0000_0001;
+
+/// Moves a canonical `x` into Montgomery form, x * 2^64 mod p
+#[inline(always)]
+pub const fn to_montgomery(x: u64) -> u64 {
+    reduce_montgomery(x as u128 * R2 as u128)
+}
+
+/// Moves `x` out of Montgomery form, x * 2^-64 mod p
+#[inline(always)]
+pub const fn from_montgomery(x: u64) -> u64 {
+    reduce_montgomery(x as u128)
+}
+
+/// Returns the balanced representative of a canonical `x`, in `[-(p - 1) / 2, (p - 1) / 2]`
+pub const fn to_signed(x: u64) -> i64 {
+    if x > P64 / 2 {
+        -((P64 - x) as i64)
+    } else {
+        x as i64
+    }
+}
+
+/// Maps any `i64` to its canonical residue, so that negative `x` becomes `p - |x|`
+pub const fn from_signed(x: i64) -> u64 {
+    // |x| <= 2^63 < p, so neither branch needs a further reduction
+    if x < 0 {
+        P64 - x.unsigned_abs()
+    } else {
+        x as u64
+    }
+}
+
+/// Raises a canonical `x` to the power `exp` by left-to-right square-and-multiply
+///
+/// Scanning from the most significant bit, every multiplication is by `x` itself.
+pub const fn pow(x: u64, exp: u64) -> u64 {
+    let mut acc = 1;
+    let mut bit = u64::BITS - exp.leading_zeros();
+    while bit > 0 {
+        bit -= 1;
+        acc = reduce_u64(acc as u128 * acc as u128);
+        if (exp >> bit) & 1 == 1 {
+            acc = reduce_u64(acc as u128 * x as u128);
+        }
+    }
+    acc
+}
+
+/// Raises a canonical `x` to the power `exp` with a fixed window of `W` bits
+///
+/// Precomputes x^0 through x^(2^W - 1), then spends W squarings and one
+/// multiplication per window; `W` must be between 1 and 8.
+pub const fn pow_window<const W: u32>(x: u64, exp: u64) -> u64 {
+    assert!(W >= 1 && W <= 8, "window must be between 1 and 8 bits");
+
+    let mut table = [1u64; 256];
+    let mut i = 1;
+    while i < 1 << W {
+        table[i] = reduce_u64(table[i - 1] as u128 * x as u128);
+        i += 1;
+    }
+
+    let mask = (1 << W) - 1;
+    let mut window = (u64::BITS - exp.leading_zeros()).div_ceil(W);
+    let mut acc = 1;
+    while window > 0 {
+        window -= 1;
+        acc = exp_power_of_2(acc, W);
+        let digit = (exp >> (window * W)) & mask;
+        acc = reduce_u64(acc as u128 * table[digit as usize] as u128);
+    }
+    acc
+}
+
+/// Raises a canonical `x` to the power `exp` by right-to-left square-and-multiply
+pub const fn pow_right_to_left(x: u64, mut exp: u64) -> u64 {
+    let mut base = x;
+    let mut acc = 1;
+    while exp > 0 {
+        if exp & 1 == 1 {
+            acc = reduce_u64(acc as u128 * base as u128);
+        }
+        base = reduce_u64(base as u128 * base as u128);
+        exp >>= 1;
+    }
+    acc
+}
+
+/// Raises a canonical `x` to a short power `exp`, as in S-boxes like x^7
+///
+/// Left-to-right like `pow`, but the loop runs over at most 32 bits and
+/// intermediate values are left unreduced modulo p.
+#[inline]
+pub const fn pow_u32(x: u64, exp: u32) -> u64 {
+    let mut acc = 1;
+    let mut bit = u32::BITS - exp.leading_zeros();
+    while bit > 0 {
+        bit -= 1;
+        acc = reduce159(acc as u128 * acc as u128);
+        if (exp >> bit) & 1 == 1 {
+            acc = reduce159(acc as u128 * x as u128);
+        }
+    }
+    to_canonical(acc)
+}
+
+/// Computes x^3 for a canonical `x` in two multiplications
+///
+/// The square is left unreduced modulo p, since `reduce159` accepts any product.
+#[inline]
+pub const fn cube(x: u64) -> u64 {
+    let x2 = reduce159(x as u128 * x as u128);
+    reduce_u64(x2 as u128 * x as u128)
+}
+
+/// Computes x^7 for a canonical `x` in four multiplications, the α = 7 S-box
+///
+/// Follows the addition chain 1, 2, 3, 6, 7 and reduces modulo p only once.
+#[inline]
+pub const fn pow7(x: u64) -> u64 {
+    let x2 = reduce159(x as u128 * x as u128);
+    let x3 = reduce159(x2 as u128 * x as u128);
+    let x6 = reduce159(x3 as u128 * x3 as u128);
+    reduce_u64(x6 as u128 * x as u128)
+}
+
+/// Squares a canonical `x` `k` times, i.e. computes x^(2^k)
+///
+/// Intermediate squares stay below 2^64 but are left unreduced modulo p, since
+/// `reduce159` accepts any 128-bit product; the loop is unrolled by four.
+#[inline]
+pub const fn exp_power_of_2(x: u64, k: u32) -> u64 {
+    const fn square_lazy(x: u64) -> u64 {
+        reduce159(x as u128 * x as u128)
+    }
+
+    let mut x = x;
+    let mut i = 0;
+    while i + 4 <= k {
+        x = square_lazy(square_lazy(square_lazy(square_lazy(x))));
+        i += 4;
+    }
+    while i < k {
+        x = square_lazy(x);
+        i += 1;
+    }
+    to_canonical(x)
+}
+
+/// Returns the Legendre symbol of a canonical `x`: 1, -1 or 0 for zero
+///
+/// By Euler's criterion x^((p - 1) / 2) is 1 for quadratic residues and -1
+/// for non-residues.
+pub const fn legendre(x: u64) -> i8 {
+    match pow(x, (P64 - 1) / 2) {
+        0 => 0,
+        1 => 1,
+        _ => -1,
+    }
+}
+
+/// Returns whether a canonical `x` has a square root, counting zero
+pub const fn is_square(x: u64) -> bool {
+    legendre(x) >= 0
+}
+
+/// Returns a square root of a canonical `x` by Tonelli-Shanks, or `None` for non-squares
+///
+/// With p - 1 = 2^32 * q, the 2^k-th roots of unity that the algorithm would
+/// compute by repeated squaring are read from `constants::ROOTS_OF_UNITY`.
+pub const fn sqrt(x: u64) -> Option<u64> {
+    const Q: u64 = (P64 - 1) >> constants::TWO_ADICITY;
+
+    if x == 0 {
+        return Some(0);
+    }
+
+    // invariant: r^2 = t * x, where t has order 2^i for some i < m
+    let mut m = constants::TWO_ADICITY;
+    let mut t = pow(x, Q);
+    let mut r = pow(x, Q.div_ceil(2));
+    while t != 1 {
+        // find the order 2^i of t; reaching 2^m means x is not a square
+        let mut i = 0;
+        let mut t2i = t;
+        while t2i != 1 {
+            t2i = reduce_u64(t2i as u128 * t2i as u128);
+            i += 1;
+            if i == m {
+                return None;
+            }
+        }
+
+        // b has order 2^(i + 1), so multiplying t by b^2 lowers its order
+        let b = constants::ROOTS_OF_UNITY[i as usize + 1];
+        m = i;
+        t = reduce_u64(t as u128 * reduce_u64(b as u128 * b as u128) as u128);
+        r = reduce_u64(r as u128 * b as u128);
+    }
+    Some(r)
+}
+
+/// Returns an `n`-th root of a canonical `x`, or `None` if it has none
+///
+/// The odd part m of n divides the squarefree 2^32 - 1, so m is invertible
+/// modulo (p - 1) / m and one exponentiation finds an m-th root. The factor
+/// 2^a is then taken by `a` square roots, which cannot fail: for k ≤ 32, both
+/// square roots of a 2^k-th power are 2^(k - 1)-th powers.
+///
+/// # Panics
+///
+/// Panics if `n` does not divide `p - 1`.
+pub const fn nth_root(x: u64, n: u64) -> Option<u64> {
+    assert!(n != 0 && (P64 - 1).is_multiple_of(n), "n must divide p - 1");
+
+    if x == 0 {
+        return Some(0);
+    }
+
+    let a = n.trailing_zeros();
+    let m = n >> a;
+    let s = (P64 - 1) / m;
+    if m > 1 && pow(x, s) != 1 {
+        return None;
+    }
+
+    let mut root = pow(x, inverse_mod(m, s));
+    let mut i = 0;
+    while i < a {
+        root = match sqrt(root) {
+            Some(root) => root,
+            None => return None,
+        };
+        i += 1;
+    }
+    Some(root)
+}
+
+/// Returns the multiplicative order of a canonical `x`, or `None` for zero
+///
+/// Starting from p - 1, each prime factor q is divided out of the order for
+/// as long as x raised to the remaining order over q is still 1.
+pub const fn multiplicative_order(x: u64) -> Option<u64> {
+    if x == 0 {
+        return None;
+    }
+
+    let mut order = P64 - 1;
+    let mut i = 0;
+    while i < constants::FACTORS_OF_P_MINUS_1.len() {
+        let (q, e) = constants::FACTORS_OF_P_MINUS_1[i];
+        let mut j = 0;
+        while j < e && pow(x, order / q) == 1 {
+            order /= q;
+            j += 1;
+        }
+        i += 1;
+    }
+    Some(order)
+}
+
+/// Returns e < 2^k with g^e = x for the 2^k-th root of unity g = `ROOTS_OF_UNITY[k]`
+///
+/// Pohlig-Hellman recovers e one bit at a time: with the bits found so far
+/// cancelled, raising to 2^(k - 1 - i) leaves 1 or -1 depending on bit i.
+/// Returns `None` if `x` is not in the subgroup of order 2^k.
+///
+/// # Panics
+///
+/// Panics if `k` exceeds the two-adicity 32.
+pub const fn log2_subgroup_dlog(x: u64, k: u32) -> Option<u64> {
+    use constants::{ROOTS_OF_UNITY_INV, TWO_ADICITY};
+
+    assert!(k <= TWO_ADICITY, "k must not exceed the two-adicity");
+
+    if x == 0 || exp_power_of_2(x, k) != 1 {
+        return None;
+    }
+
+    // y = x * g^(-e) for the low bits of e found so far
+    let mut y = x;
+    let mut e = 0;
+    let mut i = 0;
+    while i < k {
+        if exp_power_of_2(y, k - 1 - i) != 1 {
+            e |= 1 << i;
+            y = reduce_u64(y as u128 * ROOTS_OF_UNITY_INV[(k - i) as usize] as u128);
+        }
+        i += 1;
+    }
+    Some(e)
+}
+
+/// Computes x^(p - 2) for a canonical `x` with a dedicated addition chain
+///
+/// The chain for p - 2 = 2^64 - 2^32 - 1 costs 63 squarings and 9
+/// multiplications, where plain square-and-multiply needs 62 of the latter.
+pub const fn pow_p_minus_2(x: u64) -> u64 {
+    const fn mul(x: u64, y: u64) -> u64 {
+        reduce_u64(x as u128 * y as u128)
+    }
+
+    // each tk is x^(2^k - 1)
+    let t2 = mul(exp_power_of_2(x, 1), x);
+    let t3 = mul(exp_power_of_2(t2, 1), x);
+    let t6 = mul(exp_power_of_2(t3, 3), t3);
+    let t12 = mul(exp_power_of_2(t6, 6), t6);
+    let t24 = mul(exp_power_of_2(t12, 12), t12);
+    let t30 = mul(exp_power_of_2(t24, 6), t6);
+    let t31 = mul(exp_power_of_2(t30, 1), x);
+
+    // x^(2^32 - 2), then x^((2^32 - 2) * 2^32 + 2^32 - 1)
+    let u = exp_power_of_2(t31, 1);
+    let t32 = mul(u, x);
+    mul(exp_power_of_2(u, 32), t32)
+}
+
+/// Computes x^(p - 2) = x^-1 by Fermat's little theorem, mapping zero to zero
+pub const fn inverse_fermat(x: u64) -> u64 {
+    pow_p_minus_2(x)
+}
+
+/// Computes x^-1 with the extended Euclidean algorithm, mapping zero to zero
+pub const fn inverse_xgcd(x: u64) -> u64 {
+    inverse_mod(x, P64)
+}
+
+/// Computes the inverse of `x` modulo any `m` coprime to it
+///
+/// Only the Bezout coefficient of `x` is tracked; it stays within `(-m, m)`.
+#[inline(always)]
+const fn inverse_mod(x: u64, m: u64) -> u64 {
+    let (mut r0, mut r1) = (m, x);
+    let (mut t0, mut t1) = (0i128, 1i128);
+    while r1 != 0 {
+        let q = r0 / r1;
+        (r0, r1) = (r1, r0 - q * r1);
+        (t0, t1) = (t1, t0 - q as i128 * t1);
+    }
+    if t0 < 0 {
+        (t0 + m as i128) as u64
+    } else {
+        t0 as u64
+    }
+}
+
+/// Computes x^-1 with Bernstein-Yang divsteps, mapping zero to zero
+///
+/// Runs a fixed number of iterations and selects with masks instead of
+/// branching, so the running time does not depend on `x`.
+pub const fn inverse_safegcd(x: u64) -> u64 {
+    // the bound on divsteps needed for 64-bit inputs, from Bernstein-Yang section 11
+    const ITERATIONS: u32 = (49 * 64 + 80) / 17;
+
+    // invariants: f = d * x and g = e * x modulo p, with f odd
+    let (mut f, mut g) = (P64 as i128, x as i128);
+    let (mut d, mut e) = (0u64, 1u64);
+    let mut delta = 1i64;
+    let mut i = 0;
+    while i < ITERATIONS {
+        // if delta > 0 and g is odd, replace (delta, f, g) with (-delta, g, -f)
+        let swap = ((delta > 0) as u64 & g as u64 & 1).wrapping_neg();
+        let swap128 = swap as i64 as i128;
+        (f, g) = (f ^ ((f ^ g) & swap128), g ^ ((g ^ -f) & swap128));
+        (d, e) = (d ^ ((d ^ e) & swap), e ^ ((e ^ neg_branchless(d)) & swap));
+        delta ^= (delta ^ -delta) & swap as i64;
+
+        // then halve g, after adding f if g is odd
+        let odd = (g as u64 & 1).wrapping_neg();
+        g = (g + (f & odd as i64 as i128)) >> 1;
+        e = halve(add_winterfell(e, d & odd));
+        delta += 1;
+        i += 1;
+    }
+
+    // now g = 0 and f = ±1, so ±d is the inverse
+    let negative = (f >> 127) as u64;
+    d ^ ((d ^ neg_branchless(d)) & negative)
+}
+
+/// Returns the multiplicative inverse of a canonical `x`, or `None` for zero
+pub const fn inverse(x: u64) -> Option<u64> {
+    if x == 0 {
+        None
+    } else {
+        Some(inverse_fermat(x))
+    }
+}
+
+/// Divides a canonical `x` by `y` with the variable-time `inverse_xgcd`
+///
+/// # Panics
+///
+/// Panics if `y` is zero.
+pub const fn div(x: u64, y: u64) -> u64 {
+    assert!(y != 0, "division by zero");
+    reduce_u64(x as u128 * inverse_xgcd(y) as u128)
+}
+
+/// Divides a canonical `x` by `y`, or returns `None` if `y` is zero
+pub const fn checked_div(x: u64, y: u64) -> Option<u64> {
+    if y == 0 {
+        None
+    } else {
+        Some(div(x, y))
+    }
+}
+
+/// Adds and reduces using the `%` operator
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Modulo;
+
+impl PrimeField64 for Modulo {
+    const MODULUS: u64 = P64;
+    const NAME: &'static str = "mod";
+
+    #[inline(always)]
+    fn add(x: u64, y: u64) -> u64 {
+        add_modulo(x, y)
+    }
+
+    #[inline(always)]
+    fn sub(x: u64, y: u64) -> u64 {
+        sub_modulo(x, y)
+    }
+
+    #[inline(always)]
+    fn mul_add(a: u64, b: u64, c: u64) -> u64 {
+        ((a as u128 * b as u128 + c as u128) % P128) as u64
+    }
+
+    #[inline(always)]
+    fn reduce(x: u128) -> u64 {
+        (x % P128) as u64
+    }
+}
+
+impl Reducer for Modulo {}
+
+/// Adds with `u128` subtraction and reduces using the prime-specific `reduce159`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Reduce159;
+
+impl PrimeField64 for Reduce159 {
+    const MODULUS: u64 = P64;
+    const NAME: &'static str = "reduce159";
+
+    #[inline(always)]
+    fn add(x: u64, y: u64) -> u64 {
+        add_with_sub_u128(x, y)
+    }
+
+    #[inline(always)]
+    fn sub(x: u64, y: u64) -> u64 {
+        sub_branchless(x, y)
+    }
+
+    #[inline(always)]
+    fn mul_add(a: u64, b: u64, c: u64) -> u64 {
+        mul_add(a, b, c)
+    }
+
+    #[inline(always)]
+    fn reduce(x: u128) -> u64 {
+        reduce_u64(x)
+    }
+}
+
+impl Reducer for Reduce159 {}
+
+/// Adds with `u128` subtraction and reduces using `reduce_barrett`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Barrett;
+
+impl PrimeField64 for Barrett {
+    const MODULUS: u64 = P64;
+    const NAME: &'static str = "barrett";
+
+    #[inline(always)]
+    fn add(x: u64, y: u64) -> u64 {
+        add_with_sub_u128(x, y)
+    }
+
+    #[inline(always)]
+    fn sub(x: u64, y: u64) -> u64 {
+        sub_branchless(x, y)
+    }
+
+    #[inline(always)]
+    fn reduce(x: u128) -> u64 {
+        reduce_barrett(x)
+    }
+}
+
+impl Reducer for Barrett {}
+
+/// Adds with `u128` subtraction and reduces using `reduce_crandall`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Crandall;
+
+impl PrimeField64 for Crandall {
+    const MODULUS: u64 = P64;
+    const NAME: &'static str = "crandall";
+
+    #[inline(always)]
+    fn add(x: u64, y: u64) -> u64 {
+        add_with_sub_u128(x, y)
+    }
+
+    #[inline(always)]
+    fn sub(x: u64, y: u64) -> u64 {
+        sub_branchless(x, y)
+    }
+
+    #[inline(always)]
+    fn reduce(x: u128) -> u64 {
+        reduce_crandall(x)
+    }
+}
+
+impl Reducer for Crandall {}
+
+/// Stores elements in Montgomery form, adds with `add_winterfell` and reduces
+/// using `reduce_montgomery`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Montgomery;
+
+impl PrimeField64 for Montgomery {
+    const MODULUS: u64 = P64;
+    const NAME: &'static str = "montgomery";
+
+    #[inline(always)]
+    fn add(x: u64, y: u64) -> u64 {
+        add_winterfell(x, y)
+    }
+
+    #[inline(always)]
+    fn sub(x: u64, y: u64) -> u64 {
+        sub_winterfell(x, y)
+    }
+
+    #[inline(always)]
+    fn reduce(x: u128) -> u64 {
+        reduce_montgomery(x)
+    }
+}
+
+impl Reducer for Montgomery {
+    /// 2^64 mod p
+    const REPR_FACTOR: u64 = 0xffff_ffff;
+
+    #[inline(always)]
+    fn to_repr(x: u64) -> u64 {
+        to_montgomery(x)
+    }
+
+    #[inline(always)]
+    fn from_repr(x: u64) -> u64 {
+        from_montgomery(x)
+    }
+}
+
+/// Stores elements scaled by 2^32 and multiplies with signed Plantard reduction
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Plantard;
+
+impl PrimeField64 for Plantard {
+    const MODULUS: u64 = P64;
+    const NAME: &'static str = "plantard";
+
+    #[inline(always)]
+    fn add(x: u64, y: u64) -> u64 {
+        add_with_sub_u128(x, y)
+    }
+
+    #[inline(always)]
+    fn sub(x: u64, y: u64) -> u64 {
+        sub_branchless(x, y)
+    }
+
+    #[inline(always)]
+    fn mul(x: u64, y: u64) -> u64 {
+        mul_reduce_plantard(x, y)
+    }
+
+    #[inline(always)]
+    fn reduce(x: u128) -> u64 {
+        // from x >= 2^127, x as i128 is x - 2^128 ≡ x + 2^32, which comes out
+        // of the reduction as one too many
+        from_signed(reduce_plantard(x as i128) - (x >> 127) as i64)
+    }
+}
+
+impl Reducer for Plantard {
+    /// -2^128 mod p
+    const REPR_FACTOR: u64 = 1 << 32;
+
+    #[inline(always)]
+    fn to_repr(x: u64) -> u64 {
+        mul_pow2(x, 32)
+    }
+
+    #[inline(always)]
+    fn from_repr(x: u64) -> u64 {
+        Self::reduce(x as u128)
+    }
+}
+
+/// Multiplies with 32-bit limbs via `mul_limbs32`, never using `u128` arithmetic
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Limbs32;
+
+impl PrimeField64 for Limbs32 {
+    const MODULUS: u64 = P64;
+    const NAME: &'static str = "limbs32";
+
+    #[inline(always)]
+    fn add(x: u64, y: u64) -> u64 {
+        add_winterfell(x, y)
+    }
+
+    #[inline(always)]
+    fn sub(x: u64, y: u64) -> u64 {
+        sub_branchless(x, y)
+    }
+
+    #[inline(always)]
+    fn mul(x: u64, y: u64) -> u64 {
+        to_canonical(mul_limbs32(x, y))
+    }
+
+    #[inline(always)]
+    fn square(x: u64) -> u64 {
+        square(x)
+    }
+
+    #[inline(always)]
+    fn mul_add(a: u64, b: u64, c: u64) -> u64 {
+        let (lo, hi) = mul_wide_limbs32(a, b);
+        let (lo, carry) = lo.overflowing_add(c);
+        to_canonical(reduce159_limbs(lo, hi + carry as u64))
+    }
+
+    #[inline(always)]
+    fn reduce(x: u128) -> u64 {
+        to_canonical(reduce159_limbs(x as u64, (x >> 64) as u64))
+    }
+}
+
+impl Reducer for Limbs32 {}
+
+/// The default strategy backing [`FieldElement`] on this target
+///
+/// This is [`Reduce159`] on 64-bit targets and [`Limbs32`] on narrower ones,
+/// where `u128` multiplication is emulated.
+#[cfg(target_pointer_width = "64")]
+pub type NativeReducer = Reduce159;
+
+/// The default strategy backing [`FieldElement`] on this target
+///
+/// This is [`Reduce159`] on 64-bit targets and [`Limbs32`] on narrower ones,
+/// where `u128` multiplication is emulated.
+#[cfg(not(target_pointer_width = "64"))]
+pub type NativeReducer = Limbs32;
+
+impl_meta!(constants::META; Modulo, Reduce159, Barrett, Crandall, Montgomery, Plantard, Limbs32);
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use itertools::Itertools;
+
+    use ::rand::{thread_rng, Rng};
+
+    use super::*;
+    use crate::rand::random_elements;
+
+    #[test]
+    fn add_equivalence() {
+        let n_operations = 1_000;
+        let operands = random_elements(n_operations + 1);
+        for (&x, &y) in operands.iter().tuple_windows() {
+            assert_eq!(add_modulo(x, y), add_with_sub_u128(x, y));
+            assert_eq!(add_modulo(x, y), add_winterfell(x, y));
+        }
+        assert_eq!(add_with_sub_u128(P64 - 1, 1), 0);
+    }
+
+    #[test]
+    fn sub_equivalence() {
+        let n_operations = 1_000;
+        let operands = random_elements(n_operations + 1);
+        let edge_cases = [0, 1, P64 - 1];
+        for (&x, &y) in operands.iter().chain(&edge_cases).tuple_windows() {
+            assert_eq!(sub_modulo(x, y), sub_branchless(x, y));
+            assert_eq!(sub_modulo(x, y), sub_winterfell(x, y));
+            assert_eq!(add_modulo(sub_modulo(x, y), y), x);
+        }
+        assert_eq!(sub_branchless(0, 1), P64 - 1);
+        assert_eq!(sub_winterfell(0, P64 - 1), 1);
+    }
+
+    #[test]
+    fn negation() {
+        for (x, minus_x) in [(0, 0), (1, P64 - 1), (P64 - 1, 1), (P64 / 2, P64 / 2 + 1)] {
+            assert_eq!(neg(x), minus_x);
+            assert_eq!(neg_branchless(x), minus_x);
+        }
+        for x in random_elements(1_000) {
+            assert_eq!(neg(x), sub_modulo(0, x));
+            assert_eq!(neg_branchless(x), neg(x));
+            assert_eq!(add_modulo(x, neg(x)), 0);
+        }
+    }
+
+    #[test]
+    fn small_constant_multiplication() {
+        for x in random_elements(1_000)
+            .into_iter()
+            .chain([0, 1, P64 / 2, P64 - 1])
+        {
+            assert_eq!(double(x), mul_modulo(x, 2));
+            assert_eq!(triple(x), mul_modulo(x, 3));
+            assert_eq!(mul_by_const::<0>(x), 0);
+            assert_eq!(mul_by_const::<1>(x), x);
+            assert_eq!(mul_by_const::<7>(x), mul_modulo(x, 7));
+            assert_eq!(mul_by_const::<23>(x), mul_modulo(x, 23));
+            assert_eq!(mul_by_nonresidue(x), mul_modulo(x, constants::NONRESIDUE));
+            assert_eq!(halve(x), mul_modulo(x, inverse(2).unwrap()));
+            assert_eq!(double(halve(x)), x);
+            assert_eq!(
+                mul_by_const::<{ u32::MAX as u64 }>(x),
+                mul_modulo(x, u32::MAX as u64)
+            );
+        }
+    }
+
+    #[test]
+    fn power_of_two_multiplication() {
+        for x in random_elements(100).into_iter().chain([0, 1, P64 - 1]) {
+            let mut expected = x;
+            for k in 0..192 {
+                assert_eq!(mul_pow2(x, k), expected, "x = {x}, k = {k}");
+                assert_eq!(mul_pow2(x, k), mul_modulo(x, pow(2, k as u64)));
+                expected = double(expected);
+            }
+            assert_eq!(mul_pow2(x, 192), x);
+        }
+    }
+
+    #[test]
+    fn mul_equivalence() {
+        let n_operations = 1_000;
+        let operands = random_elements(n_operations + 1);
+        for (&x, &y) in operands.iter().tuple_windows() {
+            let expected_product = mul_modulo(x, y);
+            assert_eq!(expected_product, mul_reduce159(x, y));
+            assert_eq!(expected_product, to_canonical(mul_limbs32(x, y)));
+            assert_eq!(x as u128 * y as u128, {
+                let (lo, hi) = mul_wide_limbs32(x, y);
+                (hi as u128) << 64 | lo as u128
+            });
+
+            let expected_montgomery_product = reduce_montgomery(expected_product as u128);
+            let actual_montgomery_product = mul_reduce_montgomery(x, y);
+            assert!(montgomery_equals(
+                expected_montgomery_product,
+                actual_montgomery_product
+            ));
+        }
+    }
+
+    #[test]
+    fn montgomery_conversion() {
+        assert_eq!(R2, montgomery_r2(P64));
+        for (&x, &y) in random_elements(1_000)
+            .iter()
+            .chain(&[0, 1, P64 - 1])
+            .tuple_windows()
+        {
+            assert_eq!(to_montgomery(x), mul_modulo(x, Montgomery::REPR_FACTOR));
+            assert_eq!(from_montgomery(to_montgomery(x)), x);
+            let product = mul_reduce_montgomery(to_montgomery(x), to_montgomery(y));
+            assert_eq!(from_montgomery(product), mul_modulo(x, y));
+        }
+    }
+
+    #[test]
+    fn const_evaluation() {
+        const SUM: u64 = add_winterfell(P64 - 1, 2);
+        const PRODUCT: u64 = reduce_u64((P128 - 1) * (P128 - 1));
+        const WIDE: (u64, u64) = mul_wide_limbs32(u64::MAX, u64::MAX);
+        const ROOT: u64 = pow(constants::MULTIPLICATIVE_GENERATOR, (P64 - 1) >> 32);
+        const HALF: Option<u64> = inverse(2);
+
+        assert_eq!(SUM, 1);
+        assert_eq!(PRODUCT, 1);
+        assert_eq!(WIDE, (1, u64::MAX - 1));
+        assert_eq!(ROOT, constants::ROOTS_OF_UNITY[32]);
+        assert_eq!(HALF, Some(P64 / 2 + 1));
+        assert_eq!(inverse(0), None);
+
+        for x in random_elements(100) {
+            assert_eq!(pow(x, 3), mul_modulo(mul_modulo(x, x), x));
+            if let Some(x_inv) = inverse(x) {
+                assert_eq!(mul_modulo(x, x_inv), 1);
+            }
+        }
+    }
+
+    #[test]
+    fn legendre_symbol() {
+        assert_eq!(legendre(0), 0);
+        assert_eq!(legendre(1), 1);
+        // p = 1 mod 8, so -1 and 2 are residues
+        assert_eq!(legendre(P64 - 1), 1);
+        assert_eq!(legendre(2), 1);
+        // a generator of the multiplicative group is never a square
+        assert_eq!(legendre(constants::MULTIPLICATIVE_GENERATOR), -1);
+        assert!(!is_square(constants::ROOTS_OF_UNITY[32]));
+
+        for x in random_elements(1_000) {
+            if x == 0 {
+                continue;
+            }
+            assert!(is_square(mul_modulo(x, x)));
+            let non_residue = mul_modulo(mul_modulo(x, x), constants::MULTIPLICATIVE_GENERATOR);
+            assert_eq!(legendre(non_residue), -1);
+            assert_eq!(legendre(x) * legendre(7), legendre(mul_modulo(x, 7)));
+        }
+    }
+
+    #[test]
+    fn square_root() {
+        assert_eq!(sqrt(0), Some(0));
+        assert_eq!(sqrt(1), Some(1));
+        assert_eq!(sqrt(constants::MULTIPLICATIVE_GENERATOR), None);
+
+        for x in random_elements(1_000).into_iter().chain([1, 2, P64 - 1]) {
+            let root = sqrt(mul_modulo(x, x)).unwrap();
+            assert!(root == x || root == neg(x), "sqrt({x}^2) = {root}");
+            assert_eq!(sqrt(x).is_some(), is_square(x));
+            if let Some(root) = sqrt(x) {
+                assert_eq!(mul_modulo(root, root), x);
+            }
+        }
+    }
+
+    #[test]
+    fn nth_roots() {
+        let divisors = [
+            1,
+            2,
+            3,
+            4,
+            5,
+            6,
+            17,
+            48,
+            257,
+            65537,
+            1 << 32,
+            (1 << 32) - 1,
+            P64 - 1,
+        ];
+        for n in divisors {
+            assert_eq!(nth_root(0, n), Some(0));
+            assert_eq!(pow(nth_root(1, n).unwrap(), n), 1);
+            if n > 1 {
+                assert_eq!(nth_root(constants::MULTIPLICATIVE_GENERATOR, n), None);
+            }
+            for x in random_elements(100) {
+                let x = pow(x, n);
+                let root = nth_root(x, n).unwrap();
+                assert_eq!(pow(root, n), x, "n = {n}");
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "n must divide p - 1")]
+    fn nth_root_of_non_divisor() {
+        nth_root(2, 7);
+    }
+
+    #[test]
+    fn multiplicative_orders() {
+        use constants::{FACTORS_OF_P_MINUS_1, MULTIPLICATIVE_GENERATOR, ROOTS_OF_UNITY};
+
+        assert_eq!(multiplicative_order(0), None);
+        assert_eq!(multiplicative_order(1), Some(1));
+        assert_eq!(multiplicative_order(P64 - 1), Some(2));
+        assert_eq!(
+            multiplicative_order(MULTIPLICATIVE_GENERATOR),
+            Some(P64 - 1)
+        );
+        for (k, &root) in ROOTS_OF_UNITY.iter().enumerate() {
+            assert_eq!(multiplicative_order(root), Some(1 << k));
+        }
+
+        for x in random_elements(100).into_iter().filter(|&x| x != 0) {
+            let order = multiplicative_order(x).unwrap();
+            assert_eq!(pow(x, order), 1);
+            for (q, _) in FACTORS_OF_P_MINUS_1 {
+                if order.is_multiple_of(q) {
+                    assert_ne!(pow(x, order / q), 1);
+                }
+            }
+            let cube_order = if order.is_multiple_of(3) {
+                order / 3
+            } else {
+                order
+            };
+            assert_eq!(multiplicative_order(pow(x, 3)), Some(cube_order));
+        }
+    }
+
+    #[test]
+    fn subgroup_discrete_logs() {
+        use constants::{MULTIPLICATIVE_GENERATOR, ROOTS_OF_UNITY};
+
+        for k in [0, 1, 2, 8, 31, 32] {
+            let g = ROOTS_OF_UNITY[k as usize];
+            let mask = (1u64 << k) - 1;
+            for e in random_elements(100).into_iter().chain([0, 1, mask]) {
+                let e = e & mask;
+                assert_eq!(log2_subgroup_dlog(pow(g, e), k), Some(e), "k = {k}");
+            }
+            assert_eq!(log2_subgroup_dlog(0, k), None);
+        }
+        assert_eq!(log2_subgroup_dlog(MULTIPLICATIVE_GENERATOR, 32), None);
+        assert_eq!(log2_subgroup_dlog(ROOTS_OF_UNITY[9], 8), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "k must not exceed the two-adicity")]
+    fn subgroup_discrete_log_beyond_two_adicity() {
+        log2_subgroup_dlog(1, 33);
+    }
+
+    #[test]
+    fn fermat_inverse() {
+        let edge_cases = [1, 2, 1 << 32, P64 / 2, P64 / 2 + 1, P64 - 2, P64 - 1];
+        for x in random_elements(1_000).into_iter().chain(edge_cases) {
+            let x_inv = inverse_fermat(x);
+            assert_eq!(mul_modulo(x, x_inv), 1);
+            assert_eq!(x_inv, pow(x, P64 - 2));
+            assert_eq!(pow_p_minus_2(x), pow_window::<4>(x, P64 - 2));
+        }
+        assert_eq!(inverse_fermat(0), 0);
+        assert_eq!(inverse_fermat(P64 - 1), P64 - 1);
+    }
+
+    #[test]
+    fn xgcd_inverse() {
+        let edge_cases = [0, 1, 2, 1 << 32, P64 / 2, P64 / 2 + 1, P64 - 2, P64 - 1];
+        for x in random_elements(1_000).into_iter().chain(edge_cases) {
+            assert_eq!(inverse_xgcd(x), inverse_fermat(x));
+        }
+    }
+
+    #[test]
+    fn safegcd_inverse() {
+        let edge_cases = [
+            0,
+            1,
+            2,
+            3,
+            1 << 32,
+            (1 << 32) - 1,
+            P64 / 2,
+            P64 - 2,
+            P64 - 1,
+        ];
+        for x in random_elements(1_000).into_iter().chain(edge_cases) {
+            assert_eq!(inverse_safegcd(x), inverse_fermat(x));
+        }
+    }
+
+    #[test]
+    fn division() {
+        for (&x, &y) in random_elements(1_000).iter().tuple_windows() {
+            assert_eq!(div(mul_modulo(x, y), y), x);
+            assert_eq!(checked_div(x, y), Some(mul_modulo(x, inverse_fermat(y))));
+        }
+        assert_eq!(div(1, P64 - 1), P64 - 1);
+        assert_eq!(div(0, 2), 0);
+        assert_eq!(checked_div(1, 0), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "division by zero")]
+    fn division_by_zero() {
+        div(1, 0);
+    }
+
+    #[test]
+    fn exponentiation() {
+        use num_bigint::BigUint;
+
+        let modulus = BigUint::from(P64);
+        let exponents = [0, 1, 2, P64 - 2, P64 - 1, u64::MAX];
+        for (&x, &exp) in random_elements(1_000).iter().tuple_windows() {
+            for exp in exponents.into_iter().chain([exp]) {
+                let expected = BigUint::from(x).modpow(&BigUint::from(exp), &modulus);
+                assert_eq!(BigUint::from(pow(x, exp)), expected);
+                assert_eq!(pow_right_to_left(x, exp), pow(x, exp));
+            }
+        }
+        assert_eq!(pow(0, 0), 1);
+    }
+
+    #[test]
+    fn short_exponentiation() {
+        let exponents = [0, 1, 2, 3, 5, 7, 17, u32::MAX];
+        for (&x, &exp) in random_elements(1_000).iter().tuple_windows() {
+            for exp in exponents.into_iter().chain([exp as u32]) {
+                assert_eq!(pow_u32(x, exp), pow(x, exp as u64));
+            }
+            assert_eq!(pow7(x), pow(x, 7));
+        }
+        for x in [0, 1, P64 - 1] {
+            assert_eq!(pow_u32(x, 7), pow(x, 7));
+            assert_eq!(pow7(x), pow(x, 7));
+        }
+    }
+
+    #[test]
+    fn cube_and_seventh_power() {
+        for x in random_elements(1_000).into_iter().chain([0, 1, 2, P64 - 1]) {
+            assert_eq!(cube(x), mul_modulo(mul_modulo(x, x), x));
+            assert_eq!(pow7(x), mul_modulo(cube(x), mul_modulo(cube(x), x)));
+            assert!(is_canonical(cube(x)) && is_canonical(pow7(x)));
+        }
+    }
+
+    #[test]
+    fn windowed_exponentiation() {
+        let exponents = [0, 1, 15, 16, P64 - 2, u64::MAX];
+        for (&x, &exp) in random_elements(1_000).iter().tuple_windows() {
+            for exp in exponents.into_iter().chain([exp, exp >> 40]) {
+                let expected = pow(x, exp);
+                assert_eq!(pow_window::<1>(x, exp), expected);
+                assert_eq!(pow_window::<3>(x, exp), expected);
+                assert_eq!(pow_window::<4>(x, exp), expected);
+                assert_eq!(pow_window::<5>(x, exp), expected);
+                assert_eq!(pow_window::<8>(x, exp), expected);
+            }
+        }
+    }
+
+    #[test]
+    fn repeated_squaring() {
+        for x in random_elements(100).into_iter().chain([0, 1, P64 - 1]) {
+            let mut expected = x;
+            for k in 0..70 {
+                assert_eq!(exp_power_of_2(x, k), expected);
+                expected = mul_modulo(expected, expected);
+            }
+        }
+        assert_eq!(exp_power_of_2(constants::ROOTS_OF_UNITY[32], 32), 1);
+    }
+
+    #[test]
+    fn squaring() {
+        let edge_cases = [0, 1, (1 << 32) - 1, 1 << 32, (1 << 63) + 1, P64 - 1];
+        for x in random_elements(1_000).into_iter().chain(edge_cases) {
+            assert_eq!(square(x), mul_modulo(x, x));
+            assert_eq!(square_wide_limbs32(x), mul_wide_limbs32(x, x));
+        }
+        assert_eq!(
+            square_wide_limbs32(u64::MAX),
+            mul_wide_limbs32(u64::MAX, u64::MAX)
+        );
+    }
+
+    #[test]
+    fn branchy_and_branchless_reduction() {
+        let edge_cases = [0, 1, P64 / 2, P64 / 2 + 1, P64 - 1, P64 - 1];
+        for (&x, &y) in random_elements(1_000)
+            .iter()
+            .chain(&edge_cases)
+            .tuple_windows()
+        {
+            assert_eq!(add_branchy(x, y), add_modulo(x, y));
+            assert_eq!(add_branchless(x, y), add_modulo(x, y));
+            assert_eq!(mul_branchy(x, y), mul_modulo(x, y));
+            assert_eq!(mul_branchless(x, y), mul_modulo(x, y));
+        }
+
+        // non-canonical operands whose product reduces to a value of at least p
+        for y in [P64, P64 + 1, u64::MAX] {
+            assert_eq!(mul_branchy(1, y), y - P64);
+            assert_eq!(mul_branchless(1, y), y - P64);
+        }
+    }
+
+    #[test]
+    fn fused_multiply_add() {
+        let edge_cases = [0, 1, P64 - 1, P64 - 1];
+        for (&a, &b, &c) in random_elements(1_000)
+            .iter()
+            .chain(&edge_cases)
+            .tuple_windows()
+        {
+            let expected = add_modulo(mul_modulo(a, b), c);
+            assert_eq!(mul_add(a, b, c), expected);
+            assert_eq!(Modulo::mul_add(a, b, c), expected);
+            assert_eq!(Limbs32::mul_add(a, b, c), expected);
+        }
+        assert_eq!(mul_add(P64 - 1, P64 - 1, P64 - 1), 0);
+    }
+
+    #[test]
+    fn plantard_multiplication() {
+        let edge_cases = [0, 1, P64 / 2, P64 / 2 + 1, P64 - 1];
+        for (&x, &y) in random_elements(1_000)
+            .iter()
+            .chain(&edge_cases)
+            .tuple_windows()
+        {
+            assert_eq!(Plantard::from_repr(Plantard::to_repr(x)), x);
+            assert_eq!(
+                mul_reduce_plantard(Plantard::to_repr(x), y),
+                mul_modulo(x, y)
+            );
+        }
+        for x in [0, 1, P128, P128 * P128, (1 << 127) - 1, 1 << 127, u128::MAX] {
+            let reduced = Plantard::reduce(x);
+            assert!(is_canonical(reduced));
+            assert_eq!(Plantard::to_repr(reduced) as u128, x % P128);
+        }
+    }
+
+    #[test]
+    fn mul_small_equivalence() {
+        let small = [0, 1, 2, 7, u32::MAX - 1, u32::MAX];
+        for x in random_elements(1_000).into_iter().chain([0, 1, P64 - 1]) {
+            let y = thread_rng().gen::<u32>();
+            for y in small.into_iter().chain([y]) {
+                assert_eq!(mul_small(x, y), mul_modulo(x, y as u64));
+                let element = FieldElement::<Montgomery>::new(x);
+                assert_eq!(element.mul_small(y).value(), mul_modulo(x, y as u64));
+            }
+        }
+    }
+
+    #[test]
+    fn signed_representatives() {
+        let half = (P64 / 2) as i64;
+        assert_eq!(to_signed(0), 0);
+        assert_eq!(to_signed(P64 - 1), -1);
+        assert_eq!(to_signed(P64 / 2), half);
+        assert_eq!(to_signed(P64 / 2 + 1), -half);
+        assert_eq!(from_signed(-1), P64 - 1);
+        assert_eq!(from_signed(i64::MIN), P64 - (1 << 63));
+        assert_eq!(from_signed(i64::MAX), i64::MAX as u64);
+
+        for x in random_elements(1_000) {
+            let signed = to_signed(x);
+            assert!(-half <= signed && signed <= half);
+            assert_eq!(from_signed(signed), x);
+            assert_eq!(FieldElement::<Montgomery>::new(x).to_signed(), signed);
+            assert_eq!(FieldElement::<Montgomery>::from_i64(signed).value(), x);
+        }
+    }
+}
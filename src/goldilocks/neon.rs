@@ -0,0 +1,252 @@
+//! Two-lane packed arithmetic on canonical `P64` residues with NEON
+//!
+//! Laid out like [`super::avx2`] for aarch64, such as Apple Silicon and
+//! Graviton. NEON is part of the baseline there, so [`is_available`] is true
+//! on every standard aarch64 target.
+//!
+//! NEON compares unsigned 64-bit lanes directly but has no 64-bit multiply,
+//! so products are assembled from four widening 32 x 32-bit `vmull_u32`
+//! products, with both 32-bit halves narrowed out of each operand first.
+
+use core::arch::aarch64::*;
+
+#[cfg(target_feature = "neon")]
+use super::packed::PackedField;
+use super::slice::packed_slice_kernel;
+use super::{add_winterfell, reduce_u64, sub_winterfell, P64};
+
+/// The number of residues in a packed vector
+pub const LANES: usize = 2;
+
+/// 2^64 - `P64` = 2^32 - 1, which is also 2^64 mod p
+const EPSILON: u64 = 0xffff_ffff;
+
+/// Returns whether NEON is enabled at compile time or, with `std`, detected at runtime
+#[inline]
+pub fn is_available() -> bool {
+    #[cfg(target_feature = "neon")]
+    {
+        true
+    }
+    #[cfg(all(not(target_feature = "neon"), feature = "std"))]
+    {
+        std::arch::is_aarch64_feature_detected!("neon")
+    }
+    #[cfg(all(not(target_feature = "neon"), not(feature = "std")))]
+    {
+        false
+    }
+}
+
+/// Narrows all-ones mask lanes to `EPSILON`
+#[inline]
+#[target_feature(enable = "neon")]
+fn epsilon_where(mask: uint64x2_t) -> uint64x2_t {
+    vshrq_n_u64::<32>(mask)
+}
+
+/// Adds canonical lanes
+///
+/// A sum that wraps past 2^64 is short by 2^64 ≡ `EPSILON`, and one at or
+/// above p is reduced by wrapping it the same way, so both add `EPSILON`.
+///
+/// # Safety
+///
+/// Outside NEON code, the caller must ensure the CPU supports NEON, for
+/// example with [`is_available`].
+#[inline]
+#[target_feature(enable = "neon")]
+pub fn add(x: uint64x2_t, y: uint64x2_t) -> uint64x2_t {
+    let sum = vaddq_u64(x, y);
+    let carry = vcltq_u64(sum, x);
+    let above = vcgeq_u64(sum, vdupq_n_u64(P64));
+    vaddq_u64(sum, epsilon_where(vorrq_u64(carry, above)))
+}
+
+/// Subtracts canonical lanes, taking `EPSILON` off the lanes that borrow
+///
+/// # Safety
+///
+/// Outside NEON code, the caller must ensure the CPU supports NEON, for
+/// example with [`is_available`].
+#[inline]
+#[target_feature(enable = "neon")]
+pub fn sub(x: uint64x2_t, y: uint64x2_t) -> uint64x2_t {
+    let diff = vsubq_u64(x, y);
+    let borrow = vcltq_u64(x, y);
+    vsubq_u64(diff, epsilon_where(borrow))
+}
+
+/// Computes the full 128-bit products of the lanes, as high and low halves
+#[inline]
+#[target_feature(enable = "neon")]
+fn mul_wide(x: uint64x2_t, y: uint64x2_t) -> (uint64x2_t, uint64x2_t) {
+    let (x_lo, x_hi) = (vmovn_u64(x), vshrn_n_u64::<32>(x));
+    let (y_lo, y_hi) = (vmovn_u64(y), vshrn_n_u64::<32>(y));
+    let lo_lo = vmull_u32(x_lo, y_lo);
+    let lo_hi = vmull_u32(x_lo, y_hi);
+    let hi_lo = vmull_u32(x_hi, y_lo);
+    let hi_hi = vmull_u32(x_hi, y_hi);
+
+    // each partial sum stays below 2^64, as in schoolbook multiplication
+    let mask = vdupq_n_u64(EPSILON);
+    let t = vaddq_u64(hi_lo, vshrq_n_u64::<32>(lo_lo));
+    let u = vaddq_u64(lo_hi, vandq_u64(t, mask));
+    let lo = vorrq_u64(vshlq_n_u64::<32>(u), vandq_u64(lo_lo, mask));
+    let hi = vaddq_u64(hi_hi, vaddq_u64(vshrq_n_u64::<32>(t), vshrq_n_u64::<32>(u)));
+    (hi, lo)
+}
+
+/// Reduces 128-bit lanes to canonical residues
+///
+/// With hi = a * 2^32 + b, 2^96 ≡ -1 and 2^64 ≡ `EPSILON` give
+/// lo - a + b * `EPSILON`, each step correcting a borrow or carry by `EPSILON`.
+#[inline]
+#[target_feature(enable = "neon")]
+fn reduce(hi: uint64x2_t, lo: uint64x2_t) -> uint64x2_t {
+    let hi_hi = vshrq_n_u64::<32>(hi);
+    let t = vsubq_u64(lo, hi_hi);
+    let t = vsubq_u64(t, epsilon_where(vcltq_u64(lo, hi_hi)));
+
+    let hi_lo = vandq_u64(hi, vdupq_n_u64(EPSILON));
+    let product = vsubq_u64(vshlq_n_u64::<32>(hi_lo), hi_lo);
+    let sum = vaddq_u64(t, product);
+    let sum = vaddq_u64(sum, epsilon_where(vcltq_u64(sum, t)));
+
+    let above = vcgeq_u64(sum, vdupq_n_u64(P64));
+    vaddq_u64(sum, epsilon_where(above))
+}
+
+/// Multiplies canonical lanes
+///
+/// # Safety
+///
+/// Outside NEON code, the caller must ensure the CPU supports NEON, for
+/// example with [`is_available`].
+#[inline]
+#[target_feature(enable = "neon")]
+pub fn mul(x: uint64x2_t, y: uint64x2_t) -> uint64x2_t {
+    let (hi, lo) = mul_wide(x, y);
+    reduce(hi, lo)
+}
+
+packed_slice_kernel!(
+    /// Writes the elementwise sums a[i] + b[i] into `dst`, two lanes at a time
+    add_slices,
+    "neon",
+    is_available(),
+    uint64x2_t,
+    vld1q_u64,
+    vst1q_u64,
+    add,
+    add_winterfell
+);
+
+packed_slice_kernel!(
+    /// Writes the elementwise differences a[i] - b[i] into `dst`, two lanes at a time
+    sub_slices,
+    "neon",
+    is_available(),
+    uint64x2_t,
+    vld1q_u64,
+    vst1q_u64,
+    sub,
+    sub_winterfell
+);
+
+packed_slice_kernel!(
+    /// Writes the elementwise products a[i] * b[i] into `dst`, two lanes at a time
+    mul_slices,
+    "neon",
+    is_available(),
+    uint64x2_t,
+    vld1q_u64,
+    vst1q_u64,
+    mul,
+    |x: u64, y: u64| reduce_u64(x as u128 * y as u128)
+);
+
+/// Two packed residues, implementing [`PackedField`] when NEON is enabled at compile time
+#[cfg(target_feature = "neon")]
+#[derive(Clone, Copy, Debug)]
+pub struct PackedNeon(pub uint64x2_t);
+
+// NEON is enabled for the whole build wherever this impl exists
+#[cfg(target_feature = "neon")]
+impl PackedField for PackedNeon {
+    const WIDTH: usize = LANES;
+    const NAME: &'static str = "neon";
+
+    #[inline(always)]
+    fn from_slice(values: &[u64]) -> Self {
+        let values = &values[..LANES];
+        Self(unsafe { vld1q_u64(values.as_ptr()) })
+    }
+
+    #[inline(always)]
+    fn write_to_slice(self, out: &mut [u64]) {
+        let out = &mut out[..LANES];
+        unsafe { vst1q_u64(out.as_mut_ptr(), self.0) }
+    }
+
+    #[inline(always)]
+    fn broadcast(x: u64) -> Self {
+        Self(unsafe { vdupq_n_u64(x) })
+    }
+
+    #[inline(always)]
+    fn add(self, rhs: Self) -> Self {
+        Self(unsafe { add(self.0, rhs.0) })
+    }
+
+    #[inline(always)]
+    fn sub(self, rhs: Self) -> Self {
+        Self(unsafe { sub(self.0, rhs.0) })
+    }
+
+    #[inline(always)]
+    fn mul(self, rhs: Self) -> Self {
+        Self(unsafe { mul(self.0, rhs.0) })
+    }
+
+    #[inline(always)]
+    fn horizontal_sum(self) -> u64 {
+        let mut lanes = [0; LANES];
+        self.write_to_slice(&mut lanes);
+        super::sum(&lanes)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::goldilocks::{add_modulo, mul_modulo, sub_modulo};
+    use crate::rand::random_elements_exact;
+
+    #[test]
+    fn packed_kernels() {
+        let edge_cases = [0, 1, EPSILON, EPSILON + 1, 1 << 63, P64 - 2, P64 - 1];
+        let (edge_a, edge_b): (Vec<_>, Vec<_>) = edge_cases
+            .iter()
+            .flat_map(|&x| edge_cases.iter().map(move |&y| (x, y)))
+            .unzip();
+        for len in [0, 1, 2, 1_001] {
+            let a = [&random_elements_exact(len)[..], &edge_a].concat();
+            let b = [&random_elements_exact(len)[..], &edge_b].concat();
+            let mut dst = vec![0; a.len()];
+
+            add_slices(&mut dst, &a, &b);
+            for ((&d, &x), &y) in dst.iter().zip(&a).zip(&b) {
+                assert_eq!(d, add_modulo(x, y));
+            }
+            sub_slices(&mut dst, &a, &b);
+            for ((&d, &x), &y) in dst.iter().zip(&a).zip(&b) {
+                assert_eq!(d, sub_modulo(x, y));
+            }
+            mul_slices(&mut dst, &a, &b);
+            for ((&d, &x), &y) in dst.iter().zip(&a).zip(&b) {
+                assert_eq!(d, mul_modulo(x, y), "{x} * {y}");
+            }
+        }
+    }
+}
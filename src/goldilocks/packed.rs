@@ -0,0 +1,342 @@
+//! Slice kernels and an NTT written once over any packed backend
+//!
+//! A [`PackedField`] holds `WIDTH` canonical residues and operates on all of
+//! them at once. The scalar `u64` backend has width one and is always
+//! available; each SIMD backend implements the trait only when its target
+//! features are enabled at compile time, for example with
+//! `-C target-cpu=native`, so generic code runs them without runtime checks.
+
+use super::{add_winterfell, reduce_u64, sub_winterfell};
+
+/// A vector of `WIDTH` canonical `P64` residues with lane-wise arithmetic
+pub trait PackedField: Copy {
+    /// The number of residues in the vector
+    const WIDTH: usize;
+
+    /// Short name used to label benchmarks
+    const NAME: &'static str;
+
+    /// Loads the first `WIDTH` values
+    ///
+    /// # Panics
+    ///
+    /// Panics if `values` holds fewer than `WIDTH` elements.
+    fn from_slice(values: &[u64]) -> Self;
+
+    /// Stores the lanes into the first `WIDTH` elements of `out`
+    ///
+    /// # Panics
+    ///
+    /// Panics if `out` holds fewer than `WIDTH` elements.
+    fn write_to_slice(self, out: &mut [u64]);
+
+    /// Returns a vector with every lane set to `x`
+    fn broadcast(x: u64) -> Self;
+
+    /// Adds lane-wise
+    fn add(self, rhs: Self) -> Self;
+
+    /// Subtracts lane-wise
+    fn sub(self, rhs: Self) -> Self;
+
+    /// Multiplies lane-wise
+    fn mul(self, rhs: Self) -> Self;
+
+    /// Sums the lanes into one residue
+    fn horizontal_sum(self) -> u64;
+}
+
+/// The scalar backend, one residue at a time
+impl PackedField for u64 {
+    const WIDTH: usize = 1;
+    const NAME: &'static str = "scalar";
+
+    #[inline(always)]
+    fn from_slice(values: &[u64]) -> Self {
+        values[0]
+    }
+
+    #[inline(always)]
+    fn write_to_slice(self, out: &mut [u64]) {
+        out[0] = self;
+    }
+
+    #[inline(always)]
+    fn broadcast(x: u64) -> Self {
+        x
+    }
+
+    #[inline(always)]
+    fn add(self, rhs: Self) -> Self {
+        add_winterfell(self, rhs)
+    }
+
+    #[inline(always)]
+    fn sub(self, rhs: Self) -> Self {
+        sub_winterfell(self, rhs)
+    }
+
+    #[inline(always)]
+    fn mul(self, rhs: Self) -> Self {
+        reduce_u64(self as u128 * rhs as u128)
+    }
+
+    #[inline(always)]
+    fn horizontal_sum(self) -> u64 {
+        self
+    }
+}
+
+/// Applies `packed` to whole vectors of `P::WIDTH` elements and `scalar` to the rest
+#[inline(always)]
+fn map_slices<P: PackedField>(
+    dst: &mut [u64],
+    a: &[u64],
+    b: &[u64],
+    packed: impl Fn(P, P) -> P,
+    scalar: impl Fn(u64, u64) -> u64,
+) {
+    super::slice::assert_same_length(dst, a, b);
+    let mut dst_chunks = dst.chunks_exact_mut(P::WIDTH);
+    let (a_chunks, b_chunks) = (a.chunks_exact(P::WIDTH), b.chunks_exact(P::WIDTH));
+    let (a_rest, b_rest) = (a_chunks.remainder(), b_chunks.remainder());
+    for ((d, x), y) in (&mut dst_chunks).zip(a_chunks).zip(b_chunks) {
+        packed(P::from_slice(x), P::from_slice(y)).write_to_slice(d);
+    }
+    let rest = dst_chunks.into_remainder();
+    for ((d, &x), &y) in rest.iter_mut().zip(a_rest).zip(b_rest) {
+        *d = scalar(x, y);
+    }
+}
+
+/// Writes the elementwise sums a[i] + b[i] into `dst`, `P::WIDTH` lanes at a time
+///
+/// # Panics
+///
+/// Panics if the slices differ in length.
+pub fn add_slices<P: PackedField>(dst: &mut [u64], a: &[u64], b: &[u64]) {
+    map_slices(dst, a, b, P::add, u64::add);
+}
+
+/// Writes the elementwise differences a[i] - b[i] into `dst`, `P::WIDTH` lanes at a time
+///
+/// # Panics
+///
+/// Panics if the slices differ in length.
+pub fn sub_slices<P: PackedField>(dst: &mut [u64], a: &[u64], b: &[u64]) {
+    map_slices(dst, a, b, P::sub, u64::sub);
+}
+
+/// Writes the elementwise products a[i] * b[i] into `dst`, `P::WIDTH` lanes at a time
+///
+/// # Panics
+///
+/// Panics if the slices differ in length.
+pub fn mul_slices<P: PackedField>(dst: &mut [u64], a: &[u64], b: &[u64]) {
+    map_slices(dst, a, b, P::mul, u64::mul);
+}
+
+/// Computes the inner product of `a` and `b` in `P::WIDTH` independent lanes
+///
+/// # Panics
+///
+/// Panics if `a` and `b` differ in length.
+pub fn dot<P: PackedField>(a: &[u64], b: &[u64]) -> u64 {
+    assert_eq!(a.len(), b.len(), "operands differ in length");
+    let (a_chunks, b_chunks) = (a.chunks_exact(P::WIDTH), b.chunks_exact(P::WIDTH));
+    let (a_rest, b_rest) = (a_chunks.remainder(), b_chunks.remainder());
+    let lanes = a_chunks.zip(b_chunks).fold(P::broadcast(0), |acc, (x, y)| {
+        acc.add(P::from_slice(x).mul(P::from_slice(y)))
+    });
+    a_rest
+        .iter()
+        .zip(b_rest)
+        .fold(lanes.horizontal_sum(), |acc, (&x, &y)| acc.add(x.mul(y)))
+}
+
+/// Lays out twiddle factors for [`ntt`] from a primitive `n`-th root of unity
+///
+/// The layer combining halves of length h reads twiddles[h..2h], the first h
+/// powers of a primitive 2h-th root, so that every layer loads them
+/// contiguously. Entry 0 is unused.
+///
+/// # Panics
+///
+/// Panics unless `n` is a power of two.
+#[cfg(feature = "std")]
+pub fn ntt_twiddles(root: u64, n: usize) -> Vec<u64> {
+    assert!(n.is_power_of_two(), "NTT size {n} is not a power of two");
+    let mut twiddles = vec![0; n];
+    let half = n / 2;
+    for (k, power) in super::powers(root).take(half).enumerate() {
+        twiddles[half + k] = power;
+    }
+    // squaring the root halves its order, keeping every second power
+    for k in (1..half).rev() {
+        twiddles[k] = twiddles[2 * k];
+    }
+    twiddles
+}
+
+/// Transforms `values` in place with iterative radix-2 Cooley-Tukey butterflies
+///
+/// Layers whose halves span at least `P::WIDTH` elements run packed; the
+/// first few narrow ones run on the scalar backend. The twiddles are laid out
+/// by [`ntt_twiddles`], and the output is in natural order.
+///
+/// # Panics
+///
+/// Panics unless `values` has a power-of-two length equal to `twiddles`.
+pub fn ntt<P: PackedField>(values: &mut [u64], twiddles: &[u64]) {
+    let n = values.len();
+    assert!(n.is_power_of_two(), "NTT size {n} is not a power of two");
+    assert_eq!(twiddles.len(), n, "twiddles do not match the NTT size");
+
+    let log_n = n.trailing_zeros();
+    for i in 0..n {
+        let j = i
+            .reverse_bits()
+            .checked_shr(usize::BITS - log_n)
+            .unwrap_or(0);
+        if i < j {
+            values.swap(i, j);
+        }
+    }
+
+    let mut half = 1;
+    while half < n {
+        let layer = &twiddles[half..2 * half];
+        if half >= P::WIDTH {
+            for chunk in values.chunks_exact_mut(2 * half) {
+                let (lo, hi) = chunk.split_at_mut(half);
+                butterflies::<P>(lo, hi, layer);
+            }
+        } else {
+            narrow_layer(values, layer);
+        }
+        half *= 2;
+    }
+}
+
+/// Runs a layer too narrow to pack on the scalar backend
+///
+/// Fixing the half length lets the compiler unroll each group of butterflies.
+/// Otherwise it may vectorize the loop of a few iterations with masked loads
+/// and stores, which stall store forwarding and run several times slower.
+#[inline(never)]
+fn narrow_layer(values: &mut [u64], twiddles: &[u64]) {
+    match twiddles.len() {
+        1 => fixed_layer::<1>(values, twiddles),
+        2 => fixed_layer::<2>(values, twiddles),
+        4 => fixed_layer::<4>(values, twiddles),
+        8 => fixed_layer::<8>(values, twiddles),
+        half => {
+            for chunk in values.chunks_exact_mut(2 * half) {
+                let (lo, hi) = chunk.split_at_mut(half);
+                butterflies::<u64>(lo, hi, twiddles);
+            }
+        }
+    }
+}
+
+#[inline(always)]
+fn fixed_layer<const HALF: usize>(values: &mut [u64], twiddles: &[u64]) {
+    let twiddles: &[u64; HALF] = twiddles.try_into().unwrap();
+    for chunk in values.chunks_exact_mut(2 * HALF) {
+        let (lo, hi) = chunk.split_at_mut(HALF);
+        for k in 0..HALF {
+            let t = hi[k].mul(twiddles[k]);
+            (lo[k], hi[k]) = (lo[k].add(t), lo[k].sub(t));
+        }
+    }
+}
+
+/// Replaces each pair (x, y) of `lo` and `hi` with (x + w * y, x - w * y)
+#[inline(always)]
+fn butterflies<P: PackedField>(lo: &mut [u64], hi: &mut [u64], twiddles: &[u64]) {
+    let chunks = lo
+        .chunks_exact_mut(P::WIDTH)
+        .zip(hi.chunks_exact_mut(P::WIDTH))
+        .zip(twiddles.chunks_exact(P::WIDTH));
+    for ((x, y), w) in chunks {
+        let t = P::from_slice(y).mul(P::from_slice(w));
+        let x_packed = P::from_slice(x);
+        x_packed.add(t).write_to_slice(x);
+        x_packed.sub(t).write_to_slice(y);
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::goldilocks::{add_modulo, horner_eval, mul_modulo, pow, sub_modulo, P64};
+    use crate::rand::random_elements_exact;
+
+    /// Evaluates the polynomial with coefficients `values` at every n-th root of unity
+    fn naive_ntt(values: &[u64], root: u64) -> Vec<u64> {
+        let n = values.len() as u64;
+        (0..n).map(|i| horner_eval(values, pow(root, i))).collect()
+    }
+
+    fn check<P: PackedField>() {
+        let edge_cases = [0, 1, 0xffff_ffff, 1 << 32, 1 << 63, P64 - 2, P64 - 1];
+        let (edge_a, edge_b): (Vec<_>, Vec<_>) = edge_cases
+            .iter()
+            .flat_map(|&x| edge_cases.iter().map(move |&y| (x, y)))
+            .unzip();
+        for len in [0, P::WIDTH - 1, P::WIDTH, 1_001] {
+            let a = [&random_elements_exact(len)[..], &edge_a].concat();
+            let b = [&random_elements_exact(len)[..], &edge_b].concat();
+            let mut dst = vec![0; a.len()];
+
+            add_slices::<P>(&mut dst, &a, &b);
+            for ((&d, &x), &y) in dst.iter().zip(&a).zip(&b) {
+                assert_eq!(d, add_modulo(x, y), "{}", P::NAME);
+            }
+            sub_slices::<P>(&mut dst, &a, &b);
+            for ((&d, &x), &y) in dst.iter().zip(&a).zip(&b) {
+                assert_eq!(d, sub_modulo(x, y), "{}", P::NAME);
+            }
+            mul_slices::<P>(&mut dst, &a, &b);
+            for ((&d, &x), &y) in dst.iter().zip(&a).zip(&b) {
+                assert_eq!(d, mul_modulo(x, y), "{}: {x} * {y}", P::NAME);
+            }
+            assert_eq!(dot::<P>(&a, &b), dot::<u64>(&a, &b), "{}", P::NAME);
+        }
+
+        // 7 generates the multiplicative group, whose order 2^32 (2^32 - 1) is divisible by n
+        for log_n in [0, 1, 2, 3, 4, 8] {
+            let n = 1 << log_n;
+            let root = pow(7, (P64 - 1) >> log_n);
+            let values = random_elements_exact(n);
+            let mut transformed = values.clone();
+            ntt::<P>(&mut transformed, &ntt_twiddles(root, n));
+            assert_eq!(
+                transformed,
+                naive_ntt(&values, root),
+                "{} at 2^{log_n}",
+                P::NAME
+            );
+        }
+    }
+
+    #[test]
+    fn backends() {
+        check::<u64>();
+        assert_eq!(dot::<u64>(&[3, P64 - 1], &[5, 2]), 13);
+
+        #[cfg(all(target_arch = "x86_64", target_feature = "avx2"))]
+        check::<crate::goldilocks::avx2::PackedAvx2>();
+        #[cfg(all(target_arch = "x86_64", target_feature = "avx512f"))]
+        check::<crate::goldilocks::avx512::PackedAvx512>();
+        #[cfg(all(target_arch = "aarch64", target_feature = "neon"))]
+        check::<crate::goldilocks::neon::PackedNeon>();
+        #[cfg(all(target_arch = "wasm32", target_feature = "simd128"))]
+        check::<crate::goldilocks::simd128::PackedSimd128>();
+        #[cfg(feature = "portable-simd")]
+        {
+            check::<core::simd::Simd<u64, 2>>();
+            check::<core::simd::Simd<u64, 8>>();
+        }
+    }
+}
@@ -0,0 +1,194 @@
+//! Packed arithmetic on canonical `P64` residues with `core::simd`
+//!
+//! One source for any lane count `N` on `Simd<u64, N>`, so that it can be
+//! compared against the hand-written intrinsics. The slice kernels are the
+//! [`packed`] ones on this backend. Requires a nightly compiler and the
+//! `portable-simd` feature.
+//!
+//! The vector width is fixed at compile time: without `-C target-cpu=native`
+//! or explicit target features, the compiler lowers wide vectors to SSE2 on
+//! x86_64 and to NEON on aarch64.
+
+use core::simd::prelude::*;
+
+use super::packed::{self, PackedField};
+use super::P64;
+
+/// 2^64 - `P64` = 2^32 - 1, which is also 2^64 mod p
+const EPSILON: u64 = 0xffff_ffff;
+
+/// Adds `EPSILON` to the lanes selected by `mask`
+#[inline]
+fn add_epsilon<const N: usize>(x: Simd<u64, N>, mask: Mask<i64, N>) -> Simd<u64, N> {
+    mask.select(x + Simd::splat(EPSILON), x)
+}
+
+/// Adds canonical lanes
+///
+/// A sum that wraps past 2^64 is short by 2^64 ≡ `EPSILON`, and one at or
+/// above p is reduced by wrapping it the same way, so both add `EPSILON`.
+#[inline]
+pub fn add<const N: usize>(x: Simd<u64, N>, y: Simd<u64, N>) -> Simd<u64, N> {
+    let sum = x + y;
+    let carry = sum.simd_lt(x);
+    let above = sum.simd_ge(Simd::splat(P64));
+    add_epsilon(sum, carry | above)
+}
+
+/// Subtracts canonical lanes, taking `EPSILON` off the lanes that borrow
+#[inline]
+pub fn sub<const N: usize>(x: Simd<u64, N>, y: Simd<u64, N>) -> Simd<u64, N> {
+    let diff = x - y;
+    x.simd_lt(y).select(diff - Simd::splat(EPSILON), diff)
+}
+
+/// Computes the full 128-bit products of the lanes, as high and low halves
+#[inline]
+fn mul_wide<const N: usize>(x: Simd<u64, N>, y: Simd<u64, N>) -> (Simd<u64, N>, Simd<u64, N>) {
+    let mask = Simd::splat(EPSILON);
+    let (x_lo, x_hi) = (x & mask, x >> 32);
+    let (y_lo, y_hi) = (y & mask, y >> 32);
+    let lo_lo = x_lo * y_lo;
+    let lo_hi = x_lo * y_hi;
+    let hi_lo = x_hi * y_lo;
+    let hi_hi = x_hi * y_hi;
+
+    // each partial sum stays below 2^64, as in schoolbook multiplication
+    let t = hi_lo + (lo_lo >> 32);
+    let u = lo_hi + (t & mask);
+    let lo = (u << 32) | (lo_lo & mask);
+    let hi = hi_hi + (t >> 32) + (u >> 32);
+    (hi, lo)
+}
+
+/// Reduces 128-bit lanes to canonical residues
+///
+/// With hi = a * 2^32 + b, 2^96 ≡ -1 and 2^64 ≡ `EPSILON` give
+/// lo - a + b * `EPSILON`, each step correcting a borrow or carry by `EPSILON`.
+#[inline]
+fn reduce<const N: usize>(hi: Simd<u64, N>, lo: Simd<u64, N>) -> Simd<u64, N> {
+    let hi_hi = hi >> 32;
+    let t = lo - hi_hi;
+    let t = lo.simd_lt(hi_hi).select(t - Simd::splat(EPSILON), t);
+
+    let hi_lo = hi & Simd::splat(EPSILON);
+    let sum = t + ((hi_lo << 32) - hi_lo);
+    let sum = add_epsilon(sum, sum.simd_lt(t));
+    add_epsilon(sum, sum.simd_ge(Simd::splat(P64)))
+}
+
+/// Multiplies canonical lanes
+#[inline]
+pub fn mul<const N: usize>(x: Simd<u64, N>, y: Simd<u64, N>) -> Simd<u64, N> {
+    let (hi, lo) = mul_wide(x, y);
+    reduce(hi, lo)
+}
+
+/// Writes the elementwise sums a[i] + b[i] into `dst`, `N` lanes at a time
+///
+/// # Panics
+///
+/// Panics if the slices differ in length.
+pub fn add_slices<const N: usize>(dst: &mut [u64], a: &[u64], b: &[u64]) {
+    packed::add_slices::<Simd<u64, N>>(dst, a, b);
+}
+
+/// Writes the elementwise differences a[i] - b[i] into `dst`, `N` lanes at a time
+///
+/// # Panics
+///
+/// Panics if the slices differ in length.
+pub fn sub_slices<const N: usize>(dst: &mut [u64], a: &[u64], b: &[u64]) {
+    packed::sub_slices::<Simd<u64, N>>(dst, a, b);
+}
+
+/// Writes the elementwise products a[i] * b[i] into `dst`, `N` lanes at a time
+///
+/// # Panics
+///
+/// Panics if the slices differ in length.
+pub fn mul_slices<const N: usize>(dst: &mut [u64], a: &[u64], b: &[u64]) {
+    packed::mul_slices::<Simd<u64, N>>(dst, a, b);
+}
+
+/// Every lane count is a backend, with the width chosen at the call site
+impl<const N: usize> PackedField for Simd<u64, N> {
+    const WIDTH: usize = N;
+    const NAME: &'static str = "portable";
+
+    #[inline(always)]
+    fn from_slice(values: &[u64]) -> Self {
+        Simd::from_slice(values)
+    }
+
+    #[inline(always)]
+    fn write_to_slice(self, out: &mut [u64]) {
+        self.copy_to_slice(out);
+    }
+
+    #[inline(always)]
+    fn broadcast(x: u64) -> Self {
+        Simd::splat(x)
+    }
+
+    #[inline(always)]
+    fn add(self, rhs: Self) -> Self {
+        add(self, rhs)
+    }
+
+    #[inline(always)]
+    fn sub(self, rhs: Self) -> Self {
+        sub(self, rhs)
+    }
+
+    #[inline(always)]
+    fn mul(self, rhs: Self) -> Self {
+        mul(self, rhs)
+    }
+
+    #[inline(always)]
+    fn horizontal_sum(self) -> u64 {
+        super::sum(self.as_array())
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::goldilocks::{add_modulo, mul_modulo, sub_modulo};
+    use crate::rand::random_elements_exact;
+
+    fn check<const N: usize>() {
+        let edge_cases = [0, 1, EPSILON, EPSILON + 1, 1 << 63, P64 - 2, P64 - 1];
+        let (edge_a, edge_b): (Vec<_>, Vec<_>) = edge_cases
+            .iter()
+            .flat_map(|&x| edge_cases.iter().map(move |&y| (x, y)))
+            .unzip();
+        for len in [0, N - 1, N, 1_001] {
+            let a = [&random_elements_exact(len)[..], &edge_a].concat();
+            let b = [&random_elements_exact(len)[..], &edge_b].concat();
+            let mut dst = vec![0; a.len()];
+
+            add_slices::<N>(&mut dst, &a, &b);
+            for ((&d, &x), &y) in dst.iter().zip(&a).zip(&b) {
+                assert_eq!(d, add_modulo(x, y));
+            }
+            sub_slices::<N>(&mut dst, &a, &b);
+            for ((&d, &x), &y) in dst.iter().zip(&a).zip(&b) {
+                assert_eq!(d, sub_modulo(x, y));
+            }
+            mul_slices::<N>(&mut dst, &a, &b);
+            for ((&d, &x), &y) in dst.iter().zip(&a).zip(&b) {
+                assert_eq!(d, mul_modulo(x, y), "{x} * {y} in {N} lanes");
+            }
+        }
+    }
+
+    #[test]
+    fn packed_kernels() {
+        check::<1>();
+        check::<2>();
+        check::<4>();
+        check::<8>();
+    }
+}
@@ -0,0 +1,219 @@
+//! Two-lane packed arithmetic on canonical `P64` residues with WASM SIMD128
+//!
+//! Laid out like [`super::neon`] for `wasm32`, to estimate how this prime
+//! performs in browser and WASI provers. WebAssembly has no runtime feature
+//! detection, so the module only exists when `simd128` is enabled at compile
+//! time, as `.cargo/config.toml` does for `wasm32-wasip1`.
+//!
+//! SIMD128 only compares signed 64-bit lanes, so unsigned compares flip the
+//! sign bits first. It has no 64-bit multiply either, and products are
+//! assembled from four `u64x2_extmul_*_u32x4` products of the 32-bit halves,
+//! which a shuffle first groups into the low and high 64 bits of a vector.
+//!
+//! The slice kernels are the [`packed`] ones on [`PackedSimd128`].
+
+use core::arch::wasm32::*;
+
+use super::packed::{self, PackedField};
+use super::P64;
+
+/// The number of residues in a packed vector
+pub const LANES: usize = 2;
+
+/// 2^64 - `P64` = 2^32 - 1, which is also 2^64 mod p
+const EPSILON: u64 = 0xffff_ffff;
+
+/// Flips the sign bits, so that signed compares order lanes as unsigned
+#[inline]
+fn flip(x: v128) -> v128 {
+    v128_xor(x, u64x2_splat(1 << 63))
+}
+
+/// Returns all-ones in the lanes where x < y as unsigned integers
+#[inline]
+fn lt(x: v128, y: v128) -> v128 {
+    i64x2_lt(flip(x), flip(y))
+}
+
+/// Narrows all-ones mask lanes to `EPSILON`
+#[inline]
+fn epsilon_where(mask: v128) -> v128 {
+    u64x2_shr(mask, 32)
+}
+
+/// Adds canonical lanes
+///
+/// A sum that wraps past 2^64 is short by 2^64 ≡ `EPSILON`, and one at or
+/// above p is reduced by wrapping it the same way, so both add `EPSILON`.
+#[inline]
+pub fn add(x: v128, y: v128) -> v128 {
+    let sum = u64x2_add(x, y);
+    let carry = lt(sum, x);
+    let above = v128_not(lt(sum, u64x2_splat(P64)));
+    u64x2_add(sum, epsilon_where(v128_or(carry, above)))
+}
+
+/// Subtracts canonical lanes, taking `EPSILON` off the lanes that borrow
+#[inline]
+pub fn sub(x: v128, y: v128) -> v128 {
+    let diff = u64x2_sub(x, y);
+    u64x2_sub(diff, epsilon_where(lt(x, y)))
+}
+
+/// Computes the full 128-bit products of the lanes, as high and low halves
+#[inline]
+fn mul_wide(x: v128, y: v128) -> (v128, v128) {
+    // the low halves of both lanes, then the high halves
+    let x = i32x4_shuffle::<0, 2, 1, 3>(x, x);
+    let y = i32x4_shuffle::<0, 2, 1, 3>(y, y);
+    let y_swapped = i64x2_shuffle::<1, 0>(y, y);
+    let lo_lo = u64x2_extmul_low_u32x4(x, y);
+    let lo_hi = u64x2_extmul_low_u32x4(x, y_swapped);
+    let hi_lo = u64x2_extmul_high_u32x4(x, y_swapped);
+    let hi_hi = u64x2_extmul_high_u32x4(x, y);
+
+    // each partial sum stays below 2^64, as in schoolbook multiplication
+    let mask = u64x2_splat(EPSILON);
+    let t = u64x2_add(hi_lo, u64x2_shr(lo_lo, 32));
+    let u = u64x2_add(lo_hi, v128_and(t, mask));
+    let lo = v128_or(u64x2_shl(u, 32), v128_and(lo_lo, mask));
+    let hi = u64x2_add(hi_hi, u64x2_add(u64x2_shr(t, 32), u64x2_shr(u, 32)));
+    (hi, lo)
+}
+
+/// Reduces 128-bit lanes to canonical residues
+///
+/// With hi = a * 2^32 + b, 2^96 ≡ -1 and 2^64 ≡ `EPSILON` give
+/// lo - a + b * `EPSILON`, each step correcting a borrow or carry by `EPSILON`.
+#[inline]
+fn reduce(hi: v128, lo: v128) -> v128 {
+    let hi_hi = u64x2_shr(hi, 32);
+    let t = u64x2_sub(lo, hi_hi);
+    let t = u64x2_sub(t, epsilon_where(lt(lo, hi_hi)));
+
+    let hi_lo = v128_and(hi, u64x2_splat(EPSILON));
+    let product = u64x2_sub(u64x2_shl(hi_lo, 32), hi_lo);
+    let sum = u64x2_add(t, product);
+    let sum = u64x2_add(sum, epsilon_where(lt(sum, t)));
+
+    let above = v128_not(lt(sum, u64x2_splat(P64)));
+    u64x2_add(sum, epsilon_where(above))
+}
+
+/// Multiplies canonical lanes
+#[inline]
+pub fn mul(x: v128, y: v128) -> v128 {
+    let (hi, lo) = mul_wide(x, y);
+    reduce(hi, lo)
+}
+
+/// Writes the elementwise sums a[i] + b[i] into `dst`, two lanes at a time
+///
+/// # Panics
+///
+/// Panics if the slices differ in length.
+pub fn add_slices(dst: &mut [u64], a: &[u64], b: &[u64]) {
+    packed::add_slices::<PackedSimd128>(dst, a, b);
+}
+
+/// Writes the elementwise differences a[i] - b[i] into `dst`, two lanes at a time
+///
+/// # Panics
+///
+/// Panics if the slices differ in length.
+pub fn sub_slices(dst: &mut [u64], a: &[u64], b: &[u64]) {
+    packed::sub_slices::<PackedSimd128>(dst, a, b);
+}
+
+/// Writes the elementwise products a[i] * b[i] into `dst`, two lanes at a time
+///
+/// # Panics
+///
+/// Panics if the slices differ in length.
+pub fn mul_slices(dst: &mut [u64], a: &[u64], b: &[u64]) {
+    packed::mul_slices::<PackedSimd128>(dst, a, b);
+}
+
+/// Two packed residues, implementing [`PackedField`]
+#[derive(Clone, Copy, Debug)]
+pub struct PackedSimd128(pub v128);
+
+impl PackedField for PackedSimd128 {
+    const WIDTH: usize = LANES;
+    const NAME: &'static str = "simd128";
+
+    #[inline(always)]
+    fn from_slice(values: &[u64]) -> Self {
+        let values = &values[..LANES];
+        // the slice holds one vector of elements, and the access may be unaligned
+        Self(unsafe { v128_load(values.as_ptr().cast()) })
+    }
+
+    #[inline(always)]
+    fn write_to_slice(self, out: &mut [u64]) {
+        let out = &mut out[..LANES];
+        unsafe { v128_store(out.as_mut_ptr().cast(), self.0) }
+    }
+
+    #[inline(always)]
+    fn broadcast(x: u64) -> Self {
+        Self(u64x2_splat(x))
+    }
+
+    #[inline(always)]
+    fn add(self, rhs: Self) -> Self {
+        Self(add(self.0, rhs.0))
+    }
+
+    #[inline(always)]
+    fn sub(self, rhs: Self) -> Self {
+        Self(sub(self.0, rhs.0))
+    }
+
+    #[inline(always)]
+    fn mul(self, rhs: Self) -> Self {
+        Self(mul(self.0, rhs.0))
+    }
+
+    #[inline(always)]
+    fn horizontal_sum(self) -> u64 {
+        super::sum(&[
+            u64x2_extract_lane::<0>(self.0),
+            u64x2_extract_lane::<1>(self.0),
+        ])
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::goldilocks::{add_modulo, mul_modulo, sub_modulo};
+    use crate::rand::random_elements_exact;
+
+    #[test]
+    fn packed_kernels() {
+        let edge_cases = [0, 1, EPSILON, EPSILON + 1, 1 << 63, P64 - 2, P64 - 1];
+        let (edge_a, edge_b): (Vec<_>, Vec<_>) = edge_cases
+            .iter()
+            .flat_map(|&x| edge_cases.iter().map(move |&y| (x, y)))
+            .unzip();
+        for len in [0, 1, 2, 1_001] {
+            let a = [&random_elements_exact(len)[..], &edge_a].concat();
+            let b = [&random_elements_exact(len)[..], &edge_b].concat();
+            let mut dst = vec![0; a.len()];
+
+            add_slices(&mut dst, &a, &b);
+            for ((&d, &x), &y) in dst.iter().zip(&a).zip(&b) {
+                assert_eq!(d, add_modulo(x, y));
+            }
+            sub_slices(&mut dst, &a, &b);
+            for ((&d, &x), &y) in dst.iter().zip(&a).zip(&b) {
+                assert_eq!(d, sub_modulo(x, y));
+            }
+            mul_slices(&mut dst, &a, &b);
+            for ((&d, &x), &y) in dst.iter().zip(&a).zip(&b) {
+                assert_eq!(d, mul_modulo(x, y), "{x} * {y}");
+            }
+        }
+    }
+}
@@ -0,0 +1,412 @@
+//! Kernels over whole slices of canonical field elements
+
+use super::{add_winterfell, mul_add, mul_wide, packed, reduce_u64, LazyAcc};
+
+/// Writes the elementwise sums a[i] + b[i] into `dst`
+///
+/// # Panics
+///
+/// Panics if the slices differ in length.
+pub fn add_slices(dst: &mut [u64], a: &[u64], b: &[u64]) {
+    packed::add_slices::<u64>(dst, a, b);
+}
+
+/// Writes the elementwise products a[i] * b[i] into `dst`
+///
+/// # Panics
+///
+/// Panics if the slices differ in length.
+pub fn mul_slices(dst: &mut [u64], a: &[u64], b: &[u64]) {
+    packed::mul_slices::<u64>(dst, a, b);
+}
+
+/// Multiplies every element of `v` by `s`
+pub fn scale_in_place(v: &mut [u64], s: u64) {
+    for x in v.iter_mut() {
+        *x = reduce_u64(*x as u128 * s as u128);
+    }
+}
+
+/// Multiplies every element of `v` by `s`, four independent elements at a time
+pub fn scale_in_place_unrolled(v: &mut [u64], s: u64) {
+    let mut chunks = v.chunks_exact_mut(4);
+    for chunk in &mut chunks {
+        let products = [
+            reduce_u64(chunk[0] as u128 * s as u128),
+            reduce_u64(chunk[1] as u128 * s as u128),
+            reduce_u64(chunk[2] as u128 * s as u128),
+            reduce_u64(chunk[3] as u128 * s as u128),
+        ];
+        chunk.copy_from_slice(&products);
+    }
+    scale_in_place(chunks.into_remainder(), s);
+}
+
+/// Writes the linear combination of `vectors` weighted by `coeffs` into `dst`
+///
+/// Each dst[i] = Σ coeffs[j] * vectors[j][i] is accumulated wide across all
+/// vectors and reduced once.
+///
+/// # Panics
+///
+/// Panics if `coeffs` and `vectors` differ in length, or if any vector differs
+/// in length from `dst`.
+pub fn linear_combination(coeffs: &[u64], vectors: &[&[u64]], dst: &mut [u64]) {
+    assert_eq!(coeffs.len(), vectors.len(), "operands differ in length");
+    assert!(
+        vectors.iter().all(|v| v.len() == dst.len()),
+        "operands differ in length"
+    );
+
+    for (i, d) in dst.iter_mut().enumerate() {
+        let mut acc = LazyAcc::new();
+        for (&c, v) in coeffs.iter().zip(vectors) {
+            acc += mul_wide(c, v[i]);
+        }
+        *d = acc.reduce();
+    }
+}
+
+/// Evaluates the polynomial with coefficients `coeffs`, lowest degree first, at `x`
+///
+/// Horner's rule is one chain of dependent `mul_add`s.
+pub fn horner_eval(coeffs: &[u64], x: u64) -> u64 {
+    coeffs.iter().rev().fold(0, |acc, &c| mul_add(acc, x, c))
+}
+
+/// Evaluates like `horner_eval`, splitting into even and odd coefficients
+///
+/// p(x) = e(x^2) + x * o(x^2), where the Horner chains for e and o are
+/// independent, so two multiplications can be in flight at once.
+pub fn even_odd_eval(coeffs: &[u64], x: u64) -> u64 {
+    let x2 = reduce_u64(x as u128 * x as u128);
+    let mut pairs = coeffs.chunks_exact(2);
+    let (mut even, mut odd) = match pairs.remainder() {
+        [c] => (*c, 0),
+        _ => (0, 0),
+    };
+    for pair in pairs.by_ref().rev() {
+        even = mul_add(even, x2, pair[0]);
+        odd = mul_add(odd, x2, pair[1]);
+    }
+    mul_add(odd, x, even)
+}
+
+/// An endless iterator over the powers 1, x, x^2, ... of a canonical `x`
+#[derive(Clone, Debug)]
+pub struct Powers {
+    base: u64,
+    current: u64,
+}
+
+impl Iterator for Powers {
+    type Item = u64;
+
+    #[inline(always)]
+    fn next(&mut self) -> Option<u64> {
+        let power = self.current;
+        self.current = reduce_u64(power as u128 * self.base as u128);
+        Some(power)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (usize::MAX, None)
+    }
+}
+
+/// Returns an iterator over the powers of `base`, starting from 1
+pub fn powers(base: u64) -> Powers {
+    Powers { base, current: 1 }
+}
+
+/// Collects the first `n` powers of `base`, starting from 1
+#[cfg(feature = "std")]
+pub fn powers_collect(base: u64, n: usize) -> Vec<u64> {
+    powers(base).take(n).collect()
+}
+
+pub(super) fn assert_same_length(dst: &[u64], a: &[u64], b: &[u64]) {
+    assert!(
+        dst.len() == a.len() && a.len() == b.len(),
+        "operands differ in length"
+    );
+}
+
+/// Defines a safe elementwise slice kernel over a packed lane operation
+///
+/// The kernel runs `$packed` on whole vectors of `$vector` when `$available`
+/// holds, and `$scalar` on the remainder or, without `$feature`, everywhere.
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+macro_rules! packed_slice_kernel {
+    (
+        $(#[$doc:meta])*
+        $name:ident,
+        $feature:literal,
+        $available:expr,
+        $vector:ty,
+        $load:ident,
+        $store:ident,
+        $packed:ident,
+        $scalar:expr $(,)?
+    ) => {
+        $(#[$doc])*
+        ///
+        /// # Panics
+        ///
+        /// Panics if the slices differ in length.
+        pub fn $name(dst: &mut [u64], a: &[u64], b: &[u64]) {
+            const LANES: usize = core::mem::size_of::<$vector>() / 8;
+
+            #[target_feature(enable = $feature)]
+            fn packed(dst: &mut [u64], a: &[u64], b: &[u64]) {
+                let mut dst_chunks = dst.chunks_exact_mut(LANES);
+                let (a_chunks, b_chunks) = (a.chunks_exact(LANES), b.chunks_exact(LANES));
+                let (a_rest, b_rest) = (a_chunks.remainder(), b_chunks.remainder());
+                for ((d, x), y) in (&mut dst_chunks).zip(a_chunks).zip(b_chunks) {
+                    // each chunk holds one vector of elements, and the accesses may be unaligned
+                    unsafe {
+                        let x = $load(x.as_ptr().cast());
+                        let y = $load(y.as_ptr().cast());
+                        $store(d.as_mut_ptr().cast(), $packed(x, y));
+                    }
+                }
+                let rest = dst_chunks.into_remainder();
+                for ((d, &x), &y) in rest.iter_mut().zip(a_rest).zip(b_rest) {
+                    *d = $scalar(x, y);
+                }
+            }
+
+            $crate::goldilocks::slice::assert_same_length(dst, a, b);
+            if $available {
+                // the target features were detected just above
+                unsafe { packed(dst, a, b) }
+            } else {
+                for ((d, &x), &y) in dst.iter_mut().zip(a).zip(b) {
+                    *d = $scalar(x, y);
+                }
+            }
+        }
+    };
+}
+
+#[cfg(any(target_arch = "x86_64", target_arch = "aarch64"))]
+pub(super) use packed_slice_kernel;
+
+/// Sums a slice with a single final reduction
+///
+/// A `u128` accumulator of canonical terms could only overflow after 2^64 of
+/// them, more than any slice can hold.
+pub fn sum(values: &[u64]) -> u64 {
+    reduce_u64(values.iter().map(|&x| x as u128).sum())
+}
+
+/// Computes the sum of the products a[i] * b[i] with a single final reduction
+///
+/// The 128-bit products are summed into a `u128` whose overflows are counted,
+/// giving a 192-bit accumulator that is folded by 2^128 ≡ -2^32 at the end.
+///
+/// # Panics
+///
+/// Panics if `a` and `b` differ in length.
+pub fn sum_of_products(a: &[u64], b: &[u64]) -> u64 {
+    assert_eq!(a.len(), b.len(), "operands differ in length");
+    let mut acc = LazyAcc::new();
+    for (&x, &y) in a.iter().zip(b) {
+        acc += mul_wide(x, y);
+    }
+    acc.reduce()
+}
+
+/// Computes the inner product of `a` and `b`, reducing once per lane at the end
+///
+/// Products are summed into four independent wide accumulators, so consecutive
+/// additions do not wait on each other's carries.
+///
+/// # Panics
+///
+/// Panics if `a` and `b` differ in length.
+pub fn dot(a: &[u64], b: &[u64]) -> u64 {
+    assert_eq!(a.len(), b.len(), "operands differ in length");
+
+    let mut lanes = [LazyAcc::new(); 4];
+    let (a_chunks, b_chunks) = (a.chunks_exact(4), b.chunks_exact(4));
+    let (a_rest, b_rest) = (a_chunks.remainder(), b_chunks.remainder());
+    for (x, y) in a_chunks.zip(b_chunks) {
+        for lane in 0..4 {
+            lanes[lane] += mul_wide(x[lane], y[lane]);
+        }
+    }
+    for (lane, (&x, &y)) in a_rest.iter().zip(b_rest).enumerate() {
+        lanes[lane] += mul_wide(x, y);
+    }
+
+    lanes
+        .into_iter()
+        .fold(0, |acc, lane| add_winterfell(acc, lane.reduce()))
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+    use crate::goldilocks::{add_modulo, mul_modulo, pow, P64};
+    use crate::rand::random_elements_exact;
+
+    #[test]
+    fn elementwise_kernels() {
+        let len = 1_001;
+        let a = random_elements_exact(len);
+        let b = random_elements_exact(len);
+        let mut dst = vec![0; len];
+
+        add_slices(&mut dst, &a, &b);
+        for ((&d, &x), &y) in dst.iter().zip(&a).zip(&b) {
+            assert_eq!(d, add_modulo(x, y));
+        }
+        mul_slices(&mut dst, &a, &b);
+        for ((&d, &x), &y) in dst.iter().zip(&a).zip(&b) {
+            assert_eq!(d, mul_modulo(x, y));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "operands differ in length")]
+    fn elementwise_length_mismatch() {
+        add_slices(&mut [0; 2], &[1, 2], &[3]);
+    }
+
+    #[test]
+    fn scaling() {
+        for len in [0, 3, 4, 1_001] {
+            let v = &random_elements_exact(len);
+            let s = random_elements_exact(1)[0];
+            let expected = v.iter().map(|&x| mul_modulo(x, s)).collect::<Vec<_>>();
+
+            let mut scaled = v.to_vec();
+            scale_in_place(&mut scaled, s);
+            assert_eq!(scaled, expected);
+
+            let mut scaled = v.to_vec();
+            scale_in_place_unrolled(&mut scaled, s);
+            assert_eq!(scaled, expected);
+        }
+    }
+
+    #[test]
+    fn linear_combinations() {
+        let len = 100;
+        for k in [0, 1, 2, 8] {
+            let coeffs = &random_elements_exact(k);
+            let vectors = (0..k)
+                .map(|_| random_elements_exact(len))
+                .collect::<Vec<_>>();
+            let vectors = vectors.iter().map(Vec::as_slice).collect::<Vec<_>>();
+            let mut dst = vec![1; len];
+            linear_combination(coeffs, &vectors, &mut dst);
+            for (i, &d) in dst.iter().enumerate() {
+                let expected = coeffs
+                    .iter()
+                    .zip(&vectors)
+                    .fold(0, |acc, (&c, v)| add_modulo(acc, mul_modulo(c, v[i])));
+                assert_eq!(d, expected);
+            }
+        }
+
+        let ones = [P64 - 1; 4];
+        let mut dst = [0; 4];
+        linear_combination(&ones, &[&ones[..]; 4], &mut dst);
+        assert_eq!(dst, [4; 4]);
+    }
+
+    #[test]
+    #[should_panic(expected = "operands differ in length")]
+    fn linear_combination_length_mismatch() {
+        linear_combination(&[1, 2], &[&[1, 2], &[3]], &mut [0; 2]);
+    }
+
+    #[test]
+    fn polynomial_evaluation() {
+        let x = random_elements_exact(1)[0];
+        for len in [0, 1, 2, 3, 8, 1_001] {
+            let coeffs = &random_elements_exact(len);
+            let mut expected = 0;
+            let mut power = 1;
+            for &c in coeffs {
+                expected = add_modulo(expected, mul_modulo(c, power));
+                power = mul_modulo(power, x);
+            }
+            assert_eq!(horner_eval(coeffs, x), expected);
+            assert_eq!(even_odd_eval(coeffs, x), expected);
+        }
+        assert_eq!(horner_eval(&[1, 2, 3], 0), 1);
+        assert_eq!(even_odd_eval(&[1, 2, 3], 0), 1);
+    }
+
+    #[test]
+    fn powers_iterator() {
+        let x = random_elements_exact(1)[0];
+        let collected = powers_collect(x, 100);
+        assert_eq!(collected.len(), 100);
+        for (k, (&power, lazy)) in collected.iter().zip(powers(x)).enumerate() {
+            assert_eq!(power, pow(x, k as u64));
+            assert_eq!(lazy, power);
+        }
+        assert_eq!(powers(0).take(3).collect::<Vec<_>>(), [1, 0, 0]);
+        assert!(powers_collect(x, 0).is_empty());
+    }
+
+    #[test]
+    fn slice_sum() {
+        for len in [0, 1, 2, 1_000] {
+            let values = &random_elements_exact(len);
+            let naive = values.iter().fold(0, |acc, &x| add_modulo(acc, x));
+            assert_eq!(sum(values), naive);
+        }
+        assert_eq!(sum(&[P64 - 1; 3]), P64 - 3);
+    }
+
+    #[test]
+    fn sums_of_products() {
+        for len in [0, 1, 2, 3, 17, 1_000] {
+            let a = &random_elements_exact(len);
+            let b = &random_elements_exact(len);
+            let naive = a
+                .iter()
+                .zip(b)
+                .fold(0, |acc, (&x, &y)| add_modulo(acc, mul_modulo(x, y)));
+            assert_eq!(sum_of_products(a, b), naive);
+        }
+
+        // every product is (p - 1)^2, so the accumulator overflows repeatedly
+        let a = vec![P64 - 1; 1_000];
+        assert_eq!(sum_of_products(&a, &a), 1_000);
+    }
+
+    #[test]
+    #[should_panic(expected = "operands differ in length")]
+    fn sum_of_products_length_mismatch() {
+        sum_of_products(&[1, 2], &[3]);
+    }
+
+    #[test]
+    fn dot_product() {
+        for len in [0, 1, 3, 4, 5, 17, 1_000] {
+            let a = &random_elements_exact(len);
+            let b = &random_elements_exact(len);
+            let naive = a
+                .iter()
+                .zip(b)
+                .fold(0, |acc, (&x, &y)| add_modulo(acc, mul_modulo(x, y)));
+            assert_eq!(dot(a, b), naive);
+            assert_eq!(dot(a, b), sum_of_products(a, b));
+        }
+
+        let a = vec![P64 - 1; 1_001];
+        assert_eq!(dot(&a, &a), 1_001);
+    }
+
+    #[test]
+    #[should_panic(expected = "operands differ in length")]
+    fn dot_length_mismatch() {
+        dot(&[1, 2], &[3]);
+    }
+}
@@ -0,0 +1,111 @@
+//! The KoalaBear field `P` = 2^31 - 2^24 + 1 and its reduction strategies
+//!
+//! `Modulo`, `Barrett` and `Montgomery` are the strategies of
+//! [`babybear`](crate::babybear), defined by the same macro on the constants
+//! below. [`Fold`] uses the shape of p instead: since 2^31 ≡ 2^24 - 1, the
+//! bits above bit 31 are folded back in with a shift and a subtraction.
+
+use crate::field31::field31_strategies;
+use crate::meta::{impl_meta, FieldMeta};
+use crate::traits::PrimeField64;
+
+/// 2^31 - 2^24 + 1
+pub const P: u32 = 0x7f00_0001;
+
+/// The description shared by the strategies below
+pub const META: FieldMeta = FieldMeta::new("koalabear", &[P as u64], Some(3));
+
+/// floor(2^64 / p) for Barrett reduction
+pub const BARRETT_MU: u64 = u64::MAX / P as u64;
+
+/// p^-1 mod 2^32, for Montgomery reduction
+pub const MONTGOMERY_INV: u32 = 0x8100_0001;
+
+/// (2^32)^2 mod p, used to move canonical values into Montgomery form
+pub const R2: u32 = 0x17f7_efe4;
+
+field31_strategies!("koalabear", "KoalaBear");
+
+/// Replaces the bits of `x` above bit 31 by their multiple of 2^24 - 1
+#[inline(always)]
+const fn fold(x: u64) -> u64 {
+    let hi = x >> 31;
+    (x & 0x7fff_ffff) + (hi << 24) - hi
+}
+
+/// Reduces any `u64` to its canonical residue by folding with 2^31 ≡ 2^24 - 1
+///
+/// Each fold shortens the value by about 7 bits, so five folds leave less
+/// than 2p, and one conditional subtraction finishes.
+#[inline(always)]
+pub const fn reduce_fold(x: u64) -> u32 {
+    let r = fold(fold(fold(fold(fold(x)))));
+    if r >= P as u64 {
+        (r - P as u64) as u32
+    } else {
+        r as u32
+    }
+}
+
+/// Performs multiplication with `reduce_fold`
+pub const fn mul_fold(x: u32, y: u32) -> u32 {
+    reduce_fold(x as u64 * y as u64)
+}
+
+/// Adds KoalaBear residues under a mask and reduces using `reduce_fold`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Fold;
+
+impl PrimeField64 for Fold {
+    const MODULUS: u64 = P as u64;
+    const NAME: &'static str = "koalabear/fold";
+
+    #[inline(always)]
+    fn add(x: u64, y: u64) -> u64 {
+        add(x as u32, y as u32) as u64
+    }
+
+    #[inline(always)]
+    fn sub(x: u64, y: u64) -> u64 {
+        sub(x as u32, y as u32) as u64
+    }
+
+    #[inline(always)]
+    fn mul(x: u64, y: u64) -> u64 {
+        mul_fold(x as u32, y as u32) as u64
+    }
+
+    #[inline(always)]
+    fn reduce(x: u128) -> u64 {
+        debug_assert!(x >> 64 == 0, "input must fit in 64 bits");
+        reduce_fold(x as u64) as u64
+    }
+}
+
+impl_meta!(META; Fold);
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn constants() {
+        assert_eq!(P, (1 << 31) - (1 << 24) + 1);
+        assert_eq!((META.bits, META.two_adicity), (31, 24));
+        assert_eq!(P.wrapping_mul(MONTGOMERY_INV), 1);
+        assert_eq!(R2 as u128, (1u128 << 64) % P as u128);
+        assert_eq!(BARRETT_MU, ((1u128 << 64) / P as u128) as u64);
+    }
+
+    #[test]
+    fn folding() {
+        let max = (P as u64 - 1) * (P as u64 - 1);
+        for x in [0, 1, P as u64, 2 * P as u64 - 1, max, u64::MAX] {
+            assert_eq!(reduce_fold(x) as u64, x % P as u64);
+        }
+        for (x, y) in Fold::random(1_000).into_iter().zip(Fold::random(1_000)) {
+            assert_eq!(Fold::mul(x, y), Barrett::mul(x, y));
+            assert_eq!(Fold::add(x, y), Barrett::add(x, y));
+        }
+    }
+}
@@ -0,0 +1,194 @@
+//! The Mersenne field `P` = 2^31 - 1 and its shift-based reduction
+//!
+//! Since 2^31 ≡ 1, reduction adds the bits above bit 31 back onto the low 31
+//! bits. Residues are `u32`, and the strategy types implement [`PrimeField64`]
+//! on zero-extended residues like those of [`babybear`](crate::babybear).
+
+use crate::meta::{impl_meta, FieldMeta};
+use crate::traits::PrimeField64;
+
+/// 2^31 - 1
+pub const P: u32 = 0x7fff_ffff;
+
+/// The description shared by the strategies below
+pub const META: FieldMeta = FieldMeta::new("mersenne31", &[P as u64], Some(7));
+
+/// Performs addition with modulo using `%` operator
+pub const fn add_modulo(x: u32, y: u32) -> u32 {
+    ((x as u64 + y as u64) % P as u64) as u32
+}
+
+/// Performs addition of canonical values by folding bit 31 back in
+///
+/// The sum is below 2^32, and adding its top bit to the low 31 bits gives at
+/// most p, which only the sum p itself reaches.
+pub const fn add(x: u32, y: u32) -> u32 {
+    let sum = x + y;
+    let folded = (sum & P) + (sum >> 31);
+    if folded == P {
+        0
+    } else {
+        folded
+    }
+}
+
+/// Performs subtraction of canonical values, adding `P` back under a borrow mask
+pub const fn sub(x: u32, y: u32) -> u32 {
+    let (diff, borrow) = x.overflowing_sub(y);
+    diff.wrapping_add(P & (borrow as u32).wrapping_neg())
+}
+
+/// Performs multiplication with modulo using `%` operator
+pub const fn mul_modulo(x: u32, y: u32) -> u32 {
+    (x as u64 * y as u64 % P as u64) as u32
+}
+
+/// Reduces an `x` of at most 2^62 to a value in [0, 2^31], congruent modulo p
+///
+/// Two folds of the bits above bit 31 suffice: the first leaves less than
+/// 2^32, and the second at most 2^31.
+#[inline(always)]
+pub const fn reduce_partial(x: u64) -> u32 {
+    debug_assert!(x <= 1 << 62, "input must be at most 2^62");
+    let folded = (x & P as u64) + (x >> 31);
+    ((folded & P as u64) + (folded >> 31)) as u32
+}
+
+/// Reduces an `x` of at most 2^62 to its canonical residue
+#[inline(always)]
+pub const fn reduce(x: u64) -> u32 {
+    canonicalize(reduce_partial(x))
+}
+
+/// Maps a value in [0, 2^31] to its canonical residue
+#[inline(always)]
+pub const fn canonicalize(x: u32) -> u32 {
+    if x >= P {
+        x - P
+    } else {
+        x
+    }
+}
+
+/// Performs multiplication with the shift-based `reduce`
+pub const fn mul(x: u32, y: u32) -> u32 {
+    reduce(x as u64 * y as u64)
+}
+
+/// Multiplies values in [0, 2^31], returning a value in [0, 2^31]
+///
+/// The product is at most 2^62, so results can be fed back in without the
+/// final subtraction of `mul`, as long as they are canonicalized at the end.
+pub const fn mul_partial(x: u32, y: u32) -> u32 {
+    reduce_partial(x as u64 * y as u64)
+}
+
+/// Multiplies a canonical `x` by 2^k, which is a rotation of its 31 bits
+pub const fn mul_pow2(x: u32, k: u32) -> u32 {
+    let k = k % 31;
+    let rotated = ((x << k) & P) | (x >> (31 - k));
+    canonicalize(rotated)
+}
+
+/// Adds and reduces Mersenne31 residues using the `%` operator
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Modulo;
+
+impl PrimeField64 for Modulo {
+    const MODULUS: u64 = P as u64;
+    const NAME: &'static str = "mersenne31/mod";
+
+    #[inline(always)]
+    fn add(x: u64, y: u64) -> u64 {
+        add_modulo(x as u32, y as u32) as u64
+    }
+
+    #[inline(always)]
+    fn reduce(x: u128) -> u64 {
+        (x % P as u128) as u64
+    }
+}
+
+/// Adds and reduces Mersenne31 residues by folding the bits above bit 31
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Fold;
+
+impl PrimeField64 for Fold {
+    const MODULUS: u64 = P as u64;
+    const NAME: &'static str = "mersenne31/fold";
+
+    #[inline(always)]
+    fn add(x: u64, y: u64) -> u64 {
+        add(x as u32, y as u32) as u64
+    }
+
+    #[inline(always)]
+    fn sub(x: u64, y: u64) -> u64 {
+        sub(x as u32, y as u32) as u64
+    }
+
+    #[inline(always)]
+    fn mul(x: u64, y: u64) -> u64 {
+        mul(x as u32, y as u32) as u64
+    }
+
+    #[inline(always)]
+    fn reduce(x: u128) -> u64 {
+        debug_assert!(x <= 1 << 62, "input must be at most 2^62");
+        reduce(x as u64) as u64
+    }
+}
+
+impl_meta!(META; Modulo, Fold);
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+
+    fn random_elements(n: usize) -> Vec<u32> {
+        Modulo::random(n).into_iter().map(|x| x as u32).collect()
+    }
+
+    #[test]
+    fn arithmetic() {
+        let edge_cases = [0, 1, P / 2, P / 2 + 1, P - 1, P - 1];
+        for (&x, &y) in random_elements(1_000)
+            .iter()
+            .chain(&edge_cases)
+            .tuple_windows()
+        {
+            assert_eq!(add(x, y), add_modulo(x, y));
+            assert_eq!(add(sub(x, y), y), x);
+            assert_eq!(mul(x, y), mul_modulo(x, y));
+            assert_eq!(Fold::mul(x as u64, y as u64), mul_modulo(x, y) as u64);
+            assert_eq!(Fold::add(x as u64, y as u64), add_modulo(x, y) as u64);
+            for k in [0, 1, 30, 31, 62] {
+                let power = (1u64 << (k % 31)) as u32;
+                assert_eq!(mul_pow2(x, k), mul_modulo(x, power));
+            }
+        }
+    }
+
+    #[test]
+    fn partial_reduction() {
+        for x in [0, 1, P as u64, 1 << 31, (P as u64) * (P as u64), 1 << 62] {
+            let partial = reduce_partial(x);
+            assert!(partial <= 1 << 31);
+            assert_eq!(canonicalize(partial) as u64, x % P as u64);
+            assert_eq!(reduce(x) as u64, x % P as u64);
+        }
+
+        // a chain of partial products only needs canonicalizing at the end
+        for (&x, &y) in random_elements(1_000).iter().tuple_windows() {
+            let (mut partial, mut strict) = (x, x);
+            for _ in 0..8 {
+                partial = mul_partial(partial, y);
+                strict = mul(strict, y);
+            }
+            assert!(partial <= 1 << 31);
+            assert_eq!(canonicalize(partial), strict);
+        }
+    }
+}
@@ -0,0 +1,166 @@
+//! The Mersenne field `P` = 2^61 - 1 and its mask-and-fold reduction
+//!
+//! Since 2^61 ≡ 1, reduction masks off the low 61 bits and adds the rest back.
+//! Residues leave three slack bits in a `u64`, so up to eight of them can be
+//! added before any reduction is needed.
+
+use crate::meta::{impl_meta, FieldMeta};
+use crate::traits::PrimeField64;
+
+/// 2^61 - 1
+pub const P: u64 = 0x1fff_ffff_ffff_ffff;
+
+/// The description shared by the strategies below
+pub const META: FieldMeta = FieldMeta::new("mersenne61", &[P], Some(37));
+
+/// Performs addition with modulo using `%` operator
+pub const fn add_modulo(x: u64, y: u64) -> u64 {
+    (x + y) % P
+}
+
+/// Performs addition of canonical values by folding bit 61 back in
+pub const fn add(x: u64, y: u64) -> u64 {
+    reduce64(x + y)
+}
+
+/// Performs subtraction of canonical values, adding `P` back under a borrow mask
+pub const fn sub(x: u64, y: u64) -> u64 {
+    let (diff, borrow) = x.overflowing_sub(y);
+    diff.wrapping_add(P & (borrow as u64).wrapping_neg())
+}
+
+/// Performs multiplication with modulo using `%` operator
+pub const fn mul_modulo(x: u64, y: u64) -> u64 {
+    (x as u128 * y as u128 % P as u128) as u64
+}
+
+/// Reduces any `u64` to its canonical residue with a single fold
+///
+/// The fold leaves at most p + 7, so one conditional subtraction finishes.
+#[inline(always)]
+pub const fn reduce64(x: u64) -> u64 {
+    let folded = (x & P) + (x >> 61);
+    if folded >= P {
+        folded - P
+    } else {
+        folded
+    }
+}
+
+/// Reduces an `x` below p * 2^61, such as a product of residues, to canonical form
+///
+/// The high part is below p, so the fold sum is below 2p and one conditional
+/// subtraction finishes.
+#[inline(always)]
+pub const fn reduce(x: u128) -> u64 {
+    debug_assert!(x < (P as u128) << 61, "input must be below p * 2^61");
+    let folded = (x as u64 & P) + (x >> 61) as u64;
+    if folded >= P {
+        folded - P
+    } else {
+        folded
+    }
+}
+
+/// Performs multiplication with the mask-and-fold `reduce`
+pub const fn mul(x: u64, y: u64) -> u64 {
+    reduce(x as u128 * y as u128)
+}
+
+/// Sums canonical values, reducing once per eight terms using the slack bits
+pub fn sum(values: &[u64]) -> u64 {
+    values.chunks(8).fold(0, |acc, chunk| {
+        // acc and seven residues stay below 2^64; the eighth is folded in after
+        let (last, rest) = chunk.split_last().unwrap();
+        let partial = rest.iter().fold(acc, |acc, &x| acc + x);
+        add(reduce64(partial), *last)
+    })
+}
+
+/// Adds and reduces Mersenne61 residues using the `%` operator
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Modulo;
+
+impl PrimeField64 for Modulo {
+    const MODULUS: u64 = P;
+    const NAME: &'static str = "mersenne61/mod";
+
+    #[inline(always)]
+    fn add(x: u64, y: u64) -> u64 {
+        add_modulo(x, y)
+    }
+
+    #[inline(always)]
+    fn reduce(x: u128) -> u64 {
+        (x % P as u128) as u64
+    }
+}
+
+/// Adds and reduces Mersenne61 residues by masking and folding
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Fold;
+
+impl PrimeField64 for Fold {
+    const MODULUS: u64 = P;
+    const NAME: &'static str = "mersenne61/fold";
+
+    #[inline(always)]
+    fn add(x: u64, y: u64) -> u64 {
+        add(x, y)
+    }
+
+    #[inline(always)]
+    fn sub(x: u64, y: u64) -> u64 {
+        sub(x, y)
+    }
+
+    #[inline(always)]
+    fn reduce(x: u128) -> u64 {
+        reduce(x)
+    }
+}
+
+impl_meta!(META; Modulo, Fold);
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+
+    #[test]
+    fn arithmetic() {
+        let edge_cases = [0, 1, P / 2, P / 2 + 1, P - 1, P - 1];
+        for (&x, &y) in Modulo::random(1_000)
+            .iter()
+            .chain(&edge_cases)
+            .tuple_windows()
+        {
+            assert_eq!(add(x, y), add_modulo(x, y));
+            assert_eq!(add(sub(x, y), y), x);
+            assert_eq!(mul(x, y), mul_modulo(x, y));
+            assert_eq!(Fold::mul(x, y), mul_modulo(x, y));
+        }
+    }
+
+    #[test]
+    fn reductions() {
+        for x in [0, 1, P - 1, P, P + 7, 1 << 61, u64::MAX] {
+            assert_eq!(reduce64(x), x % P);
+        }
+        let max = (P as u128 - 1) * (P as u128 - 1);
+        for x in [0, 1, P as u128, max, ((P as u128) << 61) - 1] {
+            assert_eq!(reduce(x) as u128, x % P as u128);
+        }
+    }
+
+    #[test]
+    fn sum_with_slack_bits() {
+        for len in [0, 1, 7, 8, 9, 100] {
+            let values = Modulo::random(len);
+            let expected = values.iter().fold(0, |acc, &x| add_modulo(acc, x));
+            assert_eq!(sum(&values), expected);
+        }
+        assert_eq!(sum(&[P - 1; 17]), (17 * (P as u128 - 1) % P as u128) as u64);
+    }
+}
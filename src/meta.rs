@@ -0,0 +1,213 @@
+//! Descriptions of each field, for labelling benchmark output
+
+use core::fmt;
+
+/// Static facts about a field, independent of the strategy implementing it
+///
+/// The modulus, bit size, two-adicity and generator describe the prime field;
+/// an extension shares them with its base field and records its degree.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FieldMeta {
+    /// Name of the field, shared by all its strategies
+    pub name: &'static str,
+    /// The prime modulus, least significant limb first
+    pub modulus: &'static [u64],
+    /// The bit size of the modulus
+    pub bits: u32,
+    /// The largest k such that 2^k divides p - 1
+    pub two_adicity: u32,
+    /// A generator of the multiplicative group, where one is known
+    pub generator: Option<u64>,
+    /// The degree over the prime field, 1 for the prime field itself
+    pub extension_degree: usize,
+}
+
+impl FieldMeta {
+    /// Describes the prime field with the given odd `modulus`
+    pub const fn new(name: &'static str, modulus: &'static [u64], generator: Option<u64>) -> Self {
+        let mut top = modulus.len() - 1;
+        while top > 0 && modulus[top] == 0 {
+            top -= 1;
+        }
+        let bits = 64 * top as u32 + (64 - modulus[top].leading_zeros());
+
+        // p is odd, so subtracting one does not borrow past the lowest limb
+        let mut two_adicity = (modulus[0] - 1).trailing_zeros();
+        let mut i = 1;
+        while two_adicity == 64 * i as u32 && i < modulus.len() {
+            two_adicity += modulus[i].trailing_zeros();
+            i += 1;
+        }
+
+        Self {
+            name,
+            modulus,
+            bits,
+            two_adicity,
+            generator,
+            extension_degree: 1,
+        }
+    }
+
+    /// Describes the extension of degree `degree` over this field
+    pub const fn extension(self, degree: usize) -> Self {
+        Self {
+            extension_degree: self.extension_degree * degree,
+            ..self
+        }
+    }
+}
+
+/// Prints the name, with the extension degree as an exponent
+impl fmt::Display for FieldMeta {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.extension_degree {
+            1 => write!(f, "{}", self.name),
+            degree => write!(f, "{}^{degree}", self.name),
+        }
+    }
+}
+
+/// A field implementation that can describe its field
+pub trait Meta {
+    /// The description of the implemented field
+    const META: FieldMeta;
+}
+
+/// Implements [`Meta`] with the same description for each of the listed types
+macro_rules! impl_meta {
+    ($meta:expr; $($field:ty),* $(,)?) => {
+        $(
+            impl $crate::meta::Meta for $field {
+                const META: $crate::meta::FieldMeta = $meta;
+            }
+        )*
+    };
+}
+
+pub(crate) use impl_meta;
+
+/// A name assembled at compile time, for strategies generic over their modulus
+pub(crate) struct ConstName {
+    bytes: [u8; 64],
+    len: usize,
+}
+
+impl ConstName {
+    /// Starts a name with `s`
+    pub(crate) const fn new(s: &str) -> Self {
+        Self {
+            bytes: [0; 64],
+            len: 0,
+        }
+        .push_str(s)
+    }
+
+    /// Appends `s`
+    pub(crate) const fn push_str(mut self, s: &str) -> Self {
+        let s = s.as_bytes();
+        let mut i = 0;
+        while i < s.len() {
+            self.bytes[self.len] = s[i];
+            self.len += 1;
+            i += 1;
+        }
+        self
+    }
+
+    /// Appends `x` in the given radix, with lowercase digits
+    pub(crate) const fn push_radix(mut self, x: u64, radix: u64) -> Self {
+        let mut digits = 1;
+        while x / radix.pow(digits - 1) >= radix {
+            digits += 1;
+        }
+        while digits > 0 {
+            digits -= 1;
+            let d = (x / radix.pow(digits) % radix) as u8;
+            self.bytes[self.len] = if d < 10 { b'0' + d } else { b'a' + d - 10 };
+            self.len += 1;
+        }
+        self
+    }
+
+    /// Returns the name built so far
+    pub(crate) const fn as_str(&self) -> &str {
+        match core::str::from_utf8(self.bytes.split_at(self.len).0) {
+            Ok(s) => s,
+            Err(_) => panic!("names are built from whole strings and digits"),
+        }
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn derived_constants() {
+        let small = FieldMeta::new("small", &[65537], Some(3));
+        assert_eq!((small.bits, small.two_adicity), (17, 16));
+
+        let p128 = FieldMeta::new("p128", &[1, 0xffff_f000_0000_0000], None);
+        assert_eq!((p128.bits, p128.two_adicity), (128, 108));
+
+        let padded = FieldMeta::new("padded", &[7, 1, 0], None);
+        assert_eq!((padded.bits, padded.two_adicity), (65, 1));
+
+        assert_eq!(small.to_string(), "small");
+        assert_eq!(crate::babybear::Fp5::META.to_string(), "babybear^5");
+        assert_eq!(small.extension(2).extension(3).to_string(), "small^6");
+    }
+
+    #[test]
+    fn const_names() {
+        let name = ConstName::new("2^")
+            .push_radix(64, 10)
+            .push_str("-")
+            .push_radix(0, 10);
+        assert_eq!(name.as_str(), "2^64-0");
+        let name = ConstName::new("0x").push_radix(u64::MAX, 16);
+        assert_eq!(name.as_str(), "0xffffffffffffffff");
+        assert_eq!(
+            ConstName::new("").push_radix(u64::MAX, 10).as_str(),
+            "18446744073709551615"
+        );
+    }
+
+    #[test]
+    fn generators() {
+        fn pow(mut base: u64, mut exp: u64, p: u64) -> u64 {
+            let mut acc = 1;
+            while exp > 0 {
+                if exp & 1 == 1 {
+                    acc = (acc as u128 * base as u128 % p as u128) as u64;
+                }
+                base = (base as u128 * base as u128 % p as u128) as u64;
+                exp >>= 1;
+            }
+            acc
+        }
+
+        // p - 1 is smooth for each of these, so trial division finds its factors
+        for meta in [
+            crate::goldilocks::constants::META,
+            crate::babybear::META,
+            crate::koalabear::META,
+            crate::mersenne31::META,
+            crate::mersenne61::META,
+        ] {
+            let (p, g) = (meta.modulus[0], meta.generator.unwrap());
+            assert_eq!(meta.two_adicity, (p - 1).trailing_zeros(), "{meta}");
+            let (mut rest, mut q) = (p - 1, 2);
+            while rest > 1 {
+                if rest % q == 0 {
+                    assert_ne!(pow(g, (p - 1) / q, p), 1, "{meta}: {g}^((p - 1) / {q})");
+                    while rest % q == 0 {
+                        rest /= q;
+                    }
+                }
+                q += 1;
+            }
+        }
+    }
+}
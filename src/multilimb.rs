@@ -0,0 +1,458 @@
+//! Montgomery arithmetic over moduli of several 64-bit limbs
+
+/// An odd modulus of `N` 64-bit limbs, least significant first, with its
+/// Montgomery constants
+///
+/// Elements are `[u64; N]` in Montgomery form, x * 2^(64N) mod p. Both the
+/// separated (SOS) and the coarsely integrated (CIOS) operand scanning
+/// multiplications are provided, since which one is faster depends on the
+/// micro-architecture.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MultiLimbModulus<const N: usize> {
+    /// The modulus p
+    pub modulus: [u64; N],
+    /// -p^-1 mod 2^64
+    pub neg_inv: u64,
+    /// (2^(64N))^2 mod p, used to move canonical values into Montgomery form
+    pub r2: [u64; N],
+    /// 2p, the bound on values in the relaxed range
+    pub double_modulus: [u64; N],
+}
+
+/// Computes a + b * c + carry as its low and high words, which cannot overflow
+#[inline(always)]
+pub(crate) const fn mac(a: u64, b: u64, c: u64, carry: u64) -> (u64, u64) {
+    let t = a as u128 + b as u128 * c as u128 + carry as u128;
+    (t as u64, (t >> 64) as u64)
+}
+
+/// Computes a + b + carry as its low word and carry
+#[inline(always)]
+pub(crate) const fn adc(a: u64, b: u64, carry: u64) -> (u64, u64) {
+    let t = a as u128 + b as u128 + carry as u128;
+    (t as u64, (t >> 64) as u64)
+}
+
+/// Computes x^-1 mod 2^64 for an odd x by Newton iteration, like `montgomery_inv`
+/// but for any odd limb rather than an odd prime
+const fn limb_inv(x: u64) -> u64 {
+    let mut inv = x;
+    let mut i = 0;
+    while i < 5 {
+        inv = inv.wrapping_mul(2u64.wrapping_sub(x.wrapping_mul(inv)));
+        i += 1;
+    }
+    inv
+}
+
+/// Returns whether x >= y
+#[inline(always)]
+pub(crate) const fn geq<const N: usize>(x: &[u64; N], y: &[u64; N]) -> bool {
+    let mut i = N;
+    while i > 0 {
+        i -= 1;
+        if x[i] != y[i] {
+            return x[i] > y[i];
+        }
+    }
+    true
+}
+
+/// Computes x - y modulo 2^(64N) and the final borrow
+#[inline(always)]
+pub(crate) const fn sbb<const N: usize>(x: &[u64; N], y: &[u64; N]) -> ([u64; N], bool) {
+    let mut diff = [0; N];
+    let mut borrow = false;
+    let mut i = 0;
+    while i < N {
+        let (d, b1) = x[i].overflowing_sub(y[i]);
+        let (d, b2) = d.overflowing_sub(borrow as u64);
+        diff[i] = d;
+        borrow = b1 | b2;
+        i += 1;
+    }
+    (diff, borrow)
+}
+
+/// Computes the full 512-bit product of 4-limb `x` and `y`
+#[inline(always)]
+pub(crate) const fn mul_wide4(x: &[u64; 4], y: &[u64; 4]) -> [u64; 8] {
+    let mut t = [0u64; 8];
+    let mut i = 0;
+    while i < 4 {
+        let mut carry = 0;
+        let mut j = 0;
+        while j < 4 {
+            (t[i + j], carry) = mac(t[i + j], x[j], y[i], carry);
+            j += 1;
+        }
+        t[i + 4] = carry;
+        i += 1;
+    }
+    t
+}
+
+impl<const N: usize> MultiLimbModulus<N> {
+    /// Computes the Montgomery constants of an odd `modulus`
+    pub const fn new(modulus: [u64; N]) -> Self {
+        assert!(N > 0 && modulus[0] % 2 == 1, "modulus must be odd");
+        assert!(modulus[N - 1] != 0, "top limb must be nonzero");
+
+        let mut this = Self {
+            modulus,
+            neg_inv: 0u64.wrapping_sub(limb_inv(modulus[0])),
+            r2: [0; N],
+            double_modulus: [0; N],
+        };
+
+        let mut carry = 0;
+        let mut i = 0;
+        while i < N {
+            this.double_modulus[i] = modulus[i] << 1 | carry;
+            carry = modulus[i] >> 63;
+            i += 1;
+        }
+
+        // 2^(128N) mod p by doubling one modulo p
+        let mut r2 = [0; N];
+        r2[0] = 1;
+        let mut i = 0;
+        while i < 128 * N {
+            r2 = this.add(&r2, &r2);
+            i += 1;
+        }
+        this.r2 = r2;
+        this
+    }
+
+    /// Adds two values below p
+    #[inline]
+    pub const fn add(&self, x: &[u64; N], y: &[u64; N]) -> [u64; N] {
+        let mut sum = [0; N];
+        let mut carry = 0;
+        let mut i = 0;
+        while i < N {
+            (sum[i], carry) = adc(x[i], y[i], carry);
+            i += 1;
+        }
+        if carry != 0 || geq(&sum, &self.modulus) {
+            sbb(&sum, &self.modulus).0
+        } else {
+            sum
+        }
+    }
+
+    /// Subtracts two values below p
+    #[inline]
+    pub const fn sub(&self, x: &[u64; N], y: &[u64; N]) -> [u64; N] {
+        let (diff, borrow) = sbb(x, y);
+        if !borrow {
+            return diff;
+        }
+        let mut result = [0; N];
+        let mut carry = 0;
+        let mut i = 0;
+        while i < N {
+            (result[i], carry) = adc(diff[i], self.modulus[i], carry);
+            i += 1;
+        }
+        result
+    }
+
+    /// Multiplies two values in Montgomery form with separated operand scanning
+    ///
+    /// The full 2N-limb product is computed first, and then reduced one limb
+    /// at a time by adding multiples of p.
+    #[inline]
+    pub const fn mul_sos(&self, x: &[u64; N], y: &[u64; N]) -> [u64; N] {
+        let p = &self.modulus;
+
+        // t = x * y, as its low and high N limbs
+        let mut t = [[0u64; N]; 2];
+        let mut i = 0;
+        while i < N {
+            let mut carry = 0;
+            let mut j = 0;
+            while j < N {
+                let k = i + j;
+                (t[k / N][k % N], carry) = mac(t[k / N][k % N], x[j], y[i], carry);
+                j += 1;
+            }
+            t[1][i] = carry;
+            i += 1;
+        }
+
+        // clear the low limbs, carrying out of the top into `top`
+        let mut top = 0;
+        let mut i = 0;
+        while i < N {
+            let m = t[0][i].wrapping_mul(self.neg_inv);
+            let mut carry = 0;
+            let mut j = 0;
+            while j < N {
+                let k = i + j;
+                (t[k / N][k % N], carry) = mac(t[k / N][k % N], m, p[j], carry);
+                j += 1;
+            }
+            let mut k = i + N;
+            while k < 2 * N {
+                (t[1][k - N], carry) = adc(t[1][k - N], carry, 0);
+                k += 1;
+            }
+            top += carry;
+            i += 1;
+        }
+
+        if top != 0 || geq(&t[1], p) {
+            sbb(&t[1], p).0
+        } else {
+            t[1]
+        }
+    }
+
+    /// Multiplies two values in Montgomery form with coarsely integrated operand scanning
+    ///
+    /// Each limb of `y` is multiplied in and one limb reduced away in turn, so
+    /// the accumulator never grows beyond N + 2 limbs.
+    #[inline]
+    pub const fn mul_cios(&self, x: &[u64; N], y: &[u64; N]) -> [u64; N] {
+        let p = &self.modulus;
+
+        let mut t = [0u64; N];
+        let mut t_n = 0;
+        let mut i = 0;
+        while i < N {
+            let mut carry = 0;
+            let mut j = 0;
+            while j < N {
+                (t[j], carry) = mac(t[j], x[j], y[i], carry);
+                j += 1;
+            }
+            let t_n1;
+            (t_n, t_n1) = adc(t_n, carry, 0);
+
+            let m = t[0].wrapping_mul(self.neg_inv);
+            let (_, mut carry) = mac(t[0], m, p[0], 0);
+            let mut j = 1;
+            while j < N {
+                (t[j - 1], carry) = mac(t[j], m, p[j], carry);
+                j += 1;
+            }
+            (t[N - 1], carry) = adc(t_n, carry, 0);
+            t_n = t_n1 + carry;
+            i += 1;
+        }
+
+        if t_n != 0 || geq(&t, p) {
+            sbb(&t, p).0
+        } else {
+            t
+        }
+    }
+
+    /// Returns whether p < 2^(64N - 2), as the relaxed operations require
+    ///
+    /// Then 4p fits in N limbs, so sums and products of values below 2p
+    /// cannot overflow.
+    pub const fn supports_relaxed(&self) -> bool {
+        self.modulus[N - 1] >> 62 == 0
+    }
+
+    /// Adds two values in [0, 2p), returning a value in [0, 2p)
+    #[inline]
+    pub const fn add_relaxed(&self, x: &[u64; N], y: &[u64; N]) -> [u64; N] {
+        debug_assert!(self.supports_relaxed(), "modulus must be below 2^(64N - 2)");
+        let mut sum = [0; N];
+        let mut carry = 0;
+        let mut i = 0;
+        while i < N {
+            (sum[i], carry) = adc(x[i], y[i], carry);
+            i += 1;
+        }
+        if geq(&sum, &self.double_modulus) {
+            sbb(&sum, &self.double_modulus).0
+        } else {
+            sum
+        }
+    }
+
+    /// Multiplies two values in Montgomery form and [0, 2p) like `mul_cios`,
+    /// returning a value in [0, 2p)
+    ///
+    /// The result of REDC is below (4p^2 + 2^(64N) * p) / 2^(64N) < 2p, so the
+    /// final conditional subtraction is skipped.
+    #[inline]
+    pub const fn mul_cios_relaxed(&self, x: &[u64; N], y: &[u64; N]) -> [u64; N] {
+        debug_assert!(self.supports_relaxed(), "modulus must be below 2^(64N - 2)");
+        let p = &self.modulus;
+
+        let mut t = [0u64; N];
+        let mut t_n = 0;
+        let mut i = 0;
+        while i < N {
+            let mut carry = 0;
+            let mut j = 0;
+            while j < N {
+                (t[j], carry) = mac(t[j], x[j], y[i], carry);
+                j += 1;
+            }
+            (t_n, _) = adc(t_n, carry, 0);
+
+            let m = t[0].wrapping_mul(self.neg_inv);
+            let (_, mut carry) = mac(t[0], m, p[0], 0);
+            let mut j = 1;
+            while j < N {
+                (t[j - 1], carry) = mac(t[j], m, p[j], carry);
+                j += 1;
+            }
+            (t[N - 1], t_n) = adc(t_n, carry, 0);
+            i += 1;
+        }
+        t
+    }
+
+    /// Reduces a value in [0, 2p) to [0, p)
+    #[inline]
+    pub const fn reduce_relaxed(&self, x: &[u64; N]) -> [u64; N] {
+        if geq(x, &self.modulus) {
+            sbb(x, &self.modulus).0
+        } else {
+            *x
+        }
+    }
+
+    /// Moves a canonical value into Montgomery form
+    #[inline]
+    pub const fn to_montgomery(&self, x: &[u64; N]) -> [u64; N] {
+        self.mul_cios(x, &self.r2)
+    }
+
+    /// Moves a value in Montgomery form back to its canonical value
+    #[inline]
+    pub const fn from_montgomery(&self, x: &[u64; N]) -> [u64; N] {
+        let mut one = [0; N];
+        one[0] = 1;
+        self.mul_cios(x, &one)
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use ::rand::{thread_rng, Rng};
+    use num_bigint::BigUint;
+
+    use super::*;
+    use crate::goldilocks::{mul_modulo, P64};
+    use crate::rand::random_elements;
+
+    fn to_biguint<const N: usize>(x: &[u64; N]) -> BigUint {
+        x.iter()
+            .rev()
+            .fold(BigUint::default(), |acc, &limb| (acc << 64u32) + limb)
+    }
+
+    fn to_limbs<const N: usize>(x: &BigUint) -> [u64; N] {
+        let mut limbs = [0; N];
+        for (limb, digit) in limbs.iter_mut().zip(x.to_u64_digits()) {
+            *limb = digit;
+        }
+        limbs
+    }
+
+    fn check<const N: usize>(modulus: [u64; N]) {
+        let field = MultiLimbModulus::new(modulus);
+        let p = to_biguint(&modulus);
+        let r = (BigUint::from(1u32) << (64 * N)) % &p;
+        assert_eq!(to_biguint(&field.r2), &r * &r % &p);
+
+        let mut rng = thread_rng();
+        let mut random = || {
+            let limbs = core::array::from_fn::<u64, N, _>(|_| rng.gen());
+            to_limbs::<N>(&(to_biguint(&limbs) % &p))
+        };
+        let max = to_limbs::<N>(&(&p - 1u32));
+        for _ in 0..1_000 {
+            let (x, y) = (random(), random());
+            for (x, y) in [(x, y), (max, y), (max, max), ([0; N], y)] {
+                let (a, b) = (to_biguint(&x), to_biguint(&y));
+                let (x_m, y_m) = (field.to_montgomery(&x), field.to_montgomery(&y));
+                assert_eq!(field.from_montgomery(&x_m), x);
+
+                let product = to_limbs(&(&a * &b % &p));
+                assert_eq!(field.from_montgomery(&field.mul_sos(&x_m, &y_m)), product);
+                assert_eq!(field.from_montgomery(&field.mul_cios(&x_m, &y_m)), product);
+                assert_eq!(field.add(&x, &y), to_limbs(&((&a + &b) % &p)));
+                assert_eq!(field.sub(&x, &y), to_limbs(&((&a + &p - &b) % &p)));
+            }
+        }
+    }
+
+    #[test]
+    fn single_limb() {
+        check([P64]);
+        let field = MultiLimbModulus::new([P64]);
+        for (&x, &y) in random_elements(1_000)
+            .iter()
+            .zip(random_elements(1_000).iter())
+        {
+            let product = field.mul_cios(&field.to_montgomery(&[x]), &field.to_montgomery(&[y]));
+            assert_eq!(field.from_montgomery(&product), [mul_modulo(x, y)]);
+        }
+    }
+
+    fn check_relaxed<const N: usize>(modulus: [u64; N]) {
+        let field = MultiLimbModulus::new(modulus);
+        assert!(field.supports_relaxed());
+        let p = to_biguint(&modulus);
+        let double_p = to_limbs::<N>(&(&p << 1u32));
+        assert_eq!(field.double_modulus, double_p);
+
+        let mut rng = thread_rng();
+        let mut random = || {
+            let limbs = core::array::from_fn::<u64, N, _>(|_| rng.gen());
+            to_limbs::<N>(&(to_biguint(&limbs) % (&p << 1u32)))
+        };
+        let max = to_limbs::<N>(&((&p << 1u32) - 1u32));
+        for _ in 0..1_000 {
+            let (x, y) = (random(), random());
+            for (x, y) in [(x, y), (max, y), (max, max), ([0; N], y)] {
+                let (a, b) = (to_biguint(&x), to_biguint(&y));
+                let strict = field.mul_cios(&to_limbs(&(&a % &p)), &to_limbs(&(&b % &p)));
+                let relaxed = field.mul_cios_relaxed(&x, &y);
+                assert!(to_biguint(&relaxed) < to_biguint(&double_p));
+                assert_eq!(field.reduce_relaxed(&relaxed), strict);
+
+                let sum = field.add_relaxed(&x, &y);
+                assert!(to_biguint(&sum) < to_biguint(&double_p));
+                assert_eq!(to_biguint(&sum) % &p, (&a + &b) % &p);
+            }
+        }
+    }
+
+    #[test]
+    fn relaxed_range() {
+        // 2^61 - 1
+        check_relaxed([u64::MAX >> 3]);
+        // 2^251 + 17 * 2^192 + 1
+        check_relaxed([1, 0, 0, 0x0800_0000_0000_0011]);
+        check_relaxed([
+            0xb9fe_ffff_ffff_aaab,
+            0x1eab_fffe_b153_ffff,
+            0x6730_d2a0_f6b0_f624,
+            0x6477_4b84_f385_12bf,
+            0x4b1b_a7b6_434b_acd7,
+            0x1a01_11ea_397f_e69a,
+        ]);
+        assert!(!MultiLimbModulus::new([P64]).supports_relaxed());
+    }
+
+    #[test]
+    fn multiple_limbs() {
+        // 2^127 - 1
+        check([u64::MAX, u64::MAX >> 1]);
+        // 2^255 - 19
+        check([u64::MAX - 18, u64::MAX, u64::MAX, u64::MAX >> 1]);
+        // 2^256 - 2^32 - 977, which leaves no spare bit in the top limb
+        check([0xffff_fffe_ffff_fc2f, u64::MAX, u64::MAX, u64::MAX]);
+    }
+}
@@ -0,0 +1,91 @@
+//! The 128-bit prime field `P` = 2^128 - 2^108 + 1 over two 64-bit limbs
+//!
+//! Elements are `[u64; 2]` in Montgomery form, least significant limb first.
+//! [`FIELD`] provides the generic [`MultiLimbModulus`] operations, and [`mul`]
+//! a CIOS multiplication specialized to the shape of p. With p - 1 divisible
+//! by 2^108, the field supports NTTs of any practical size.
+
+use crate::meta::FieldMeta;
+use crate::multilimb::{adc, geq, mac, sbb, MultiLimbModulus};
+
+/// 2^128 - 2^108 + 1, least significant limb first
+pub const MODULUS: [u64; 2] = [1, 0xffff_f000_0000_0000];
+
+/// The description of the field
+pub const META: FieldMeta = FieldMeta::new("p128", &MODULUS, Some(59));
+
+/// The generic Montgomery constants and operations for `MODULUS`
+pub const FIELD: MultiLimbModulus<2> = MultiLimbModulus::new(MODULUS);
+
+/// The largest k such that 2^k divides p - 1
+pub const TWO_ADICITY: u32 = 108;
+
+/// Multiplies two values in Montgomery form, like `FIELD.mul_cios`
+///
+/// Since p ≡ 1 mod 2^64, the reduction factor for each limb is its negation,
+/// so no multiplication by -p^-1 is needed.
+#[inline]
+pub const fn mul(x: &[u64; 2], y: &[u64; 2]) -> [u64; 2] {
+    let mut t = [0u64; 2];
+    let mut t_2 = 0;
+    let mut i = 0;
+    while i < 2 {
+        let (t_0, carry) = mac(t[0], x[0], y[i], 0);
+        let (t_1, carry) = mac(t[1], x[1], y[i], carry);
+        let t_3;
+        (t_2, t_3) = adc(t_2, carry, 0);
+
+        let m = t_0.wrapping_neg();
+        let (_, carry) = adc(t_0, m, 0);
+        let (t_0, carry) = mac(t_1, m, MODULUS[1], carry);
+        let (t_1, carry) = adc(t_2, carry, 0);
+        t = [t_0, t_1];
+        t_2 = t_3 + carry;
+        i += 1;
+    }
+
+    if t_2 != 0 || geq(&t, &MODULUS) {
+        sbb(&t, &MODULUS).0
+    } else {
+        t
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use ::rand::{thread_rng, Rng};
+    use num_bigint::BigUint;
+
+    use super::*;
+
+    fn to_biguint(x: &[u64; 2]) -> BigUint {
+        BigUint::from(x[1]) << 64u32 | BigUint::from(x[0])
+    }
+
+    #[test]
+    fn constants() {
+        let p = (BigUint::from(1u32) << 128u32) - (BigUint::from(1u32) << 108u32) + 1u32;
+        assert_eq!(to_biguint(&MODULUS), p);
+        assert_eq!((&p - 1u32).trailing_zeros(), Some(TWO_ADICITY as u64));
+        assert_eq!((META.bits, META.two_adicity), (128, TWO_ADICITY));
+        assert_eq!(FIELD.neg_inv, u64::MAX);
+        assert!(!FIELD.supports_relaxed());
+    }
+
+    #[test]
+    fn specialized_mul() {
+        let p = to_biguint(&MODULUS);
+        let mut rng = thread_rng();
+        let mut random = || [rng.gen(), rng.gen::<u64>() % MODULUS[1]];
+        let max = [0, MODULUS[1]];
+        for _ in 0..1_000 {
+            let (x, y) = (random(), random());
+            for (x, y) in [(x, y), (max, y), (max, max), ([0; 2], y)] {
+                let (x_m, y_m) = (FIELD.to_montgomery(&x), FIELD.to_montgomery(&y));
+                let product = FIELD.from_montgomery(&mul(&x_m, &y_m));
+                assert_eq!(product, FIELD.from_montgomery(&FIELD.mul_cios(&x_m, &y_m)));
+                assert_eq!(to_biguint(&product), to_biguint(&x) * to_biguint(&y) % &p);
+            }
+        }
+    }
+}
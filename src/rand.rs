@@ -0,0 +1,59 @@
+//! Sampling of random field elements
+
+use ::rand::{thread_rng, Rng};
+
+use crate::goldilocks::P64;
+
+pub fn random_elements(n: usize) -> Vec<u64> {
+    (0..n + 1)
+        .map(|_| thread_rng().gen_range(0..P64))
+        .collect::<Vec<_>>()
+}
+
+/// Samples exactly `n` uniformly random field elements
+///
+/// Unlike [`random_elements`], which returns one extra element.
+pub fn random_elements_exact(n: usize) -> Vec<u64> {
+    (0..n)
+        .map(|_| thread_rng().gen_range(0..P64))
+        .collect::<Vec<_>>()
+}
+
+/// Samples `n` uniformly random non-zero, hence invertible, field elements
+pub fn random_nonzero_elements(n: usize) -> Vec<u64> {
+    random_nonzero_elements_with(&mut thread_rng(), n)
+}
+
+/// Samples `n` uniformly random non-zero field elements from `rng`
+pub fn random_nonzero_elements_with<R: Rng + ?Sized>(rng: &mut R, n: usize) -> Vec<u64> {
+    (0..n).map(|_| rng.gen_range(1..P64)).collect::<Vec<_>>()
+}
+
+#[cfg(test)]
+mod tests {
+    use ::rand::rngs::StdRng;
+    use ::rand::SeedableRng;
+
+    use super::*;
+
+    #[test]
+    fn exact_sampling() {
+        for n in [0, 1, 1_000] {
+            let elements = random_elements_exact(n);
+            assert_eq!(elements.len(), n);
+            assert!(elements.iter().all(|&x| x < P64));
+        }
+    }
+
+    #[test]
+    fn nonzero_sampling() {
+        let elements = random_nonzero_elements(1_000);
+        assert_eq!(elements.len(), 1_000);
+        assert!(elements.iter().all(|&x| x != 0 && x < P64));
+
+        let mut rng = StdRng::seed_from_u64(0);
+        let seeded = random_nonzero_elements_with(&mut rng, 100);
+        let mut rng = StdRng::seed_from_u64(0);
+        assert_eq!(random_nonzero_elements_with(&mut rng, 100), seeded);
+    }
+}
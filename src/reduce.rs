@@ -0,0 +1,423 @@
+//! Reductions of wide products into `u64` residues
+
+use crate::goldilocks::P64;
+
+/// Assume that x consists of four 32-bit values: a, b, c, d:
+///
+/// - a contains 32 least significant bits,
+/// - d contains 32 most significant bits.
+///
+/// x is broken into corresponding values as shown below
+#[inline(always)]
+pub(crate) const fn reduce159(x: u128) -> u64 {
+    reduce159_limbs(x as u64, (x >> 64) as u64)
+}
+
+/// `reduce159` on x given as its low half ab and high half cd
+#[inline(always)]
+pub(crate) const fn reduce159_limbs(ab: u64, cd: u64) -> u64 {
+    const LOWER_MASK: u64 = 0xffff_ffff;
+
+    let c = (cd as u32) as u64;
+    let d = cd >> 32;
+
+    // compute ab - d; because d may be greater than ab, handle potential underflow
+    let (tmp0, is_under) = ab.overflowing_sub(d);
+    let tmp1 = tmp0.wrapping_sub(LOWER_MASK * (is_under as u64));
+
+    // compute c * 2^32 - c; this is guaranteed not to underflow
+    let tmp2 = (c << 32) - c;
+
+    // add temp values and return the result; because each of the temp may be up to 64 bits,
+    // handle potential overflow
+    let (result, is_over) = tmp1.overflowing_add(tmp2);
+    result.wrapping_add(LOWER_MASK * (is_over as u64))
+}
+
+/// Reduces an `x` below 2^96, such as a product of a `u64` and a `u32`, to its canonical residue
+///
+/// With the top 32 bits known to be zero, the `2^96 ≡ -1` term of `reduce159`
+/// disappears and only the `2^64 ≡ 2^32 - 1` fold remains.
+#[inline(always)]
+pub const fn reduce96(x: u128) -> u64 {
+    const LOWER_MASK: u64 = 0xffff_ffff;
+
+    debug_assert!(x >> 96 == 0, "input must fit in 96 bits");
+    let lo = x as u64;
+    let c = (x >> 64) as u64;
+
+    // c * 2^32 - c < 2^64; on overflow, fold 2^64 back in as 2^32 - 1
+    let (result, is_over) = lo.overflowing_add((c << 32) - c);
+    to_canonical(result.wrapping_add(LOWER_MASK * (is_over as u64)))
+}
+
+/// Reduces x, given as its 32-bit limbs from least significant, using only `u64` arithmetic
+///
+/// x ≡ a + (b + c) * 2^32 - c - d by 2^64 ≡ 2^32 - 1 and 2^96 ≡ -1; the bit
+/// carried out of b + c folds in as 2^32 - 1 once more.
+#[inline(always)]
+pub const fn reduce_limbs32(limbs: [u32; 4]) -> u64 {
+    const LOWER_MASK: u64 = 0xffff_ffff;
+
+    let [a, b, c, d] = limbs;
+    let (a, b, c, d) = (a as u64, b as u64, c as u64, d as u64);
+
+    let s = b + c;
+    let pos = ((s & LOWER_MASK) << 32) | a;
+    let (tmp0, is_over) = pos.overflowing_add((s >> 32) * LOWER_MASK);
+    let tmp1 = tmp0.wrapping_add(LOWER_MASK * (is_over as u64));
+
+    let (tmp2, is_under) = tmp1.overflowing_sub(c + d);
+    to_canonical(tmp2.wrapping_sub(LOWER_MASK * (is_under as u64)))
+}
+
+#[inline(always)]
+pub(crate) const fn reduce_montgomery(x: u128) -> u64 {
+    // See reference above for a description of the following implementation.
+    let xl = x as u64;
+    let xh = (x >> 64) as u64;
+    let (a, e) = xl.overflowing_add(xl << 32);
+
+    let b = a.wrapping_sub(a >> 32).wrapping_sub(e as u64);
+
+    let (r, c) = xh.overflowing_sub(b);
+    r.wrapping_sub(0u32.wrapping_sub(c as u32) as u64)
+}
+
+#[inline(always)]
+pub const fn montgomery_equals(lhs: u64, rhs: u64) -> bool {
+    let t = lhs ^ rhs;
+    0xffffffffffffffff == !((((t | t.wrapping_neg()) as i64) >> 63) as u64)
+}
+
+/// Returns whether `x` is a canonical residue, i.e. less than `P64`
+#[inline(always)]
+pub const fn is_canonical(x: u64) -> bool {
+    x < P64
+}
+
+/// Reduces any `u64`, including those in `[P64, 2^64)`, to its canonical residue
+#[inline(always)]
+pub const fn to_canonical(x: u64) -> u64 {
+    // 2 * P64 > 2^64, so a single subtraction suffices
+    if x >= P64 {
+        x - P64
+    } else {
+        x
+    }
+}
+
+/// Reduces any `u64` to its canonical residue like `to_canonical`, selecting with a mask
+#[inline(always)]
+pub const fn to_canonical_branchless(x: u64) -> u64 {
+    let (reduced, borrow) = x.overflowing_sub(P64);
+    reduced.wrapping_add(P64 & (borrow as u64).wrapping_neg())
+}
+
+/// Reduces any `u128` to its canonical residue
+#[inline(always)]
+pub const fn reduce_u64(x: u128) -> u64 {
+    to_canonical(reduce159(x))
+}
+
+/// A reduction from `u128` to the canonical residue, for selecting one at runtime
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ReduceStrategy {
+    /// The `%` operator
+    Modulo,
+    /// [`reduce_u64`]
+    Reduce159,
+    /// [`reduce_barrett`]
+    Barrett,
+    /// [`reduce_crandall`]
+    Crandall,
+    /// [`reduce_limbs32`]
+    Limbs32,
+}
+
+impl ReduceStrategy {
+    /// Every strategy, in declaration order
+    pub const ALL: [Self; 5] = [
+        Self::Modulo,
+        Self::Reduce159,
+        Self::Barrett,
+        Self::Crandall,
+        Self::Limbs32,
+    ];
+}
+
+/// Reduces any `u128` to its canonical residue with the given strategy
+///
+/// Montgomery and Plantard reduction are left out, since they return x scaled
+/// by a power of two rather than x itself.
+#[inline]
+pub const fn reduce128(x: u128, strategy: ReduceStrategy) -> u64 {
+    match strategy {
+        ReduceStrategy::Modulo => (x % P64 as u128) as u64,
+        ReduceStrategy::Reduce159 => reduce_u64(x),
+        ReduceStrategy::Barrett => reduce_barrett(x),
+        ReduceStrategy::Crandall => reduce_crandall(x),
+        ReduceStrategy::Limbs32 => reduce_limbs32([
+            x as u32,
+            (x >> 32) as u32,
+            (x >> 64) as u32,
+            (x >> 96) as u32,
+        ]),
+    }
+}
+
+/// Computes floor(2^128 / p) for Barrett reduction
+pub const fn barrett_mu(p: u64) -> u128 {
+    assert!(p > 2 && p % 2 == 1, "modulus must be an odd prime");
+    // p is odd, so it never divides 2^128 and floor((2^128 - 1) / p) is exact
+    u128::MAX / p as u128
+}
+
+/// floor(2^128 / p) = 2^64 + 2^32 - 1, the Barrett constant for `P64`
+pub const BARRETT_MU: u128 = 0x1_0000_0000_ffff_ffff;
+
+/// Reduces any `u128` to its canonical residue using Barrett reduction
+///
+/// The quotient estimate from the high half of x * μ is short by at most one,
+/// so a single conditional subtraction remains.
+#[inline(always)]
+pub const fn reduce_barrett(x: u128) -> u64 {
+    let q = mul_hi_u128(x, BARRETT_MU);
+    let r = x - q * P64 as u128;
+    if r >= P64 as u128 {
+        (r - P64 as u128) as u64
+    } else {
+        r as u64
+    }
+}
+
+/// ε = 2^64 - p = 2^32 - 1, so that 2^64 ≡ ε
+pub const EPSILON: u64 = 0xffff_ffff;
+
+/// Reduces any `u128` to its canonical residue using Crandall reduction
+///
+/// Folds the high word in as hi * ε with a multiplication, where `reduce159`
+/// uses shifts and subtractions; the second fold of at most 2^32 * ε fits a
+/// `u64`, and a final carry is folded in as ε once more.
+#[inline(always)]
+pub const fn reduce_crandall(x: u128) -> u64 {
+    let (lo, hi) = (x as u64, (x >> 64) as u64);
+
+    // lo + hi * ε < 2^64 + 2^96, so its high word is at most 2^32
+    let t = lo as u128 + hi as u128 * EPSILON as u128;
+    let (t_lo, t_hi) = (t as u64, (t >> 64) as u64);
+
+    let (result, is_over) = t_lo.overflowing_add(t_hi * EPSILON);
+    to_canonical(result.wrapping_add(EPSILON * (is_over as u64)))
+}
+
+/// p^-1 mod 2^128 = 2^128 - 2^96 + 2^32 + 1, for Plantard reduction
+pub const PLANTARD_INV: u128 = 0xffff_ffff_0000_0000_0000_0001_0000_0001;
+
+/// Computes z * 2^-32 mod p for any signed z, as a balanced representative
+///
+/// This is signed Plantard reduction with 64-bit words, where -2^-128 ≡ 2^-32.
+/// Goldilocks exceeds Plantard's bound on the modulus for this word size, so
+/// the quotient estimate may be off by one either way and is corrected exactly.
+#[inline(always)]
+pub const fn reduce_plantard(z: i128) -> i64 {
+    let m = z.wrapping_mul(PLANTARD_INV as i128);
+    let (m1, m0) = ((m >> 64) as i64, m as u64);
+
+    // K = (m * p - z) / 2^128 is exact and in [-(p - 1) / 2, (p - 1) / 2]
+    let r = (((m1 as i128 + 1) * P64 as i128) >> 64) as i64;
+
+    // r - K = floor((p * (2^64 - m0) + z) / 2^128), which is -1, 0 or 1
+    let u = ((P64 as u128) << 64) - P64 as u128 * m0 as u128;
+    let (_, carry) = u.overflowing_add(z as u128);
+    r - carry as i64 + (z < 0) as i64
+}
+
+/// Computes p^-1 mod 2^64 by Newton iteration, for Montgomery reduction
+pub const fn montgomery_inv(p: u64) -> u64 {
+    assert!(p > 2 && p % 2 == 1, "modulus must be an odd prime");
+    // each iteration doubles the number of correct low bits, starting from 3
+    let mut inv = p;
+    let mut i = 0;
+    while i < 5 {
+        inv = inv.wrapping_mul(2u64.wrapping_sub(p.wrapping_mul(inv)));
+        i += 1;
+    }
+    inv
+}
+
+/// Computes (2^64)^2 mod p, used to move canonical values into Montgomery form
+pub const fn montgomery_r2(p: u64) -> u64 {
+    assert!(p > 2 && p % 2 == 1, "modulus must be an odd prime");
+    ((u128::MAX % p as u128 + 1) % p as u128) as u64
+}
+
+/// High 128 bits of the 256-bit product of `a` and `b`
+#[inline(always)]
+pub(crate) const fn mul_hi_u128(a: u128, b: u128) -> u128 {
+    const LO: u128 = u64::MAX as u128;
+    let (a0, a1) = (a & LO, a >> 64);
+    let (b0, b1) = (b & LO, b >> 64);
+
+    let p00 = a0 * b0;
+    let p01 = a0 * b1;
+    let p10 = a1 * b0;
+    let p11 = a1 * b1;
+
+    let mid = (p00 >> 64) + (p01 & LO) + (p10 & LO);
+    p11 + (p01 >> 64) + (p10 >> 64) + (mid >> 64)
+}
+
+/// Reduces any `u64` to its canonical residue in constant time
+///
+/// Unlike [`to_canonical`], which may compile to a branch, this selects the
+/// result without a data-dependent branch.
+#[cfg(feature = "subtle")]
+#[inline(always)]
+pub fn conditional_reduce(x: u64) -> u64 {
+    use subtle::ConditionallySelectable;
+
+    let (reduced, borrow) = x.overflowing_sub(P64);
+    u64::conditional_select(&reduced, &x, (borrow as u8).into())
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+    use crate::goldilocks::P128;
+    use crate::rand::random_elements;
+
+    #[test]
+    fn canonicalization() {
+        assert!(is_canonical(0));
+        assert!(is_canonical(P64 - 1));
+        assert!(!is_canonical(P64));
+        assert!(!is_canonical(u64::MAX));
+
+        assert_eq!(to_canonical(P64 - 1), P64 - 1);
+        assert_eq!(to_canonical(P64), 0);
+        assert_eq!(to_canonical(u64::MAX), u64::MAX - P64);
+        for x in [0, 1, P64 - 1, P64, P64 + 1, u64::MAX] {
+            assert_eq!(to_canonical_branchless(x), to_canonical(x));
+        }
+
+        for x in [
+            0,
+            1,
+            P128 - 1,
+            P128,
+            u64::MAX as u128,
+            P128 * P128,
+            u128::MAX,
+        ] {
+            assert_eq!(reduce_u64(x) as u128, x % P128);
+        }
+        for (&x, &y) in random_elements(1_000).iter().tuple_windows() {
+            let x = (x as u128) << 64 | y as u128;
+            assert_eq!(reduce_u64(x) as u128, x % P128);
+        }
+    }
+
+    #[test]
+    fn strategy_selection() {
+        let edge_cases = [0, 1, P128 - 1, P128, P128 * P128, u128::MAX];
+        let random = random_elements(1_000)
+            .into_iter()
+            .tuple_windows()
+            .map(|(x, y)| (x as u128) << 64 | y as u128);
+        for x in random.chain(edge_cases) {
+            for strategy in ReduceStrategy::ALL {
+                assert_eq!(reduce128(x, strategy) as u128, x % P128, "{strategy:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn crandall_reduction() {
+        assert_eq!(EPSILON, 0u64.wrapping_sub(P64));
+        for x in [0, 1, P128 - 1, P128, P128 * P128, u128::MAX - 1, u128::MAX] {
+            assert_eq!(reduce_crandall(x) as u128, x % P128);
+        }
+        for (&x, &y) in random_elements(1_000).iter().tuple_windows() {
+            let x = (x as u128) << 64 | y as u128;
+            assert_eq!(reduce_crandall(x), reduce_u64(x));
+        }
+    }
+
+    #[test]
+    fn plantard_reduction() {
+        use num_bigint::BigInt;
+
+        let p = BigInt::from(P64);
+        assert_eq!((P64 as u128).wrapping_mul(PLANTARD_INV), 1);
+
+        let edge_cases = [0, 1, -1, P128 as i128, i128::MAX, i128::MIN];
+        let random = random_elements(1_000)
+            .into_iter()
+            .tuple_windows()
+            .map(|(x, y)| ((x as u128) << 64 | y as u128) as i128);
+        for z in random.chain(edge_cases) {
+            let k = reduce_plantard(z);
+            assert!(k.unsigned_abs() <= P64 / 2, "reduce_plantard({z}) = {k}");
+            let scaled = (BigInt::from(k) << 32) - BigInt::from(z);
+            assert_eq!(scaled % &p, BigInt::from(0), "reduce_plantard({z}) = {k}");
+        }
+    }
+
+    #[test]
+    fn reduction_below_2_96() {
+        let max = (1u128 << 96) - 1;
+        for x in [0, 1, P128 - 1, P128, u64::MAX as u128, P128 << 32, max] {
+            assert_eq!(reduce96(x) as u128, x % P128);
+        }
+        for (&x, &y) in random_elements(1_000).iter().tuple_windows() {
+            let x = (x as u128) << 32 | y as u128 >> 32;
+            assert_eq!(reduce96(x), reduce_u64(x));
+        }
+    }
+
+    #[test]
+    fn limb_reduction() {
+        fn limbs(x: u128) -> [u32; 4] {
+            [
+                x as u32,
+                (x >> 32) as u32,
+                (x >> 64) as u32,
+                (x >> 96) as u32,
+            ]
+        }
+
+        let max = u32::MAX;
+        for limbs in [
+            [0; 4],
+            [max; 4],
+            [max, max, 0, 0],
+            [0, max, max, 0],
+            [0, 0, 1, max],
+        ] {
+            let x = limbs
+                .iter()
+                .rev()
+                .fold(0u128, |acc, &limb| acc << 32 | limb as u128);
+            assert_eq!(reduce_limbs32(limbs) as u128, x % P128);
+        }
+        for (&x, &y) in random_elements(1_000).iter().tuple_windows() {
+            let x = (x as u128) << 64 | y as u128;
+            assert_eq!(reduce_limbs32(limbs(x)), reduce_u64(x));
+        }
+    }
+
+    #[test]
+    fn barrett_reduction() {
+        assert_eq!(BARRETT_MU, barrett_mu(P64));
+        for x in [0, 1, P128 - 1, P128, P128 * P128, u128::MAX - 1, u128::MAX] {
+            assert_eq!(reduce_barrett(x) as u128, x % P128);
+        }
+        for (&x, &y) in random_elements(1_000).iter().tuple_windows() {
+            let x = (x as u128) << 64 | y as u128;
+            assert_eq!(reduce_barrett(x), reduce_u64(x));
+        }
+    }
+}
@@ -0,0 +1,104 @@
+//! The secp256k1 base field `P` = 2^256 - 2^32 - 977 over four 64-bit limbs
+//!
+//! Since 2^256 ≡ 2^32 + 977, the high half of a product folds onto the low
+//! half after one multiplication by that small constant, so canonical values
+//! need no Montgomery form. [`FIELD`] provides generic Montgomery operations
+//! to compare against.
+
+use crate::meta::FieldMeta;
+use crate::multilimb::{adc, geq, mac, mul_wide4, sbb, MultiLimbModulus};
+
+/// 2^256 - 2^32 - 977, least significant limb first
+pub const MODULUS: [u64; 4] = [0xffff_fffe_ffff_fc2f, u64::MAX, u64::MAX, u64::MAX];
+
+/// The description of the field
+pub const META: FieldMeta = FieldMeta::new("secp256k1", &MODULUS, Some(3));
+
+/// 2^256 mod p = 2^32 + 977
+pub const C: u64 = 0x1_0000_03d1;
+
+/// The generic Montgomery constants and operations for `MODULUS`
+pub const FIELD: MultiLimbModulus<4> = MultiLimbModulus::new(MODULUS);
+
+/// Reduces any 512-bit `x` to its canonical residue
+///
+/// The high half times c leaves a carry limb below 2^34, which is folded in
+/// times c once more. Wrapping past 2^256 then leaves a small value, to which
+/// c is added back.
+#[inline]
+pub const fn reduce(x: &[u64; 8]) -> [u64; 4] {
+    let mut t = [0u64; 4];
+    let mut carry = 0;
+    let mut i = 0;
+    while i < 4 {
+        (t[i], carry) = mac(x[i], x[i + 4], C, carry);
+        i += 1;
+    }
+
+    let fold = carry as u128 * C as u128;
+    let (t_0, carry) = adc(t[0], fold as u64, 0);
+    let (t_1, carry) = adc(t[1], (fold >> 64) as u64, carry);
+    let (t_2, carry) = adc(t[2], 0, carry);
+    let (t_3, carry) = adc(t[3], 0, carry);
+    let t = [t_0, t_1, t_2, t_3];
+
+    if carry != 0 {
+        let (t_0, carry) = adc(t[0], C, 0);
+        let (t_1, carry) = adc(t[1], 0, carry);
+        [t_0, t_1, t[2], t[3] + carry]
+    } else if geq(&t, &MODULUS) {
+        sbb(&t, &MODULUS).0
+    } else {
+        t
+    }
+}
+
+/// Multiplies two canonical values using the pseudo-Mersenne `reduce`
+#[inline]
+pub const fn mul(x: &[u64; 4], y: &[u64; 4]) -> [u64; 4] {
+    reduce(&mul_wide4(x, y))
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use ::rand::{thread_rng, Rng};
+    use num_bigint::BigUint;
+
+    use super::*;
+
+    fn to_biguint<const N: usize>(x: &[u64; N]) -> BigUint {
+        x.iter()
+            .rev()
+            .fold(BigUint::default(), |acc, &limb| (acc << 64u32) + limb)
+    }
+
+    #[test]
+    fn constants() {
+        let p = (BigUint::from(1u32) << 256u32) - (1u64 << 32) - 977u32;
+        assert_eq!(to_biguint(&MODULUS), p);
+        assert_eq!((META.bits, META.two_adicity), (256, 1));
+        assert_eq!(BigUint::from(C), (BigUint::from(1u32) << 256u32) % &p);
+    }
+
+    #[test]
+    fn reduction() {
+        let p = to_biguint(&MODULUS);
+        let mut rng = thread_rng();
+        let max = [MODULUS[0] - 1, MODULUS[1], MODULUS[2], MODULUS[3]];
+        let mut random = || core::array::from_fn::<u64, 4, _>(|_| rng.gen());
+        for _ in 0..1_000 {
+            let (x, y) = (random(), random());
+            for (x, y) in [(x, y), (max, y), (max, max), ([0; 4], y)] {
+                let expected = to_biguint(&x) * to_biguint(&y) % &p;
+                assert_eq!(to_biguint(&mul(&x, &y)), expected);
+
+                let (x_m, y_m) = (FIELD.to_montgomery(&x), FIELD.to_montgomery(&y));
+                let product = FIELD.from_montgomery(&FIELD.mul_cios(&x_m, &y_m));
+                assert_eq!(to_biguint(&product), expected);
+            }
+        }
+        for x in [[0; 8], [u64::MAX; 8]] {
+            assert_eq!(to_biguint(&reduce(&x)), to_biguint(&x) % &p);
+        }
+    }
+}
@@ -0,0 +1,162 @@
+//! Fold-based reduction for pseudo-Mersenne and Solinas primes p = 2^N - c
+
+use crate::meta::{ConstName, FieldMeta, Meta};
+use crate::traits::PrimeField64;
+
+/// Computes c = Σ coefficient * 2^exponent from Solinas terms
+///
+/// For example, Goldilocks is 2^64 - 2^32 + 1 = 2^64 - (2^32 - 1), so its
+/// terms are `[(1, 32), (-1, 0)]`.
+pub const fn solinas_c(terms: &[(i64, u32)]) -> u64 {
+    let mut c = 0i128;
+    let mut i = 0;
+    while i < terms.len() {
+        c += terms[i].0 as i128 * (1i128 << terms[i].1);
+        i += 1;
+    }
+    assert!(c > 0 && c <= u64::MAX as i128, "c must be a positive u64");
+    c as u64
+}
+
+/// Computes how many folds x ≡ (x mod 2^n) + (x >> n) * c bring a product of
+/// two residues below 2p
+pub const fn fold_count(n: u32, c: u64) -> u32 {
+    let p = (1u128 << n) - c as u128;
+    let mut bound = (p - 1) * (p - 1);
+    let mut folds = 0;
+    while bound >= 2 * p {
+        assert!(folds < 8, "c is too large for fold-based reduction");
+        bound = ((1u128 << n) - 1) + (bound >> n) * c as u128;
+        folds += 1;
+    }
+    folds
+}
+
+/// A prime field modulo p = 2^N - C, reduced by folding with C
+///
+/// The number of folds is fixed at compile time from the worst-case product,
+/// so any candidate prime of this form can be benchmarked without writing its
+/// reduction by hand. Values are canonical.
+///
+/// ```
+/// use prime_field::{solinas_c, PrimeField64, PseudoMersenne};
+///
+/// type Goldilocks = PseudoMersenne<64, { solinas_c(&[(1, 32), (-1, 0)]) }>;
+/// assert_eq!(Goldilocks::MODULUS, prime_field::P64);
+/// assert_eq!(Goldilocks::mul(1 << 32, 1 << 32), (1 << 32) - 1);
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct PseudoMersenne<const N: u32, const C: u64>;
+
+impl<const N: u32, const C: u64> PseudoMersenne<N, C> {
+    /// The number of folds `reduce` performs
+    pub const FOLDS: u32 = fold_count(N, C);
+
+    const MASK: u128 = (1 << N) - 1;
+
+    const FIELD_NAME: ConstName = ConstName::new("2^")
+        .push_radix(N as u64, 10)
+        .push_str("-")
+        .push_radix(C, 10);
+    const STRATEGY_NAME: ConstName = Self::FIELD_NAME.push_str("/pseudo_mersenne");
+
+    /// Reduces a product of two canonical values modulo p by folding
+    #[inline(always)]
+    pub const fn reduce_fold(x: u128) -> u64 {
+        let mut x = x;
+        let mut i = 0;
+        while i < Self::FOLDS {
+            x = (x & Self::MASK) + (x >> N) * C as u128;
+            i += 1;
+        }
+        let p = Self::MODULUS as u128;
+        if x >= p {
+            (x - p) as u64
+        } else {
+            x as u64
+        }
+    }
+}
+
+impl<const N: u32, const C: u64> PrimeField64 for PseudoMersenne<N, C> {
+    const MODULUS: u64 = {
+        assert!(N >= 2 && N <= 64, "N must be between 2 and 64");
+        assert!((C as u128) < 1 << (N - 1), "c must be below 2^(N - 1)");
+        ((1u128 << N) - C as u128) as u64
+    };
+    const NAME: &'static str = Self::STRATEGY_NAME.as_str();
+
+    #[inline(always)]
+    fn add(x: u64, y: u64) -> u64 {
+        let (sum, over) = x.overflowing_add(y);
+        if over || sum >= Self::MODULUS {
+            sum.wrapping_sub(Self::MODULUS)
+        } else {
+            sum
+        }
+    }
+
+    #[inline(always)]
+    fn reduce(x: u128) -> u64 {
+        debug_assert!(
+            x <= (Self::MODULUS as u128 - 1).pow(2),
+            "input must be a product of residues"
+        );
+        Self::reduce_fold(x)
+    }
+}
+
+/// No generator is known for arbitrary N and C, since finding one needs the
+/// factors of p - 1, so `META.generator` is `None`.
+impl<const N: u32, const C: u64> Meta for PseudoMersenne<N, C> {
+    const META: FieldMeta = FieldMeta::new(Self::FIELD_NAME.as_str(), &[Self::MODULUS], None);
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+    use crate::goldilocks::{mul_modulo, P64};
+
+    #[test]
+    fn terms() {
+        assert_eq!(solinas_c(&[(1, 32), (-1, 0)]), (1 << 32) - 1);
+        assert_eq!(solinas_c(&[(1, 8), (1, 0)]), 257);
+        assert_eq!(fold_count(61, 1), 1);
+        assert_eq!(fold_count(64, (1 << 32) - 1), 2);
+        assert_eq!(PseudoMersenne::<64, 59>::NAME, "2^64-59/pseudo_mersenne");
+        assert_eq!(PseudoMersenne::<61, 1>::META.name, "2^61-1");
+    }
+
+    #[test]
+    fn reductions() {
+        fn check<const N: u32, const C: u64>() {
+            type F<const N: u32, const C: u64> = PseudoMersenne<N, C>;
+            let p = F::<N, C>::MODULUS;
+            let edge_cases = [0, 1, p - 1, p - 1];
+            for (&x, &y) in F::<N, C>::random(1_000)
+                .iter()
+                .chain(&edge_cases)
+                .tuple_windows()
+            {
+                let expected = (x as u128 * y as u128 % p as u128) as u64;
+                assert_eq!(F::<N, C>::mul(x, y), expected);
+                assert_eq!(F::<N, C>::add(F::<N, C>::sub(x, y), y), x);
+            }
+        }
+        check::<64, { solinas_c(&[(1, 8), (1, 0)]) }>();
+        check::<64, { solinas_c(&[(1, 32), (-1, 0)]) }>();
+        check::<64, 59>();
+        check::<63, 25>();
+        check::<61, 1>();
+        check::<31, { solinas_c(&[(1, 27), (-1, 0)]) }>();
+
+        type Goldilocks = PseudoMersenne<64, { solinas_c(&[(1, 32), (-1, 0)]) }>;
+        assert_eq!(Goldilocks::MODULUS, P64);
+        assert_eq!(
+            Goldilocks::mul(P64 - 1, P64 - 1),
+            mul_modulo(P64 - 1, P64 - 1)
+        );
+    }
+}
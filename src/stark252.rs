@@ -0,0 +1,86 @@
+//! The Stark252 field `P` = 2^251 + 17 * 2^192 + 1 over four 64-bit limbs
+//!
+//! Elements are `[u64; 4]` in Montgomery form, least significant limb first.
+//! [`FIELD`] provides the generic [`MultiLimbModulus`] operations, and [`mul`]
+//! a CIOS multiplication specialized to the shape of p.
+
+use crate::meta::FieldMeta;
+use crate::multilimb::{adc, geq, mac, sbb, MultiLimbModulus};
+
+/// 2^251 + 17 * 2^192 + 1, least significant limb first
+pub const MODULUS: [u64; 4] = [1, 0, 0, 0x0800_0000_0000_0011];
+
+/// The description of the field
+pub const META: FieldMeta = FieldMeta::new("stark252", &MODULUS, Some(3));
+
+/// The generic Montgomery constants and operations for `MODULUS`
+pub const FIELD: MultiLimbModulus<4> = MultiLimbModulus::new(MODULUS);
+
+/// Multiplies two values in Montgomery form, like `FIELD.mul_cios`
+///
+/// Since p ≡ 1 mod 2^64, the reduction factor for each limb is its negation,
+/// and adding it times p only touches the lowest and the top limb.
+#[inline]
+pub const fn mul(x: &[u64; 4], y: &[u64; 4]) -> [u64; 4] {
+    let mut t = [0u64; 4];
+    let mut t_4 = 0;
+    let mut i = 0;
+    while i < 4 {
+        let mut carry = 0;
+        let mut j = 0;
+        while j < 4 {
+            (t[j], carry) = mac(t[j], x[j], y[i], carry);
+            j += 1;
+        }
+        let t_5;
+        (t_4, t_5) = adc(t_4, carry, 0);
+
+        let m = t[0].wrapping_neg();
+        let (_, carry) = adc(t[0], m, 0);
+        let (t_0, carry) = adc(t[1], carry, 0);
+        let (t_1, carry) = adc(t[2], carry, 0);
+        let (t_2, carry) = mac(t[3], m, MODULUS[3], carry);
+        (t[3], t_4) = adc(t_4, carry, 0);
+        (t[0], t[1], t[2]) = (t_0, t_1, t_2);
+        t_4 += t_5;
+        i += 1;
+    }
+
+    if t_4 != 0 || geq(&t, &MODULUS) {
+        sbb(&t, &MODULUS).0
+    } else {
+        t
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use ::rand::{thread_rng, Rng};
+
+    use super::*;
+
+    #[test]
+    fn constants() {
+        assert_eq!((META.bits, META.two_adicity), (252, 192));
+        assert_eq!(FIELD.neg_inv, u64::MAX);
+        assert!(FIELD.supports_relaxed());
+    }
+
+    #[test]
+    fn specialized_mul() {
+        let mut rng = thread_rng();
+        let mut random = || {
+            let mut x = core::array::from_fn::<u64, 4, _>(|_| rng.gen());
+            x[3] %= MODULUS[3];
+            x
+        };
+        let max = [0, 0, 0, MODULUS[3]];
+        for _ in 0..1_000 {
+            let (x, y) = (random(), random());
+            for (x, y) in [(x, y), (max, y), (max, max), ([0; 4], y)] {
+                let (x_m, y_m) = (FIELD.to_montgomery(&x), FIELD.to_montgomery(&y));
+                assert_eq!(mul(&x_m, &y_m), FIELD.mul_cios(&x_m, &y_m));
+            }
+        }
+    }
+}
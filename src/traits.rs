@@ -0,0 +1,149 @@
+//! Traits shared by every field implementation
+
+use core::fmt;
+
+#[cfg(feature = "std")]
+use ::rand::{thread_rng, Rng};
+
+/// A prime field over `u64` residues, implemented with one particular strategy
+///
+/// Operands and results are canonical values in the implementation's own
+/// representation, so benchmarks can be written once and run against every
+/// implementor.
+pub trait PrimeField64 {
+    /// The prime modulus
+    const MODULUS: u64;
+
+    /// Short name used to label benchmarks
+    const NAME: &'static str;
+
+    /// Adds two field elements
+    fn add(x: u64, y: u64) -> u64;
+
+    /// Subtracts two field elements
+    #[inline(always)]
+    fn sub(x: u64, y: u64) -> u64 {
+        Self::add(x, if y == 0 { 0 } else { Self::MODULUS - y })
+    }
+
+    /// Multiplies two field elements
+    #[inline(always)]
+    fn mul(x: u64, y: u64) -> u64 {
+        Self::reduce(x as u128 * y as u128)
+    }
+
+    /// Computes a * b + c
+    #[inline(always)]
+    fn mul_add(a: u64, b: u64, c: u64) -> u64 {
+        Self::add(Self::mul(a, b), c)
+    }
+
+    /// Squares a field element
+    #[inline(always)]
+    fn square(x: u64) -> u64 {
+        Self::mul(x, x)
+    }
+
+    /// Reduces a product of two field elements
+    fn reduce(x: u128) -> u64;
+
+    /// Samples `n` uniformly random field elements
+    #[cfg(feature = "std")]
+    fn random(n: usize) -> Vec<u64> {
+        (0..n)
+            .map(|_| thread_rng().gen_range(0..Self::MODULUS))
+            .collect::<Vec<_>>()
+    }
+}
+
+/// Performs `F::add`, asserting in debug builds that operands and result are canonical
+#[inline(always)]
+pub fn add_checked<F: PrimeField64>(x: u64, y: u64) -> u64 {
+    debug_assert!(x < F::MODULUS, "non-canonical operand {x} for {}", F::NAME);
+    debug_assert!(y < F::MODULUS, "non-canonical operand {y} for {}", F::NAME);
+    let sum = F::add(x, y);
+    debug_assert!(sum < F::MODULUS, "non-canonical sum {sum} for {}", F::NAME);
+    sum
+}
+
+/// Performs `F::mul`, asserting in debug builds that operands and result are canonical
+#[inline(always)]
+pub fn mul_checked<F: PrimeField64>(x: u64, y: u64) -> u64 {
+    debug_assert!(x < F::MODULUS, "non-canonical operand {x} for {}", F::NAME);
+    debug_assert!(y < F::MODULUS, "non-canonical operand {y} for {}", F::NAME);
+    let product = F::mul(x, y);
+    debug_assert!(
+        product < F::MODULUS,
+        "non-canonical product {product} for {}",
+        F::NAME
+    );
+    product
+}
+
+/// Reduction strategy backing a [`FieldElement`](crate::FieldElement)
+///
+/// A strategy decides how field operations are carried out, and in which
+/// domain the element is stored. `to_repr` and `from_repr` convert between
+/// canonical residues and that domain; both are the identity unless overridden.
+///
+/// A new strategy is a marker type implementing [`PrimeField64`], of which only
+/// `add` and `reduce` are required, and `Reducer`; `FieldElement` and the
+/// generic benchmarks pick it up from there.
+pub trait Reducer: PrimeField64 + Copy + fmt::Debug + Default + Eq {
+    /// Factor by which canonical residues are scaled in this representation
+    const REPR_FACTOR: u64 = 1;
+
+    /// Converts a canonical residue into this strategy's representation
+    #[inline(always)]
+    fn to_repr(x: u64) -> u64 {
+        x
+    }
+
+    /// Converts this strategy's representation into a canonical residue
+    #[inline(always)]
+    fn from_repr(x: u64) -> u64 {
+        x
+    }
+}
+
+#[cfg(all(test, feature = "std"))]
+mod tests {
+    use itertools::Itertools;
+
+    use super::*;
+    use crate::generic::GenericPrimeField;
+    use crate::goldilocks::{Limbs32, Modulo, Montgomery, Reduce159, P64};
+
+    #[test]
+    fn checked_operations() {
+        fn check<F: PrimeField64>() {
+            let edge_cases = [0, 1, F::MODULUS - 1];
+            for (&x, &y) in F::random(1_000).iter().chain(&edge_cases).tuple_windows() {
+                assert_eq!(add_checked::<F>(x, y), F::add(x, y));
+                assert_eq!(F::add(F::sub(x, y), y), x);
+                assert_eq!(F::square(x), F::mul(x, x));
+                assert_eq!(F::mul_add(x, y, x), F::add(F::mul(x, y), x));
+                assert_eq!(mul_checked::<F>(x, y), F::mul(x, y));
+            }
+        }
+        check::<Modulo>();
+        check::<Reduce159>();
+        check::<Montgomery>();
+        check::<Limbs32>();
+        check::<GenericPrimeField<P64>>();
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "non-canonical operand")]
+    fn add_checked_rejects_non_canonical() {
+        add_checked::<Reduce159>(P64, 1);
+    }
+
+    #[cfg(debug_assertions)]
+    #[test]
+    #[should_panic(expected = "non-canonical operand")]
+    fn mul_checked_rejects_non_canonical() {
+        mul_checked::<Montgomery>(1, u64::MAX);
+    }
+}